@@ -0,0 +1,45 @@
+//! Demonstrates a message dialog showing a product logo (an SVG) instead of a standard icon, and a
+//! footer icon made of RGBA pixels.
+//!
+//! Run with: cargo run --example custom_icon
+
+use xdialog::*;
+
+const LOGO: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 64 64">
+  <defs>
+    <linearGradient id="sky" x1="0" y1="0" x2="0" y2="1">
+      <stop offset="0" stop-color="#5b8def"/>
+      <stop offset="1" stop-color="#2c4fb8"/>
+    </linearGradient>
+  </defs>
+  <rect x="2" y="2" width="60" height="60" rx="14" fill="url(#sky)"/>
+  <path d="M14 44 L28 22 L36 34 L42 26 L52 44 Z" fill="#ffffff" fill-opacity="0.9"/>
+  <circle cx="46" cy="17" r="5" fill="#ffd54f"/>
+</svg>"##;
+
+fn main() {
+    XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    // A 16 × 16 green dot with a soft edge, as straight RGBA pixels.
+    let dot: Vec<u8> = (0..16 * 16)
+        .flat_map(|i| {
+            let (x, y) = ((i % 16) as f32 - 7.5, (i / 16) as f32 - 7.5);
+            let alpha = ((7.5 - (x * x + y * y).sqrt()) * 255.0).clamp(0.0, 255.0) as u8;
+            [0x2e, 0x9e, 0x4f, alpha]
+        })
+        .collect();
+
+    let options = XDialogOptions {
+        title: "My App".to_string(),
+        main_instruction: "Welcome to My App".to_string(),
+        message: "This dialog shows the product logo instead of a standard icon.".to_string(),
+        icon: XDialogIcon::Custom(IconData::from_svg(LOGO)),
+        buttons: vec!["OK".to_string()],
+        footer_text: "Everything is up to date".to_string(),
+        footer_icon: XDialogIcon::Custom(IconData::from_rgba(16, 16, dot).unwrap()),
+        ..Default::default()
+    };
+    show_message(options, None).unwrap();
+}
@@ -0,0 +1,28 @@
+fn main() {
+    println!("Starting Example...");
+    xdialog::XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    let options = xdialog::XDialogOptions {
+        title: "Release channel".to_string(),
+        main_instruction: "Switch release channel".to_string(),
+        message: "Enter the name of the channel to receive updates from.".to_string(),
+        icon: xdialog::XDialogIcon::Information,
+        buttons: vec!["Cancel".to_string(), "Switch".to_string()],
+        ..Default::default()
+    };
+
+    let response = xdialog::show_input_with_validation(options, "stable", |text| {
+        if text.trim().is_empty() {
+            Err("The channel name can't be empty.".to_string())
+        } else if !text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            Err("Only letters, digits and '-' are allowed.".to_string())
+        } else {
+            Ok(())
+        }
+    })
+    .unwrap();
+
+    println!("Result: {:?}, text: {:?}", response.result, response.text);
+}
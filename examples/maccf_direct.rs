@@ -15,6 +15,7 @@ fn main() {
             message: "This dialog was shown without any event loop.\nPick an option:".to_string(),
             icon: xdialog::XDialogIcon::Information,
             buttons: vec!["Save".to_string(), "Discard".to_string(), "Cancel".to_string()],
+            ..Default::default()
         },
         None,
     )
@@ -0,0 +1,20 @@
+fn main() {
+    println!("Starting Example...");
+    xdialog::XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    let options = xdialog::XDialogOptions {
+        title: "Sign in".to_string(),
+        main_instruction: "Proxy authentication required".to_string(),
+        message: "Enter the password for proxy.example.com.".to_string(),
+        icon: xdialog::XDialogIcon::Information,
+        buttons: vec!["Cancel".to_string(), "Sign in".to_string()],
+        ..Default::default()
+    };
+
+    let response = xdialog::show_password(options).unwrap();
+
+    // The secret's Debug output is redacted, so this never prints the password.
+    println!("Result: {:?}, secret: {:?}", response.result, response.secret);
+}
@@ -11,6 +11,7 @@ fn run() {
     result.set_value(0.2).unwrap();
     std::thread::sleep(std::time::Duration::from_secs(1));
     result.set_value(0.4).unwrap();
+    result.set_status("/Applications/My Application.app/Contents/Resources/app/node_modules/some-package/dist/index.js").unwrap();
     std::thread::sleep(std::time::Duration::from_secs(1));
     result.set_value(0.6).unwrap();
     result.set_text("This is some long text which should wrap and cause the window size to be re-calculated.").unwrap();
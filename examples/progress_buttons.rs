@@ -1,9 +1,11 @@
 //! Demonstrates progress dialogs with custom buttons.
 //!
-//! - `show_progress_with_callback` adds a "Cancel" button and reacts to clicks, keeping the dialog
-//!   open to show a "Cancelling..." message until the work loop tears it down.
+//! - `show_progress_with_callbacks` adds a "Cancel" button and reacts to clicks and to the window
+//!   being closed, keeping the dialog open to show a "Cancelling..." message until the work loop
+//!   tears it down.
 //! - `show_progress_ex` adds a "Hide" button with no callback (clicking it simply closes the
 //!   dialog). On Windows this relabels the button that is always present on a progress dialog.
+//!   The work loop checks `is_closed` to stop early, then reports `close_reason`.
 //! - `show_progress` shows the original button-less dialog (unchanged behavior).
 //!
 //! Run with: cargo run --example progress_buttons
@@ -20,23 +22,33 @@ fn main() {
 
 fn run() {
     // 1. A cancellable operation. The "Cancel" button is shown on every platform, and the callback
-    //    is notified when it is clicked.
+    //    is notified when it is clicked. Closing the window cancels too, instead of hiding the
+    //    dialog while the work carries on.
     let cancelled = Arc::new(AtomicBool::new(false));
     let flag = cancelled.clone();
-    let progress = show_progress_with_callback(
+    let close_flag = cancelled.clone();
+    let progress = show_progress_with_callbacks(
         XDialogOptions {
             title: "Downloading".to_string(),
             main_instruction: "Downloading updates".to_string(),
             message: "Starting...".to_string(),
             icon: XDialogIcon::Information,
             buttons: vec!["Cancel".to_string()],
+            ..Default::default()
         },
-        move |button_index, proxy| {
-            println!("Progress button {} clicked -> cancelling", button_index);
-            flag.store(true, Ordering::SeqCst);
-            let _ = proxy.set_text("Cancelling, please wait...");
-            true // keep the dialog open until we tear it down ourselves
-        },
+        ProgressCallbacks::new()
+            .on_button(move |button_index, proxy| {
+                println!("Progress button {} clicked -> cancelling", button_index);
+                flag.store(true, Ordering::SeqCst);
+                let _ = proxy.set_text("Cancelling, please wait...");
+                true // keep the dialog open until we tear it down ourselves
+            })
+            .on_close_requested(move |proxy| {
+                println!("Progress window close requested -> cancelling");
+                close_flag.store(true, Ordering::SeqCst);
+                let _ = proxy.set_text("Cancelling, please wait...");
+                true
+            }),
     )
     .unwrap();
 
@@ -59,11 +71,18 @@ fn run() {
         message: "Click Hide to dismiss this window.".to_string(),
         icon: XDialogIcon::Information,
         buttons: vec!["Hide".to_string()],
+        ..Default::default()
     })
     .unwrap();
     hide.set_indeterminate().unwrap();
-    std::thread::sleep(Duration::from_secs(3));
+    for _ in 0..30 {
+        if hide.is_closed() {
+            break; // the user dismissed the dialog, stop early
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
     hide.close().unwrap();
+    println!("Background task dialog closed: {:?}", hide.close_reason());
 
     // 3. The original button-less progress dialog (unchanged behavior).
     let plain = show_progress("Finishing", "Almost done", "No buttons here.", XDialogIcon::None).unwrap();
@@ -0,0 +1,40 @@
+//! Demonstrates a progress dialog listing the steps of an update, marking each as it runs,
+//! finishes, or fails.
+//!
+//! Run with: cargo run --example progress_checklist
+
+use std::time::Duration;
+
+use xdialog::*;
+
+fn main() {
+    XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    let steps = ["Download", "Verify", "Extract", "Apply", "Restart"];
+    let options = XDialogOptions {
+        title: "My App".to_string(),
+        main_instruction: "Updating My App".to_string(),
+        icon: XDialogIcon::Information,
+        checklist: steps.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    };
+    let progress = show_progress_ex(options).unwrap();
+
+    for (i, step) in steps.iter().enumerate() {
+        progress.start_step(i).unwrap();
+        progress.set_status(format!("{}...", step)).unwrap();
+        for value in 0..=20 {
+            progress.set_value(value as f32 / 20.0).unwrap();
+            std::thread::sleep(Duration::from_millis(40));
+        }
+        if *step == "Apply" {
+            progress.set_step_state(i, ChecklistStepState::Failed).unwrap();
+            progress.set_state(ProgressBarState::Error).unwrap();
+            progress.set_status("The update could not be applied").unwrap();
+            break;
+        }
+    }
+    std::thread::sleep(Duration::from_secs(3));
+}
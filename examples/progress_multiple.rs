@@ -0,0 +1,35 @@
+//! Demonstrates a progress dialog with two captioned bars: the file being installed and the
+//! installation overall.
+//!
+//! Run with: cargo run --example progress_multiple
+
+use std::time::Duration;
+
+use xdialog::*;
+
+fn main() {
+    XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    let files = ["app.exe", "resources/app.asar", "resources/icons.dat", "update.exe"];
+    let options = XDialogOptions {
+        title: "My App Setup".to_string(),
+        main_instruction: "Installing My App".to_string(),
+        icon: XDialogIcon::Information,
+        progress_bars: vec!["Current file".to_string(), "Overall".to_string()],
+        ..Default::default()
+    };
+    let progress = show_progress_ex(options).unwrap();
+
+    for (i, file) in files.iter().enumerate() {
+        progress.bar(0).set_caption(format!("Copying {}", file)).unwrap();
+        for step in 0..=20 {
+            progress.bar(0).set_value(step as f32 / 20.0).unwrap();
+            progress.bar(1).set_value((i as f32 + step as f32 / 20.0) / files.len() as f32).unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+    progress.bar(0).set_caption("Done").unwrap();
+    std::thread::sleep(Duration::from_secs(1));
+}
@@ -0,0 +1,29 @@
+//! Demonstrates `ProgressTracker`, which turns a running byte count into a progress value and a
+//! "12.3 MB of 80 MB — 2 min remaining" status line.
+//!
+//! Run with: cargo run --example progress_tracker
+
+use std::time::Duration;
+
+use xdialog::*;
+
+fn main() {
+    XDialogBuilder::new().run(run);
+}
+
+fn run() {
+    let progress = show_progress("My App", "Downloading update", "", XDialogIcon::Information).unwrap();
+    let total = 80_000_000;
+    let mut tracker = ProgressTracker::new(progress, ProgressUnit::Bytes, total);
+
+    // Pretend to download in 64 kB chunks, slowing down halfway through.
+    let mut done = 0;
+    while done < total {
+        let chunk = 64_000.min(total - done);
+        done += chunk;
+        tracker.advance(chunk).unwrap();
+        let delay = if done < total / 2 { 1 } else { 3 };
+        std::thread::sleep(Duration::from_millis(delay));
+    }
+    std::thread::sleep(Duration::from_secs(1));
+}
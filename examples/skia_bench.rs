@@ -31,6 +31,7 @@ fn run() {
         message: "Running indeterminate animation...".into(),
         icon: XDialogIcon::Information,
         buttons: vec![],
+        ..Default::default()
     })
     .unwrap();
     p.set_indeterminate().unwrap();
@@ -99,6 +99,7 @@ fn run() {
                   Clock faces: 🕐🕑🕒🕓🕔🕕🕖🕗🕘🕙🕚🕛"
             .to_string(),
         buttons: vec!["Looks Good! 👍".to_string(), "Broken 💔".to_string()],
+        ..Default::default()
     };
     let _ = xdialog::show_message(data, None);
 
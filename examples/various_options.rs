@@ -17,6 +17,7 @@ fn run() {
         buttons: vec!["OK".to_string()],
         main_instruction: "This is a main instruction".to_string(),
         title: "This is a title".to_string(),
+        ..Default::default()
     };
     let _ = xdialog::show_message(data.clone(), None);
 
@@ -34,6 +35,48 @@ fn run() {
     data.title = "".to_string();
     let _ = xdialog::show_message(data.clone(), None);
 
+    data.message = small_text.to_string();
+    data.verification_text = "Don't show this message again".to_string();
+    let response = xdialog::show_message_ex(data.clone(), None).unwrap();
+    println!("Result: {:?}, checked: {:?}", response.result, response.checked);
+
+    data.verification_text = "".to_string();
+    data.main_instruction = "A new version is available".to_string();
+    data.radio_buttons = vec!["Update now".to_string(), "Update on exit".to_string(), "Skip this version".to_string()];
+    let response = xdialog::show_message_ex(data.clone(), None).unwrap();
+    println!("Result: {:?}, choice: {:?}", response.result, response.radio_index);
+
+    data.radio_buttons = vec![];
+    data.icon = XDialogIcon::Error;
+    data.main_instruction = "The update could not be installed".to_string();
+    data.expanded_details = "Error: failed to extract package\n  at extract (src/install.rs:42)\n  at apply (src/install.rs:17)"
+        .to_string();
+    let _ = xdialog::show_message(data.clone(), None);
+
+    data.expanded_details = "".to_string();
+    data.icon = XDialogIcon::Warning;
+    data.main_instruction = "Restart to finish updating?".to_string();
+    data.footer_text = "Your data will not be lost.".to_string();
+    data.footer_icon = XDialogIcon::Information;
+    let _ = xdialog::show_message(data.clone(), None);
+
+    data.footer_text = "".to_string();
+    data.icon = XDialogIcon::Information;
+    data.main_instruction = "Update installed".to_string();
+    data.message = "Read the <a href=\"https://github.com/velopack/velopack/releases\">release notes</a> to see what's new, \
+                    or visit <a href=\"https://velopack.io\">velopack.io</a>."
+        .to_string();
+    let _ = xdialog::show_message(data.clone(), None);
+
+    data.icon = XDialogIcon::Warning;
+    data.main_instruction = "Save changes before closing?".to_string();
+    data.message = small_text.to_string();
+    data.buttons = vec!["Cancel".to_string(), "Don't Save".to_string(), "Save".to_string()];
+    data.default_button = Some(2);
+    data.cancel_button = Some(0);
+    let result = xdialog::show_message(data.clone(), None).unwrap();
+    println!("Result: {:?}", result);
+
     let d = show_progress("Title", "This is an instruction", small_text, XDialogIcon::None).unwrap();
     d.set_indeterminate().unwrap();
     std::thread::sleep(std::time::Duration::from_secs(3));
@@ -54,4 +97,29 @@ fn run() {
     std::thread::sleep(std::time::Duration::from_secs(3));
     d.close().unwrap();
 
+    let d = show_progress("Title", "Downloading", small_text, XDialogIcon::Information).unwrap();
+    d.set_value(0.5).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    d.set_main_instruction("Installing").unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    d.update(xdialog::DialogPatch {
+        main_instruction: Some("The update could not be installed".to_string()),
+        icon: Some(XDialogIcon::Error),
+        ..Default::default()
+    })
+    .unwrap();
+    d.set_state(xdialog::ProgressBarState::Error).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    d.close().unwrap();
+
+    let d = show_progress("Title", "Download paused", small_text, XDialogIcon::Information).unwrap();
+    d.set_value(0.3).unwrap();
+    d.set_state(xdialog::ProgressBarState::Paused).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    d.set_main_instruction("Downloading").unwrap();
+    d.set_state(xdialog::ProgressBarState::Normal).unwrap();
+    d.set_value(0.8).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    d.close().unwrap();
+
 }
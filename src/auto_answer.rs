@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
+use crate::*;
+
+/// The environment variable read for an [`XDialogAutoAnswer`] policy when none is set in code, eg.
+/// `XDIALOG_AUTO_ANSWER=button:Yes`.
+pub const AUTO_ANSWER_ENV: &str = "XDIALOG_AUTO_ANSWER";
+
+/// How to answer dialogs without showing them, for unattended runs (eg. CI or configuration
+/// management) where a dialog would otherwise wait forever. Set it with
+/// [`XDialogBuilder::with_auto_answer`] or [`set_auto_answer`], or with the `XDIALOG_AUTO_ANSWER`
+/// environment variable (`default`, `cancel` or `button:<label>`) when neither is used.
+///
+/// Message and input dialogs report the chosen button, with their other contents (the text field,
+/// checkbox and radio buttons) left as they started; a password dialog reports no secret. Progress
+/// dialogs aren't shown, as in silent mode. Each answered dialog is logged at the `info` level.
+/// Silent mode (see [`set_silent_mode`]) takes precedence.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum XDialogAutoAnswer {
+    /// Press the default button (see [`XDialogOptions::default_button`]), or dismiss a dialog
+    /// without one as `Cancel` does.
+    Default,
+    /// Dismiss the dialog as with Escape: press the cancel button (see
+    /// [`XDialogOptions::cancel_button`]), or report `WindowClosed` without one.
+    Cancel,
+    /// Press the button with this label, ignoring ASCII case, or dismiss a dialog without one as
+    /// `Cancel` does.
+    Button(String),
+}
+
+impl XDialogAutoAnswer {
+    /// The result of answering a dialog shown with `options`, and why that button was chosen.
+    fn resolve(&self, options: &XDialogOptions) -> (XDialogResult, &'static str) {
+        match self {
+            XDialogAutoAnswer::Default => match options.default_button_index() {
+                Some(index) => (XDialogResult::ButtonPressed(index), "the default button"),
+                None => (options.dismiss_result(), "no default button, dismissed"),
+            },
+            XDialogAutoAnswer::Cancel => (options.dismiss_result(), "dismissed"),
+            XDialogAutoAnswer::Button(label) => match options.buttons.iter().position(|b| b.eq_ignore_ascii_case(label)) {
+                Some(index) => (XDialogResult::ButtonPressed(index), "the named button"),
+                None => (options.dismiss_result(), "no such button, dismissed"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for XDialogAutoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XDialogAutoAnswer::Default => f.write_str("default"),
+            XDialogAutoAnswer::Cancel => f.write_str("cancel"),
+            XDialogAutoAnswer::Button(label) => write!(f, "button:{}", label),
+        }
+    }
+}
+
+impl FromStr for XDialogAutoAnswer {
+    type Err = XDialogError;
+
+    /// Parses the format of the `XDIALOG_AUTO_ANSWER` environment variable: `default`, `cancel` or
+    /// `button:<label>` (the keywords ignoring ASCII case).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("default") {
+            return Ok(XDialogAutoAnswer::Default);
+        }
+        if s.eq_ignore_ascii_case("cancel") {
+            return Ok(XDialogAutoAnswer::Cancel);
+        }
+        match s.split_once(':') {
+            Some((keyword, label)) if keyword.trim().eq_ignore_ascii_case("button") && !label.trim().is_empty() => {
+                Ok(XDialogAutoAnswer::Button(label.trim().to_string()))
+            }
+            _ => Err(XDialogError::InvalidAutoAnswer(format!("{:?} (expected default, cancel or button:<label>)", s))),
+        }
+    }
+}
+
+static AUTO_ANSWER: RwLock<Option<XDialogAutoAnswer>> = RwLock::new(None);
+static ENV_AUTO_ANSWER: OnceLock<Option<XDialogAutoAnswer>> = OnceLock::new();
+
+/// Answer dialogs with `policy` without showing them, or show them again with `None`. This
+/// overrides the `XDIALOG_AUTO_ANSWER` environment variable; see [`XDialogAutoAnswer`].
+pub fn set_auto_answer(policy: Option<XDialogAutoAnswer>) {
+    *AUTO_ANSWER.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+/// The policy in effect: the one set in code, otherwise the one from the environment, which is
+/// read (and an invalid value logged) once.
+fn current_policy() -> Option<XDialogAutoAnswer> {
+    if let Some(policy) = AUTO_ANSWER.read().unwrap_or_else(|e| e.into_inner()).clone() {
+        return Some(policy);
+    }
+    ENV_AUTO_ANSWER
+        .get_or_init(|| {
+            let value = std::env::var(AUTO_ANSWER_ENV).ok().filter(|v| !v.trim().is_empty())?;
+            value.parse().map_err(|e| warn!("xdialog: ignoring {}: {}", AUTO_ANSWER_ENV, e)).ok()
+        })
+        .clone()
+}
+
+/// Whether dialogs are being answered without being shown.
+pub(crate) fn auto_answer_active() -> bool {
+    current_policy().is_some()
+}
+
+/// The response to a dialog shown with `options` under the auto-answer policy, or `None` if there is
+/// no policy and the dialog should be shown. Logs the answer.
+pub(crate) fn auto_answer(options: &XDialogOptions) -> Option<XDialogResponse> {
+    let policy = current_policy()?;
+    let (result, reason) = policy.resolve(options);
+    let label = match result {
+        XDialogResult::ButtonPressed(index) => options.buttons.get(index).map(String::as_str),
+        _ => None,
+    };
+    info!(
+        "xdialog: auto-answered dialog {:?} ({:?}) with {:?} {:?}: {} (policy {})",
+        options.title, options.main_instruction, result, label.unwrap_or(""), reason, policy
+    );
+    Some(XDialogResponse { result, ..options.silent_response() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(default_button: Option<usize>, cancel_button: Option<usize>) -> XDialogOptions {
+        XDialogOptions {
+            buttons: vec!["No".to_string(), "Yes".to_string(), "Later".to_string()],
+            default_button,
+            cancel_button,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_the_environment_format() {
+        assert_eq!("default".parse::<XDialogAutoAnswer>().unwrap(), XDialogAutoAnswer::Default);
+        assert_eq!(" Cancel ".parse::<XDialogAutoAnswer>().unwrap(), XDialogAutoAnswer::Cancel);
+        assert_eq!("button:Yes".parse::<XDialogAutoAnswer>().unwrap(), XDialogAutoAnswer::Button("Yes".to_string()));
+        assert_eq!("BUTTON: Not now".parse::<XDialogAutoAnswer>().unwrap(), XDialogAutoAnswer::Button("Not now".to_string()));
+        assert!("button:".parse::<XDialogAutoAnswer>().is_err());
+        assert!("yes".parse::<XDialogAutoAnswer>().is_err());
+        assert_eq!(XDialogAutoAnswer::Button("Yes".to_string()).to_string(), "button:Yes");
+    }
+
+    #[test]
+    fn resolves_to_the_chosen_button_or_dismisses() {
+        let result = |policy: XDialogAutoAnswer, options: &XDialogOptions| policy.resolve(options).0;
+        let with_buttons = options(Some(1), Some(0));
+        assert_eq!(result(XDialogAutoAnswer::Default, &with_buttons), XDialogResult::ButtonPressed(1));
+        assert_eq!(result(XDialogAutoAnswer::Cancel, &with_buttons), XDialogResult::ButtonPressed(0));
+        assert_eq!(result(XDialogAutoAnswer::Button("later".to_string()), &with_buttons), XDialogResult::ButtonPressed(2));
+        assert_eq!(result(XDialogAutoAnswer::Button("Retry".to_string()), &with_buttons), XDialogResult::ButtonPressed(0));
+
+        let without = options(None, None);
+        assert_eq!(result(XDialogAutoAnswer::Default, &without), XDialogResult::WindowClosed);
+        assert_eq!(result(XDialogAutoAnswer::Cancel, &without), XDialogResult::WindowClosed);
+    }
+}
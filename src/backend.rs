@@ -0,0 +1,117 @@
+use std::sync::mpsc::Receiver;
+
+use crate::*;
+
+/// A custom dialog implementation which runs an event loop on the main thread, like the built-in
+/// backends, eg. to show dialogs in the application's own UI. Pass one to
+/// [`XDialogBuilder::with_backend`] to use it instead of the platform's backend.
+///
+/// The dialog functions do the same work before a request reaches a custom backend as before it
+/// reaches a built-in one: silent mode is handled without sending a request, every dialog gets a
+/// unique id, and a caller waiting on a dialog whose [`CreationSender`] (or response sender) is
+/// dropped gets an error or `WindowClosed` rather than blocking forever. A backend that panics is
+/// logged, and the application's `main` still runs to completion.
+///
+/// ### Example
+/// ```rust,no_run
+/// use std::sync::mpsc::Receiver;
+/// use xdialog::*;
+///
+/// /// Answers every message dialog with its default button, without showing anything.
+/// struct AutoBackend;
+///
+/// impl XDialogBackend for AutoBackend {
+///     fn run_loop(&mut self, requests: Receiver<DialogMessageRequest>, _theme: XDialogTheme) {
+///         while let Ok(request) = requests.recv() {
+///             match request {
+///                 DialogMessageRequest::ExitEventLoop => break,
+///                 DialogMessageRequest::ShowMessageWindow(_id, options, creation) => {
+///                     let result = match options.default_button_index() {
+///                         Some(index) => XDialogResult::ButtonPressed(index),
+///                         None => options.dismiss_result(),
+///                     };
+///                     DialogResponder::open(creation).respond(result);
+///                 }
+///                 DialogMessageRequest::ShowInputWindow(_id, _options, _field, creation) => {
+///                     DialogResponder::reject(creation, XDialogError::SystemError("not supported".to_string()));
+///                 }
+///                 DialogMessageRequest::ShowProgressWindow(_id, _options, creation, _callbacks) => {
+///                     DialogResponder::reject(creation, XDialogError::SystemError("not supported".to_string()));
+///                 }
+///                 _ => {}
+///             }
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     XDialogBuilder::new().with_backend(AutoBackend).run(|| {
+///         show_message_info_ok("My App", "Hello", "Nobody will see this.").unwrap();
+///     });
+/// }
+/// ```
+pub trait XDialogBackend {
+    /// Handle `requests`, in the order they were made, until [`DialogMessageRequest::ExitEventLoop`]
+    /// arrives (sent once the application's `main` returns) or the channel disconnects. Called on
+    /// the thread which called [`XDialogBuilder::run`] (or one of its variants), with the theme set
+    /// by [`XDialogBuilder::with_theme`].
+    ///
+    /// Each show request carries a [`CreationSender`], which must be answered once the dialog is
+    /// open (or failed to open), see [`DialogResponder`]. Requests for dialog ids which aren't open
+    /// (eg. a progress update racing the user closing the dialog) should be ignored, as should
+    /// request kinds added in later versions: [`DialogMessageRequest`] is `#[non_exhaustive]`, so a
+    /// match on it needs a fallback arm.
+    fn run_loop(&mut self, requests: Receiver<DialogMessageRequest>, theme: XDialogTheme);
+}
+
+/// Receives every dialog request as it is made, on the thread making it. Unlike an
+/// [`XDialogBackend`], a handler has no event loop of its own: install one with
+/// [`init_with_handler`] instead of running an [`XDialogBuilder`], eg. to forward the requests to an
+/// application's existing UI thread. Implementations must be thread-safe (`Send + Sync`).
+///
+/// Handlers get the same guarantees as an [`XDialogBackend`], except that a panic in [`send`](Self::send)
+/// is not caught.
+pub trait DialogRequestHandler: Send + Sync {
+    /// Send a dialog message request to the backend. An error is returned to the caller of the
+    /// dialog function which made the request.
+    fn send(&self, message: DialogMessageRequest) -> Result<(), XDialogError>;
+}
+
+/// Send every dialog request to `handler`, instead of to the backend of an [`XDialogBuilder`]. Must
+/// be called once, before any dialog is shown; later calls are ignored with a warning. Running an
+/// [`XDialogBuilder`] afterwards logs the same warning and still runs its `main`, but every request
+/// (including the final [`DialogMessageRequest::ExitEventLoop`]) keeps going to `handler`, and the
+/// builder's backend sees its request channel already disconnected.
+pub fn init_with_handler<H: DialogRequestHandler + 'static>(handler: H) {
+    crate::channel::init_handler(Box::new(handler));
+}
+
+/// The sending half of an open dialog's response, which a custom backend uses to report how the
+/// dialog closed. Dropping it without a response reports `XDialogResult::WindowClosed`.
+#[derive(Debug)]
+pub struct DialogResponder {
+    sender: oneshot::Sender<XDialogResponse>,
+}
+
+impl DialogResponder {
+    /// Answer a show request by reporting the dialog as open, which returns control to a
+    /// non-blocking caller (eg. [`show_progress`]). Keep the returned responder until the dialog
+    /// closes, then call [`respond`](Self::respond).
+    pub fn open(creation: CreationSender) -> DialogResponder {
+        let (sender, receiver) = oneshot::channel();
+        let _ = creation.send(Ok(receiver));
+        DialogResponder { sender }
+    }
+
+    /// Answer a show request by reporting that the dialog could not be shown. The caller's dialog
+    /// function returns `error`.
+    pub fn reject(creation: CreationSender, error: XDialogError) {
+        let _ = creation.send(Err(error));
+    }
+
+    /// Report how the dialog closed: an [`XDialogResult`], or an [`XDialogResponse`] which also
+    /// carries the contents of its text field, checkbox and radio buttons.
+    pub fn respond(self, response: impl Into<XDialogResponse>) {
+        let _ = self.sender.send(response.into());
+    }
+}
@@ -1,12 +1,17 @@
 use objc2::msg_send;
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
+use objc2::runtime::{AnyClass, AnyObject};
 use objc2::sel;
+use objc2::AnyThread;
 use objc2::MainThreadMarker;
 use objc2_app_kit::*;
 use objc2_foundation::*;
 
+use crate::checklist::Checklist;
+use crate::hyperlink::parse_links;
+use crate::icon::{IconData, IconSource};
 use crate::model::*;
+use crate::{ChecklistStepState, ProgressBarState};
 
 // Layout constants
 const WINDOW_MIN_WIDTH: f64 = 350.0;
@@ -20,8 +25,33 @@ const BUTTON_SPACING: f64 = 6.0;
 const BUTTON_PANEL_HEIGHT: f64 = 32.0;
 const TEXT_SPACING: f64 = 8.0;
 const PROGRESS_HEIGHT: f64 = 20.0;
+const STATUS_HEIGHT: f64 = 16.0;
+const CAPTION_HEIGHT: f64 = 16.0;
 const TITLE_FONT_SIZE: f64 = 13.0;
 const BODY_FONT_SIZE: f64 = 11.0;
+const INPUT_HEIGHT: f64 = 22.0;
+const INPUT_ERROR_HEIGHT: f64 = 16.0;
+const RADIO_HEIGHT: f64 = 20.0;
+const DISCLOSURE_HEIGHT: f64 = 20.0;
+const DISCLOSURE_BUTTON_WIDTH: f64 = 20.0;
+const LINK_HEIGHT: f64 = 18.0;
+const FOOTER_FONT_SIZE: f64 = 10.0;
+const FOOTER_ICON_SIZE: f64 = 16.0;
+
+/// View tags of the input dialog's text field and its error label. Buttons use non-negative tags
+/// (`(id << 16) | index`), so these can't collide; the click handler finds both via `viewWithTag:`.
+pub const INPUT_FIELD_TAG: isize = -100;
+pub const INPUT_ERROR_TAG: isize = -101;
+/// View tag of the verification checkbox, found the same way.
+pub const VERIFICATION_TAG: isize = -102;
+/// View tag of the first radio button; radio button `i` is tagged `RADIO_TAG_BASE - i`.
+pub const RADIO_TAG_BASE: isize = -200;
+
+/// One of a progress dialog's bars, with the caption above it (created once it has text).
+struct AppKitProgressBar {
+    caption: Option<Retained<NSTextField>>,
+    indicator: Retained<NSProgressIndicator>,
+}
 
 pub struct AppKitDialog {
     #[allow(dead_code)]
@@ -29,9 +59,25 @@ pub struct AppKitDialog {
     window: Retained<NSWindow>,
     title_field: Option<Retained<NSTextField>>,
     body_field: Option<Retained<NSTextField>>,
-    progress: Option<Retained<NSProgressIndicator>>,
+    /// One borderless button per link in the message, listed under the body text. Each carries its
+    /// href as its tooltip, which is where the click handler reads it from.
+    links: Vec<Retained<NSButton>>,
+    input_field: Option<Retained<NSTextField>>,
+    input_error: Option<Retained<NSTextField>>,
+    radios: Vec<Retained<NSButton>>,
+    /// The "Show details" toggle, its label and the (initially hidden) details text.
+    details: Option<(Retained<NSButton>, Retained<NSTextField>, Retained<NSTextField>)>,
+    details_expanded: bool,
+    /// The checklist of a progress dialog and the label listing it as text, above the progress bars.
+    checklist: Option<(Checklist, Retained<NSTextField>)>,
+    progress: Vec<AppKitProgressBar>,
+    /// The single-line status under the progress bars, created by the first `set_status`.
+    status_field: Option<Retained<NSTextField>>,
     icon_view: Option<Retained<NSImageView>>,
     buttons: Vec<Retained<NSButton>>,
+    verification: Option<Retained<NSButton>>,
+    /// The footer note beneath the buttons, with its optional small icon.
+    footer: Option<(Option<Retained<NSImageView>>, Retained<NSTextField>)>,
     has_progress: bool,
     options: XDialogOptions,
 }
@@ -41,6 +87,7 @@ impl AppKitDialog {
         id: usize,
         options: XDialogOptions,
         has_progress: bool,
+        input: Option<&crate::InputField>,
         handler: &AnyObject,
     ) -> Self {
         let mtm = unsafe { MainThreadMarker::new_unchecked() };
@@ -83,35 +130,148 @@ impl AppKitDialog {
             None
         };
 
-        // Progress bar
-        let progress = if has_progress {
-            let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, PROGRESS_HEIGHT));
-            let p = NSProgressIndicator::initWithFrame(
-                mtm.alloc::<NSProgressIndicator>(),
-                frame,
-            );
-            p.setStyle(NSProgressIndicatorStyle::Bar);
-            p.setMinValue(0.0);
-            p.setMaxValue(1.0);
-            p.setDoubleValue(0.0);
-            p.setIndeterminate(false);
-            content_view.addSubview(&p);
-            Some(p)
-        } else {
-            None
-        };
+        // Checklist, as text
+        let checklist = (has_progress && !options.checklist.is_empty()).then(|| {
+            let checklist = Checklist::new(&options.checklist);
+            let field = create_label(&checklist.to_text(), false, mtm);
+            content_view.addSubview(&field);
+            (checklist, field)
+        });
 
-        // Body text
-        let body_field = if !options.message.is_empty() {
-            let field = create_label(&options.message, false, mtm);
+        // Progress bars, each under its caption
+        let captions = if has_progress { options.progress_bar_captions() } else { Vec::new() };
+        let progress = captions
+            .iter()
+            .map(|caption| {
+                let caption = (!caption.is_empty()).then(|| {
+                    let field = create_single_line_label(caption, mtm);
+                    content_view.addSubview(&field);
+                    field
+                });
+                let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, PROGRESS_HEIGHT));
+                let p = NSProgressIndicator::initWithFrame(
+                    mtm.alloc::<NSProgressIndicator>(),
+                    frame,
+                );
+                p.setStyle(NSProgressIndicatorStyle::Bar);
+                p.setMinValue(0.0);
+                p.setMaxValue(1.0);
+                p.setDoubleValue(0.0);
+                p.setIndeterminate(false);
+                content_view.addSubview(&p);
+                AppKitProgressBar { caption, indicator: p }
+            })
+            .collect();
+
+        // Body text, without its link markup
+        let (message, message_links) = parse_links(&options.message);
+        let body_field = if !message.is_empty() {
+            let field = create_label(&message, false, mtm);
             content_view.addSubview(&field);
             Some(field)
         } else {
             None
         };
 
+        // Links, as link-coloured buttons under the body text
+        let links: Vec<Retained<NSButton>> = message_links
+            .iter()
+            .map(|link| {
+                let button = NSButton::initWithFrame(
+                    mtm.alloc::<NSButton>(),
+                    NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, LINK_HEIGHT)),
+                );
+                button.setBordered(false);
+                button.setTitle(&NSString::from_str(&message[link.range.clone()]));
+                button.setContentTintColor(Some(&NSColor::linkColor()));
+                button.setAlignment(NSTextAlignment::Left);
+                button.setToolTip(Some(&NSString::from_str(&link.href)));
+                unsafe { button.setTarget(Some(handler)) };
+                unsafe { button.setAction(Some(sel!(linkClicked:))) };
+                content_view.addSubview(&button);
+                button
+            })
+            .collect();
+
+        // Details disclosure. The triangle toggles its own state; the run loop picks the change up
+        // through `sync_details`.
+        let details = (!options.expanded_details.is_empty()).then(|| {
+            let toggle = NSButton::initWithFrame(
+                mtm.alloc::<NSButton>(),
+                NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(DISCLOSURE_BUTTON_WIDTH, DISCLOSURE_HEIGHT)),
+            );
+            toggle.setButtonType(NSButtonType::PushOnPushOff);
+            toggle.setBezelStyle(NSBezelStyle::Disclosure);
+            toggle.setTitle(&NSString::from_str(""));
+            content_view.addSubview(&toggle);
+            let label = create_label("Show details", false, mtm);
+            content_view.addSubview(&label);
+            let text = create_label(&options.expanded_details, false, mtm);
+            text.setSelectable(true);
+            text.setHidden(true);
+            content_view.addSubview(&text);
+            (toggle, label, text)
+        });
+
+        // Text input, plus a (hidden until needed) validation error line when a validator is set
+        let input_field = input.map(|input| {
+            let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, INPUT_HEIGHT));
+            // Password fields use the secure variant, which draws bullets and blocks copying.
+            let field = if input.masked {
+                Retained::into_super(NSSecureTextField::initWithFrame(mtm.alloc::<NSSecureTextField>(), frame))
+            } else {
+                NSTextField::initWithFrame(mtm.alloc::<NSTextField>(), frame)
+            };
+            field.setStringValue(&NSString::from_str(&input.initial_text));
+            field.setFont(Some(&NSFont::systemFontOfSize(BODY_FONT_SIZE)));
+            field.setTag(INPUT_FIELD_TAG);
+            unsafe {
+                let cell: Option<Retained<AnyObject>> = msg_send![&field, cell];
+                if let Some(cell) = cell {
+                    let () = msg_send![&*cell, setScrollable: true];
+                    let () = msg_send![&*cell, setUsesSingleLineMode: true];
+                }
+            }
+            content_view.addSubview(&field);
+            field
+        });
+        let input_error = input.filter(|input| input.validator.is_some()).map(|_| {
+            let label = create_label("", false, mtm);
+            label.setTextColor(Some(&NSColor::systemRedColor()));
+            label.setTag(INPUT_ERROR_TAG);
+            label.setHidden(true);
+            content_view.addSubview(&label);
+            label
+        });
+
+        // Radio buttons. AppKit groups radio buttons sharing a superview and an action, so they all
+        // get the (no-op) radioClicked: action.
+        let selected_radio = options.initial_radio_index();
+        let radios: Vec<Retained<NSButton>> = options
+            .radio_buttons
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let radio = NSButton::initWithFrame(
+                    mtm.alloc::<NSButton>(),
+                    NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, RADIO_HEIGHT)),
+                );
+                radio.setButtonType(NSButtonType::Radio);
+                radio.setTitle(&NSString::from_str(text));
+                radio.setState(if selected_radio == Some(index) { NSControlStateValueOn } else { NSControlStateValueOff });
+                radio.setTag(RADIO_TAG_BASE - index as isize);
+                unsafe { radio.setTarget(Some(handler)) };
+                unsafe { radio.setAction(Some(sel!(radioClicked:))) };
+                content_view.addSubview(&radio);
+                radio
+            })
+            .collect();
+
         // Buttons (iterate in reverse so rightmost button is last/default)
         let button_count = options.buttons.len();
+        let default_button = options.default_button_index().or(button_count.checked_sub(1));
+        // Without an explicit cancel button, Escape presses the first one (if more than one button)
+        let cancel_button = options.cancel_button.filter(|&i| i < button_count).or((button_count > 1).then_some(0));
         let mut buttons = Vec::new();
         for (index, button_text) in options.buttons.iter().enumerate().rev() {
             let btn = NSButton::initWithFrame(
@@ -128,12 +288,10 @@ impl AppKitDialog {
             unsafe { btn.setTarget(Some(handler)) };
             unsafe { btn.setAction(Some(sel!(buttonClicked:))) };
 
-            // Last button (highest index) is the default/return key
-            if index == button_count - 1 {
+            // The default button gets the return key (and the accent colour), the cancel button escape
+            if Some(index) == default_button {
                 btn.setKeyEquivalent(&NSString::from_str("\r"));
-            }
-            // First button gets escape key (if more than one button)
-            if index == 0 && button_count > 1 {
+            } else if Some(index) == cancel_button {
                 btn.setKeyEquivalent(&NSString::from_str("\u{1b}"));
             }
 
@@ -142,14 +300,55 @@ impl AppKitDialog {
         }
         buttons.reverse(); // put back in original order
 
+        // Verification checkbox, bottom-left beside the buttons
+        let verification = (!options.verification_text.is_empty()).then(|| {
+            let check = NSButton::initWithFrame(
+                mtm.alloc::<NSButton>(),
+                NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, BUTTON_HEIGHT)),
+            );
+            check.setButtonType(NSButtonType::Switch);
+            check.setTitle(&NSString::from_str(&options.verification_text));
+            check.setState(if options.verification_checked { NSControlStateValueOn } else { NSControlStateValueOff });
+            check.setTag(VERIFICATION_TAG);
+            content_view.addSubview(&check);
+            check
+        });
+
+        // Footer note, in small secondary text beneath the buttons
+        let footer = (!options.footer_text.is_empty()).then(|| {
+            let icon = get_icon_image(&options.footer_icon).map(|image| {
+                let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(FOOTER_ICON_SIZE, FOOTER_ICON_SIZE));
+                let iv = NSImageView::initWithFrame(mtm.alloc::<NSImageView>(), frame);
+                iv.setImage(Some(&image));
+                iv.setImageScaling(NSImageScaling::ScaleProportionallyUpOrDown);
+                content_view.addSubview(&iv);
+                iv
+            });
+            let label = create_label(&options.footer_text, false, mtm);
+            label.setFont(Some(&NSFont::systemFontOfSize(FOOTER_FONT_SIZE)));
+            label.setTextColor(Some(&NSColor::secondaryLabelColor()));
+            content_view.addSubview(&label);
+            (icon, label)
+        });
+
         let mut dialog = Self {
             id,
             window,
             title_field,
             body_field,
+            links,
+            input_field,
+            input_error,
+            radios,
+            details,
+            details_expanded: false,
+            checklist,
             progress,
+            status_field: None,
             icon_view,
             buttons,
+            verification,
+            footer,
             has_progress,
             options,
         };
@@ -211,12 +410,47 @@ impl AppKitDialog {
         if title_height > 0.0 {
             text_block_height += title_height + TEXT_SPACING;
         }
-        if self.has_progress {
+        let checklist_height = match self.checklist {
+            Some((_, ref field)) => measure_field_height(field, text_area_width),
+            None => 0.0,
+        };
+        if checklist_height > 0.0 {
+            text_block_height += checklist_height + TEXT_SPACING;
+        }
+        for bar in self.progress.iter() {
+            if bar.caption.is_some() {
+                text_block_height += CAPTION_HEIGHT;
+            }
             text_block_height += PROGRESS_HEIGHT + TEXT_SPACING;
         }
+        if self.status_field.is_some() {
+            text_block_height += STATUS_HEIGHT + TEXT_SPACING;
+        }
         if body_height > 0.0 {
             text_block_height += body_height + TEXT_SPACING;
         }
+        if !self.links.is_empty() {
+            text_block_height += LINK_HEIGHT * self.links.len() as f64 + TEXT_SPACING;
+        }
+        if self.input_field.is_some() {
+            text_block_height += INPUT_HEIGHT + TEXT_SPACING;
+        }
+        if self.input_error.is_some() {
+            text_block_height += INPUT_ERROR_HEIGHT;
+        }
+        if !self.radios.is_empty() {
+            text_block_height += RADIO_HEIGHT * self.radios.len() as f64 + TEXT_SPACING;
+        }
+        let details_height = match self.details {
+            Some((_, _, ref text)) if self.details_expanded => measure_field_height(text, text_area_width),
+            _ => 0.0,
+        };
+        if self.details.is_some() {
+            text_block_height += DISCLOSURE_HEIGHT + TEXT_SPACING;
+        }
+        if details_height > 0.0 {
+            text_block_height += details_height + TEXT_SPACING;
+        }
 
         // Main area is the taller of icon or text block
         let main_area_height = if has_icon {
@@ -225,10 +459,23 @@ impl AppKitDialog {
             text_block_height
         };
 
+        // Footer note, measured across the full content width (less its icon)
+        let footer_text_x = match self.footer {
+            Some((Some(_), _)) => FOOTER_ICON_SIZE + BUTTON_SPACING,
+            _ => 0.0,
+        };
+        let footer_height = match self.footer {
+            Some((_, ref label)) => measure_field_height(label, content_width - footer_text_x).max(FOOTER_ICON_SIZE),
+            None => 0.0,
+        };
+
         let mut total_height = WINDOW_PADDING + main_area_height;
-        if !self.options.buttons.is_empty() {
+        if !self.options.buttons.is_empty() || self.verification.is_some() {
             total_height += BUTTON_PANEL_HEIGHT;
         }
+        if self.footer.is_some() {
+            total_height += footer_height + TEXT_SPACING;
+        }
         total_height += WINDOW_PADDING;
 
         let window_width = content_width + WINDOW_PADDING * 2.0;
@@ -282,16 +529,43 @@ impl AppKitDialog {
             y -= TEXT_SPACING;
         }
 
-        // Progress bar
-        if let Some(ref p) = self.progress {
+        // Checklist
+        if let Some((_, ref field)) = self.checklist {
+            y -= checklist_height;
+            field.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, checklist_height),
+            ));
+            y -= TEXT_SPACING;
+        }
+
+        // Progress bars
+        for bar in self.progress.iter() {
+            if let Some(ref caption) = bar.caption {
+                y -= CAPTION_HEIGHT;
+                caption.setFrame(NSRect::new(
+                    NSPoint::new(text_x, y),
+                    NSSize::new(text_area_width, CAPTION_HEIGHT),
+                ));
+            }
             y -= PROGRESS_HEIGHT;
-            p.setFrame(NSRect::new(
+            bar.indicator.setFrame(NSRect::new(
                 NSPoint::new(text_x, y),
                 NSSize::new(text_area_width, PROGRESS_HEIGHT),
             ));
             y -= TEXT_SPACING;
         }
 
+        // Progress status
+        if let Some(ref sf) = self.status_field {
+            y -= STATUS_HEIGHT;
+            sf.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, STATUS_HEIGHT),
+            ));
+            y -= TEXT_SPACING;
+        }
+
         // Body text
         if let Some(ref bf) = self.body_field {
             y -= body_height;
@@ -299,11 +573,86 @@ impl AppKitDialog {
                 NSPoint::new(text_x, y),
                 NSSize::new(text_area_width, body_height),
             ));
+            y -= TEXT_SPACING;
+        }
+
+        // Links
+        for link in self.links.iter() {
+            y -= LINK_HEIGHT;
+            link.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, LINK_HEIGHT),
+            ));
+        }
+        if !self.links.is_empty() {
+            y -= TEXT_SPACING;
+        }
+
+        // Details toggle and text
+        if let Some((ref toggle, ref label, ref text)) = self.details {
+            y -= DISCLOSURE_HEIGHT;
+            toggle.setFrame(NSRect::new(NSPoint::new(text_x, y), NSSize::new(DISCLOSURE_BUTTON_WIDTH, DISCLOSURE_HEIGHT)));
+            label.setFrame(NSRect::new(
+                NSPoint::new(text_x + DISCLOSURE_BUTTON_WIDTH, y),
+                NSSize::new(text_area_width - DISCLOSURE_BUTTON_WIDTH, DISCLOSURE_HEIGHT),
+            ));
+            y -= TEXT_SPACING;
+            if details_height > 0.0 {
+                y -= details_height;
+                text.setFrame(NSRect::new(
+                    NSPoint::new(text_x, y),
+                    NSSize::new(text_area_width, details_height),
+                ));
+                y -= TEXT_SPACING;
+            }
+        }
+
+        // Text input and its error line
+        if let Some(ref field) = self.input_field {
+            y -= INPUT_HEIGHT;
+            field.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, INPUT_HEIGHT),
+            ));
+        }
+        if let Some(ref label) = self.input_error {
+            y -= INPUT_ERROR_HEIGHT;
+            label.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, INPUT_ERROR_HEIGHT),
+            ));
+        }
+
+        // Radio buttons
+        if !self.radios.is_empty() {
+            y -= TEXT_SPACING;
+        }
+        for radio in self.radios.iter() {
+            y -= RADIO_HEIGHT;
+            radio.setFrame(NSRect::new(
+                NSPoint::new(text_x, y),
+                NSSize::new(text_area_width, RADIO_HEIGHT),
+            ));
+        }
+
+        // Footer note at the very bottom, with the button row above it
+        let mut button_y = WINDOW_PADDING;
+        if let Some((ref icon, ref label)) = self.footer {
+            if let Some(iv) = icon {
+                iv.setFrame(NSRect::new(
+                    NSPoint::new(WINDOW_PADDING, WINDOW_PADDING + footer_height - FOOTER_ICON_SIZE),
+                    NSSize::new(FOOTER_ICON_SIZE, FOOTER_ICON_SIZE),
+                ));
+            }
+            label.setFrame(NSRect::new(
+                NSPoint::new(WINDOW_PADDING + footer_text_x, WINDOW_PADDING),
+                NSSize::new(content_width - footer_text_x, footer_height),
+            ));
+            button_y += footer_height + TEXT_SPACING;
         }
 
         // Buttons - right-aligned at bottom
         if !self.buttons.is_empty() {
-            let button_y = WINDOW_PADDING;
             let mut btn_x = window_width - WINDOW_PADDING;
 
             for btn in self.buttons.iter().rev() {
@@ -317,11 +666,25 @@ impl AppKitDialog {
                 btn_x -= BUTTON_SPACING;
             }
         }
+
+        // Verification checkbox - left-aligned in the button row, using the space the buttons leave
+        if let Some(ref check) = self.verification {
+            let buttons_left = self.buttons.first().map_or(window_width - WINDOW_PADDING, |b| b.frame().origin.x - BUTTON_SPACING);
+            check.setFrame(NSRect::new(
+                NSPoint::new(WINDOW_PADDING, button_y),
+                NSSize::new((buttons_left - WINDOW_PADDING).max(0.0), BUTTON_HEIGHT),
+            ));
+        }
     }
 
     pub fn show(&self) {
         self.window.center();
         self.window.makeKeyAndOrderFront(None);
+        // Start typing straight away, replacing the (selected) initial text
+        if let Some(ref field) = self.input_field {
+            let _: bool = unsafe { msg_send![&*self.window, makeFirstResponder: &**field] };
+            let () = unsafe { msg_send![&**field, selectText: std::ptr::null::<AnyObject>()] };
+        }
         unsafe {
             let mtm = MainThreadMarker::new_unchecked();
             let app = NSApplication::sharedApplication(mtm);
@@ -334,12 +697,55 @@ impl AppKitDialog {
         self.window.isVisible()
     }
 
+    /// The current contents of the text field, for input dialogs.
+    pub fn input_text(&self) -> Option<String> {
+        self.input_field.as_ref().map(|field| field.stringValue().to_string())
+    }
+
+    /// Show or hide the details text to match the disclosure triangle, resizing the window if it
+    /// changed. Called on every run-loop iteration.
+    pub fn sync_details(&mut self) {
+        let Some((ref toggle, ref label, ref text)) = self.details else {
+            return;
+        };
+        let expanded = toggle.state() == NSControlStateValueOn;
+        if expanded != self.details_expanded {
+            self.details_expanded = expanded;
+            text.setHidden(!expanded);
+            label.setStringValue(&NSString::from_str(if expanded { "Hide details" } else { "Show details" }));
+            self.layout();
+        }
+    }
+
+    /// The result reported when the user closes the window (see `XDialogOptions::cancel_button`).
+    pub fn dismiss_result(&self) -> XDialogResult {
+        self.options.dismiss_result()
+    }
+
+    /// The index of the selected radio button, if the dialog has any.
+    pub fn radio_index(&self) -> Option<usize> {
+        if self.radios.is_empty() {
+            return None;
+        }
+        Some(self.radios.iter().position(|radio| radio.state() == NSControlStateValueOn).unwrap_or(0))
+    }
+
+    /// The state of the verification checkbox, if the dialog has one.
+    pub fn verification_checked(&self) -> Option<bool> {
+        self.verification.as_ref().map(|check| check.state() == NSControlStateValueOn)
+    }
+
     pub fn close(&self) {
         self.window.orderOut(None);
     }
 
-    pub fn set_progress_value(&self, value: f32) {
-        if let Some(ref p) = self.progress {
+    /// Shows the window again after the user closed it, in place.
+    pub fn reopen(&self) {
+        self.window.makeKeyAndOrderFront(None);
+    }
+
+    pub fn set_progress_value(&self, bar: usize, value: f32) {
+        if let Some(p) = self.progress.get(bar).map(|b| &b.indicator) {
             if p.isIndeterminate() {
                 p.setIndeterminate(false);
                 unsafe { p.stopAnimation(None) };
@@ -348,8 +754,30 @@ impl AppKitDialog {
         }
     }
 
-    pub fn set_progress_indeterminate(&self) {
-        if let Some(ref p) = self.progress {
+    /// Tint the progress bar for `state`. NSProgressIndicator has no paused or error appearance, so
+    /// the bar is recoloured with a Core Image filter, which layer-backed views support.
+    pub fn set_progress_state(&self, bar: usize, state: ProgressBarState) {
+        let Some(p) = self.progress.get(bar).map(|b| &b.indicator) else {
+            return;
+        };
+        let tint = match state {
+            ProgressBarState::Normal => None,
+            ProgressBarState::Paused => Some((1.0, 0.8, 0.0)),
+            ProgressBarState::Error => Some((1.0, 0.23, 0.19)),
+        };
+        let filters = match tint.and_then(|(r, g, b)| unsafe { monochrome_filter(r, g, b) }) {
+            Some(filter) => NSArray::from_retained_slice(&[filter]),
+            None => NSArray::new(),
+        };
+        unsafe {
+            let () = msg_send![&**p, setWantsLayer: true];
+            let () = msg_send![&**p, setLayerUsesCoreImageFilters: true];
+            let () = msg_send![&**p, setContentFilters: &*filters];
+        }
+    }
+
+    pub fn set_progress_indeterminate(&self, bar: usize) {
+        if let Some(p) = self.progress.get(bar).map(|b| &b.indicator) {
             // Reset to 0 first - the indeterminate animation won't start
             // if the bar was previously at a high value. We need a
             // display cycle between resetting and switching modes.
@@ -362,8 +790,71 @@ impl AppKitDialog {
         }
     }
 
+    /// Replace the body text. Any links in it are shown as text only; the link buttons are created
+    /// with the dialog.
+    /// Apply a patch from `DialogMessageRequest::UpdateDialog`, adding or removing the main
+    /// instruction and icon views as needed, then lay out again.
+    pub fn apply_patch(&mut self, patch: DialogPatch) {
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        let content_view = self.window.contentView().unwrap();
+
+        if let Some(title) = patch.title {
+            self.window.setTitle(&NSString::from_str(&title));
+            self.options.title = title;
+        }
+        if let Some(text) = patch.main_instruction {
+            match self.title_field.take() {
+                Some(field) if text.is_empty() => field.removeFromSuperview(),
+                Some(field) => {
+                    field.setStringValue(&NSString::from_str(&text));
+                    self.title_field = Some(field);
+                }
+                None if text.is_empty() => {}
+                None => {
+                    let field = create_label(&text, true, mtm);
+                    content_view.addSubview(&field);
+                    self.title_field = Some(field);
+                }
+            }
+            self.options.main_instruction = text;
+        }
+        if let Some(icon) = patch.icon {
+            match (get_icon_image(&icon), self.icon_view.take()) {
+                (Some(image), Some(iv)) => {
+                    iv.setImage(Some(&image));
+                    self.icon_view = Some(iv);
+                }
+                (Some(image), None) => {
+                    let size = if self.has_progress { ICON_PROGRESS_SIZE } else { ICON_SIZE };
+                    let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(size, size));
+                    let iv = NSImageView::initWithFrame(mtm.alloc::<NSImageView>(), frame);
+                    iv.setImage(Some(&image));
+                    iv.setImageScaling(NSImageScaling::ScaleProportionallyUpOrDown);
+                    content_view.addSubview(&iv);
+                    self.icon_view = Some(iv);
+                }
+                (None, Some(iv)) => iv.removeFromSuperview(),
+                (None, None) => {}
+            }
+            self.options.icon = icon;
+        }
+        for (index, label) in patch.button_labels {
+            if let Some(btn) = self.buttons.get(index) {
+                btn.setTitle(&NSString::from_str(&label));
+                self.options.buttons[index] = label;
+            }
+        }
+
+        match patch.message {
+            // `set_body_text` lays out too.
+            Some(message) => self.set_body_text(&message),
+            None => self.layout(),
+        }
+    }
+
     pub fn set_body_text(&mut self, text: &str) {
         self.options.message = text.to_string();
+        let text = &parse_links(text).0;
 
         if let Some(ref bf) = self.body_field {
             bf.setStringValue(&NSString::from_str(text));
@@ -376,17 +867,124 @@ impl AppKitDialog {
 
         self.layout();
     }
+
+    /// Set the status under the progress bar. The field is a single line truncated in the middle,
+    /// so only the first status (which makes room for it) changes the layout.
+    pub fn set_status(&mut self, text: &str) {
+        if let Some(ref sf) = self.status_field {
+            sf.setStringValue(&NSString::from_str(text));
+            return;
+        }
+        if text.is_empty() || self.progress.is_empty() {
+            return;
+        }
+
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        let field = create_single_line_label(text, mtm);
+        self.window.contentView().unwrap().addSubview(&field);
+        self.status_field = Some(field);
+        self.layout();
+    }
+
+    /// Set the state of a checklist step. The list keeps its lines, so the layout doesn't change.
+    pub fn set_checklist_step(&mut self, index: usize, state: ChecklistStepState) {
+        if let Some((ref mut checklist, ref field)) = self.checklist {
+            if checklist.set(index, state) {
+                field.setStringValue(&NSString::from_str(&checklist.to_text()));
+            }
+        }
+    }
+
+    /// Start a checklist step, finishing the ones before it.
+    pub fn start_checklist_step(&mut self, index: usize) {
+        if let Some((ref mut checklist, ref field)) = self.checklist {
+            if checklist.start(index) {
+                field.setStringValue(&NSString::from_str(&checklist.to_text()));
+            }
+        }
+    }
+
+    /// Set the caption above a progress bar, laying out again when that shows or hides it.
+    pub fn set_progress_caption(&mut self, bar: usize, caption: &str) {
+        let Some(bar) = self.progress.get_mut(bar) else {
+            return;
+        };
+        match bar.caption.take() {
+            Some(field) if !caption.is_empty() => {
+                field.setStringValue(&NSString::from_str(caption));
+                bar.caption = Some(field);
+                return;
+            }
+            Some(field) => field.removeFromSuperview(),
+            None if caption.is_empty() => return,
+            None => {
+                let mtm = unsafe { MainThreadMarker::new_unchecked() };
+                let field = create_single_line_label(caption, mtm);
+                self.window.contentView().unwrap().addSubview(&field);
+                bar.caption = Some(field);
+            }
+        }
+        self.layout();
+    }
 }
 
 fn get_icon_image(icon: &XDialogIcon) -> Option<Retained<NSImage>> {
     let name = match icon {
         XDialogIcon::None => return None,
         XDialogIcon::Error | XDialogIcon::Warning => "NSCaution",
-        XDialogIcon::Information => "NSInfo",
+        XDialogIcon::Information | XDialogIcon::Question | XDialogIcon::Success => "NSInfo",
+        // The closest named image to "needs administrator rights".
+        XDialogIcon::Shield => "NSLockLockedTemplate",
+        XDialogIcon::Custom(data) => match custom_icon_image(data) {
+            Some(image) => return Some(image),
+            None => {
+                log::warn!("xdialog: failed to load custom icon {:?}, showing the information icon", data);
+                "NSInfo"
+            }
+        },
     };
     NSImage::imageNamed(&NSString::from_str(name))
 }
 
+/// An image of a custom icon's data. `NSImage` reads PNG (and SVG from macOS 14) itself; RGBA
+/// pixels are copied into a bitmap.
+fn custom_icon_image(data: &IconData) -> Option<Retained<NSImage>> {
+    let (width, height, pixels) = match data.source() {
+        IconSource::Png(bytes) => return NSImage::initWithData(NSImage::alloc(), &NSData::with_bytes(bytes)),
+        IconSource::Svg(text) => return NSImage::initWithData(NSImage::alloc(), &NSData::with_bytes(text.as_bytes())),
+        IconSource::Rgba { width, height, pixels } => (*width as isize, *height as isize, pixels),
+    };
+    unsafe {
+        // Planes are null, so the bitmap allocates its own buffer, which the pixels are copied into.
+        let bitmap = NSBitmapImageRep::initWithBitmapDataPlanes_pixelsWide_pixelsHigh_bitsPerSample_samplesPerPixel_hasAlpha_isPlanar_colorSpaceName_bytesPerRow_bitsPerPixel(
+            NSBitmapImageRep::alloc(),
+            std::ptr::null_mut(),
+            width,
+            height,
+            8,
+            4,
+            true,
+            false,
+            NSDeviceRGBColorSpace,
+            width * 4,
+            32,
+        )?;
+        // The bitmap is premultiplied by default.
+        let premultiplied: Vec<u8> = pixels
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let a = p[3] as u32;
+                let mul = |c: u8| ((c as u32 * a + 127) / 255) as u8;
+                [mul(p[0]), mul(p[1]), mul(p[2]), p[3]]
+            })
+            .collect();
+        std::ptr::copy_nonoverlapping(premultiplied.as_ptr(), bitmap.bitmapData(), premultiplied.len());
+        let image = NSImage::initWithSize(NSImage::alloc(), NSSize::new(width as f64, height as f64));
+        image.addRepresentation(&bitmap);
+        Some(image)
+    }
+}
+
 fn create_label(text: &str, bold: bool, mtm: MainThreadMarker) -> Retained<NSTextField> {
     let frame = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(100.0, 20.0));
     let field = NSTextField::initWithFrame(mtm.alloc::<NSTextField>(), frame);
@@ -415,6 +1013,19 @@ fn create_label(text: &str, bold: bool, mtm: MainThreadMarker) -> Retained<NSTex
     field
 }
 
+/// A body-text label kept to one line, truncating long text (eg. a path) in the middle.
+fn create_single_line_label(text: &str, mtm: MainThreadMarker) -> Retained<NSTextField> {
+    let field = create_label(text, false, mtm);
+    unsafe {
+        let cell: Option<Retained<AnyObject>> = msg_send![&field, cell];
+        if let Some(cell) = cell {
+            let () = msg_send![&*cell, setWraps: false];
+            let () = msg_send![&*cell, setLineBreakMode: 5u64]; // NSLineBreakByTruncatingMiddle
+        }
+    }
+    field
+}
+
 fn measure_field_height(field: &NSTextField, width: f64) -> f64 {
     unsafe {
         let cell: Option<Retained<AnyObject>> = msg_send![field, cell];
@@ -438,3 +1049,19 @@ fn measure_button_width(btn: &NSButton) -> f64 {
         size.width
     }
 }
+
+/// A `CIColorMonochrome` filter recolouring a view in the given colour, or `None` if Core Image isn't
+/// available.
+unsafe fn monochrome_filter(r: f64, g: f64, b: f64) -> Option<Retained<AnyObject>> {
+    let name = NSString::from_str("CIColorMonochrome");
+    let filter: Option<Retained<AnyObject>> = unsafe { msg_send![AnyClass::get(c"CIFilter")?, filterWithName: &*name] };
+    let filter = filter?;
+    let color: Retained<AnyObject> = unsafe { msg_send![AnyClass::get(c"CIColor")?, colorWithRed: r, green: g, blue: b] };
+    let intensity = NSNumber::new_f64(1.0);
+    unsafe {
+        let () = msg_send![&*filter, setDefaults];
+        let () = msg_send![&*filter, setValue: &*color, forKey: &*NSString::from_str("inputColor")];
+        let () = msg_send![&*filter, setValue: &*intensity, forKey: &*NSString::from_str("inputIntensity")];
+    }
+    Some(filter)
+}
@@ -9,36 +9,75 @@ use objc2::rc::Retained;
 use objc2::runtime::{AnyClass, AnyObject, ClassBuilder, Sel};
 use objc2::{msg_send, sel};
 use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSEvent, NSEventMask};
-use objc2_foundation::{NSDate, NSDefaultRunLoopMode};
+use objc2_foundation::{NSDate, NSDefaultRunLoopMode, NSString};
 
 use crate::backends::XDialogBackendImpl;
 use crate::model::*;
-use crate::{ProgressButtonCallback, ProgressDialogProxy};
+use crate::{InputField, ProgressCallbacks, XDialogSecret};
 
-use appkit_dialog::AppKitDialog;
+use appkit_dialog::{AppKitDialog, INPUT_ERROR_TAG, INPUT_FIELD_TAG, RADIO_TAG_BASE, VERIFICATION_TAG};
 
 // Global map of dialog result senders, keyed by dialog id.
 // Required because the button_clicked handler is an extern "C" callback
 // that can't capture Rust state — it looks up the sender by dialog id
 // extracted from the button's tag.
-static RESULT_SENDERS: LazyLock<Mutex<HashMap<usize, oneshot::Sender<XDialogResult>>>> =
+static RESULT_SENDERS: LazyLock<Mutex<HashMap<usize, oneshot::Sender<XDialogResponse>>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
-// Global map of progress-dialog button callbacks, keyed by dialog id. Same rationale as
+// Global map of progress-dialog callbacks, keyed by dialog id. Same rationale as
 // RESULT_SENDERS: the extern "C" click handler can't capture Rust state.
-static PROGRESS_CALLBACKS: LazyLock<Mutex<HashMap<usize, ProgressButtonCallback>>> =
+static PROGRESS_CALLBACKS: LazyLock<Mutex<HashMap<usize, ProgressCallbacks>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
-fn send_dialog_result(id: usize, result: XDialogResult) {
+// Global map of input-dialog fields (for their validators) and the index of the affirmative
+// button, keyed by dialog id. Same rationale as RESULT_SENDERS.
+static INPUT_FIELDS: LazyLock<Mutex<HashMap<usize, (InputField, usize)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Build the response for a dialog closed without a button (window close button or
+/// programmatically), reporting `result`.
+fn closed_response(id: usize, dialog: &AppKitDialog, result: XDialogResult) -> XDialogResponse {
+    let mut response = input_response(id, result, dialog.input_text());
+    response.checked = dialog.verification_checked();
+    response.radio_index = dialog.radio_index();
+    response
+}
+
+/// Build the response for an input dialog closing with `text` in its field. A password field's value
+/// goes into `secret` rather than `text`.
+fn input_response(id: usize, result: XDialogResult, text: Option<String>) -> XDialogResponse {
+    let masked = INPUT_FIELDS.lock().unwrap_or_else(|e| e.into_inner()).get(&id).is_some_and(|(field, _)| field.masked);
+    match text {
+        Some(text) if masked => XDialogResponse { secret: Some(XDialogSecret::new(text)), ..result.into() },
+        text => XDialogResponse { text, ..result.into() },
+    }
+}
+
+fn send_dialog_result(id: usize, response: XDialogResponse) {
+    INPUT_FIELDS.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
     if let Some(sender) = RESULT_SENDERS.lock().unwrap_or_else(|e| e.into_inner()).remove(&id) {
-        let _ = sender.send(result);
+        let _ = sender.send(response);
     }
 }
 
+/// Looks up a subview of `sender`'s window by tag.
+unsafe fn find_sibling_view(sender: &AnyObject, tag: isize) -> Option<Retained<AnyObject>> {
+    let window: Option<Retained<AnyObject>> = unsafe { msg_send![sender, window] };
+    let content: Option<Retained<AnyObject>> = unsafe { msg_send![&*window?, contentView] };
+    unsafe { msg_send![&*content?, viewWithTag: tag] }
+}
+
 fn remove_progress_callback(id: usize) {
     PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
 }
 
+/// Asks a progress dialog's close callback whether the user may close it. Returns `true` when the
+/// callback vetoes the close and the dialog should stay open.
+fn close_vetoed(id: usize) -> bool {
+    let mut callbacks = PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner());
+    callbacks.get_mut(&id).is_some_and(|cb| cb.close_requested(id))
+}
+
 pub struct AppKitBackend;
 
 fn register_button_handler_class() {
@@ -54,11 +93,32 @@ fn register_button_handler_class() {
             sel!(buttonClicked:),
             button_clicked as unsafe extern "C" fn(NonNull<AnyObject>, Sel, NonNull<AnyObject>),
         );
+        builder.add_method(
+            sel!(radioClicked:),
+            radio_clicked as unsafe extern "C" fn(NonNull<AnyObject>, Sel, NonNull<AnyObject>),
+        );
+        builder.add_method(
+            sel!(linkClicked:),
+            link_clicked as unsafe extern "C" fn(NonNull<AnyObject>, Sel, NonNull<AnyObject>),
+        );
     }
 
     builder.register();
 }
 
+/// Target of the radio buttons. AppKit only treats radio buttons as one group when they share an
+/// action, but the selection is read when the dialog closes, so there's nothing to do here.
+unsafe extern "C" fn radio_clicked(_this: NonNull<AnyObject>, _cmd: Sel, _sender: NonNull<AnyObject>) {}
+
+/// Target of the link buttons, which carry their href as their tooltip.
+unsafe extern "C" fn link_clicked(_this: NonNull<AnyObject>, _cmd: Sel, sender: NonNull<AnyObject>) {
+    let sender = unsafe { sender.as_ref() };
+    let href: Option<Retained<NSString>> = unsafe { msg_send![sender, toolTip] };
+    if let Some(href) = href {
+        crate::hyperlink::open_link(&href.to_string());
+    }
+}
+
 unsafe extern "C" fn button_clicked(
     _this: NonNull<AnyObject>,
     _cmd: Sel,
@@ -71,23 +131,54 @@ unsafe extern "C" fn button_clicked(
 
     // If a progress button callback is registered, it decides whether the dialog closes.
     // Otherwise fall back to the default behavior: deliver the result and close.
-    let mut keep_open = false;
-    let has_callback = {
+    let callback_result = {
         let mut callbacks = PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner());
-        if let Some(cb) = callbacks.get_mut(&dialog_id) {
-            let proxy = ProgressDialogProxy::non_owning(dialog_id);
-            keep_open = (cb.0)(button_index, &proxy);
-            true
-        } else {
-            false
-        }
+        callbacks.get_mut(&dialog_id).and_then(|cb| cb.button_clicked(dialog_id, button_index))
     };
+    let keep_open = callback_result.unwrap_or(false);
+
+    if callback_result.is_none() {
+        let field = unsafe { find_sibling_view(sender, INPUT_FIELD_TAG) };
+        let text = field.map(|field| {
+            let value: Retained<NSString> = unsafe { msg_send![&*field, stringValue] };
+            value.to_string()
+        });
+
+        // The affirmative button of an input dialog only closes it with valid text; otherwise the
+        // validator's message is shown under the field.
+        if let Some(text) = text.as_deref() {
+            let mut inputs = INPUT_FIELDS.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((input, affirmative)) = inputs.get_mut(&dialog_id) {
+                if *affirmative == button_index {
+                    if let Err(message) = input.validate(text) {
+                        if let Some(label) = unsafe { find_sibling_view(sender, INPUT_ERROR_TAG) } {
+                            let () = unsafe { msg_send![&*label, setStringValue: &*NSString::from_str(&message)] };
+                            let () = unsafe { msg_send![&*label, setHidden: false] };
+                        }
+                        return;
+                    }
+                }
+            }
+        }
 
-    if !has_callback {
-        send_dialog_result(dialog_id, XDialogResult::ButtonPressed(button_index));
+        let mut response = input_response(dialog_id, XDialogResult::ButtonPressed(button_index), text);
+        response.checked = unsafe { find_sibling_view(sender, VERIFICATION_TAG) }.map(|check| {
+            let state: isize = unsafe { msg_send![&*check, state] };
+            state != 0
+        });
+        let radios: Vec<isize> = (0..)
+            .map_while(|i| unsafe { find_sibling_view(sender, RADIO_TAG_BASE - i) })
+            .map(|radio| unsafe { msg_send![&*radio, state] })
+            .collect();
+        if !radios.is_empty() {
+            response.radio_index = Some(radios.iter().position(|&state| state != 0).unwrap_or(0));
+        }
+        send_dialog_result(dialog_id, response);
     }
 
     if !keep_open {
+        // Closed by a button, so the close callback isn't asked when the window is found hidden.
+        remove_progress_callback(dialog_id);
         let window: Option<Retained<AnyObject>> = unsafe { msg_send![sender, window] };
         if let Some(window) = window {
             let () = unsafe { msg_send![&*window, orderOut: std::ptr::null::<AnyObject>()] };
@@ -114,6 +205,7 @@ impl XDialogBackendImpl for AppKitBackend {
                 Ok(DialogMessageRequest::ExitEventLoop) => return,
                 Ok(
                     message @ (DialogMessageRequest::ShowMessageWindow(..)
+                    | DialogMessageRequest::ShowInputWindow(..)
                     | DialogMessageRequest::ShowProgressWindow(..)),
                 ) => break message,
                 // close/progress updates for dialogs that were never created are no-ops
@@ -172,13 +264,18 @@ impl XDialogBackendImpl for AppKitBackend {
                 }
             }
 
-            // Clean up closed windows
+            // Follow details toggles and clean up closed windows
             dialogs.retain(|id, dialog| {
                 if dialog.is_visible() {
+                    dialog.sync_details();
+                    true
+                } else if close_vetoed(*id) {
+                    // The window has already closed by the time we notice, so bring it back.
+                    dialog.reopen();
                     true
                 } else {
                     remove_progress_callback(*id);
-                    send_dialog_result(*id, XDialogResult::WindowClosed);
+                    send_dialog_result(*id, closed_response(*id, dialog, dialog.dismiss_result()));
                     false
                 }
             });
@@ -213,42 +310,51 @@ impl AppKitBackend {
                     dialog.close();
                 }
                 PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+                INPUT_FIELDS.lock().unwrap_or_else(|e| e.into_inner()).clear();
                 return true;
             }
             DialogMessageRequest::CloseWindow(id) => {
                 if let Some(dialog) = dialogs.remove(&id) {
                     dialog.close();
                     remove_progress_callback(id);
-                    send_dialog_result(id, XDialogResult::WindowClosed);
+                    send_dialog_result(id, closed_response(id, &dialog, XDialogResult::WindowClosed));
                 }
             }
             DialogMessageRequest::ShowMessageWindow(id, options, creation) => {
                 let (dialog_sender, dialog_receiver) = oneshot::channel();
                 RESULT_SENDERS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, dialog_sender);
-                let dialog = AppKitDialog::new(id, options, false, handler);
+                let dialog = AppKitDialog::new(id, options, false, None, handler);
                 dialog.show();
                 dialogs.insert(id, dialog);
                 let _ = creation.send(Ok(dialog_receiver));
             }
-            DialogMessageRequest::ShowProgressWindow(id, options, creation, on_button) => {
+            DialogMessageRequest::ShowInputWindow(id, options, field, creation) => {
                 let (dialog_sender, dialog_receiver) = oneshot::channel();
                 RESULT_SENDERS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, dialog_sender);
-                if let Some(cb) = on_button {
-                    PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, cb);
-                }
-                let dialog = AppKitDialog::new(id, options, true, handler);
+                let affirmative = options.default_button_index().unwrap_or(options.buttons.len().saturating_sub(1));
+                let dialog = AppKitDialog::new(id, options, false, Some(&field), handler);
+                INPUT_FIELDS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, (field, affirmative));
                 dialog.show();
                 dialogs.insert(id, dialog);
                 let _ = creation.send(Ok(dialog_receiver));
             }
-            DialogMessageRequest::SetProgressIndeterminate(id) => {
+            DialogMessageRequest::ShowProgressWindow(id, options, creation, callbacks) => {
+                let (dialog_sender, dialog_receiver) = oneshot::channel();
+                RESULT_SENDERS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, dialog_sender);
+                PROGRESS_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner()).insert(id, callbacks);
+                let dialog = AppKitDialog::new(id, options, true, None, handler);
+                dialog.show();
+                dialogs.insert(id, dialog);
+                let _ = creation.send(Ok(dialog_receiver));
+            }
+            DialogMessageRequest::SetProgressIndeterminate(id, bar) => {
                 if let Some(dialog) = dialogs.get_mut(&id) {
-                    dialog.set_progress_indeterminate();
+                    dialog.set_progress_indeterminate(bar);
                 }
             }
-            DialogMessageRequest::SetProgressValue(id, value) => {
+            DialogMessageRequest::SetProgressValue(id, bar, value) => {
                 if let Some(dialog) = dialogs.get_mut(&id) {
-                    dialog.set_progress_value(value);
+                    dialog.set_progress_value(bar, value);
                 }
             }
             DialogMessageRequest::SetProgressText(id, text) => {
@@ -256,6 +362,36 @@ impl AppKitBackend {
                     dialog.set_body_text(&text);
                 }
             }
+            DialogMessageRequest::SetProgressStatus(id, text) => {
+                if let Some(dialog) = dialogs.get_mut(&id) {
+                    dialog.set_status(&text);
+                }
+            }
+            DialogMessageRequest::SetProgressState(id, bar, state) => {
+                if let Some(dialog) = dialogs.get(&id) {
+                    dialog.set_progress_state(bar, state);
+                }
+            }
+            DialogMessageRequest::SetProgressCaption(id, bar, caption) => {
+                if let Some(dialog) = dialogs.get_mut(&id) {
+                    dialog.set_progress_caption(bar, &caption);
+                }
+            }
+            DialogMessageRequest::SetChecklistStep(id, index, state) => {
+                if let Some(dialog) = dialogs.get_mut(&id) {
+                    dialog.set_checklist_step(index, state);
+                }
+            }
+            DialogMessageRequest::StartChecklistStep(id, index) => {
+                if let Some(dialog) = dialogs.get_mut(&id) {
+                    dialog.start_checklist_step(index);
+                }
+            }
+            DialogMessageRequest::UpdateDialog(id, patch) => {
+                if let Some(dialog) = dialogs.get_mut(&id) {
+                    dialog.apply_patch(patch);
+                }
+            }
         }
         false
     }
@@ -7,7 +7,8 @@ use core_foundation::string::CFString;
 use core_foundation_sys::base::{CFOptionFlags, CFRelease, SInt32};
 use core_foundation_sys::user_notification::*;
 
-use crate::channel::DialogRequestHandler;
+use crate::checklist::Checklist;
+use crate::hyperlink::parse_links;
 use crate::*;
 
 struct NotificationPtr(CFUserNotificationRef);
@@ -28,12 +29,23 @@ const PROGRESS_TICK: f64 = 0.1;
 fn icon_to_alert_level(icon: &XDialogIcon) -> CFOptionFlags {
     match icon {
         XDialogIcon::Error => kCFUserNotificationStopAlertLevel,
-        XDialogIcon::Information => kCFUserNotificationNoteAlertLevel,
-        XDialogIcon::Warning => kCFUserNotificationCautionAlertLevel,
+        // Alerts only show the application's icon, so a custom image falls back to a note.
+        XDialogIcon::Information | XDialogIcon::Question | XDialogIcon::Success | XDialogIcon::Custom(_) => {
+            kCFUserNotificationNoteAlertLevel
+        }
+        XDialogIcon::Warning | XDialogIcon::Shield => kCFUserNotificationCautionAlertLevel,
         XDialogIcon::None => kCFUserNotificationPlainAlertLevel,
     }
 }
 
+/// The indices of the buttons to show, in slot order: the alert's first (default) slot is the one
+/// Return activates, so the default button goes there and the others follow in order. Alerts show
+/// at most three buttons.
+fn button_slots(options: &XDialogOptions) -> Vec<usize> {
+    let default = options.default_button_index();
+    default.into_iter().chain((0..options.buttons.len()).filter(|&i| Some(i) != default)).take(3).collect()
+}
+
 fn build_notification_dict(options: &XDialogOptions) -> CFDictionary<CFString, CFString> {
     let mut pairs: Vec<(CFString, CFString)> = Vec::new();
 
@@ -50,12 +62,12 @@ fn build_notification_dict(options: &XDialogOptions) -> CFDictionary<CFString, C
         ));
     }
 
-    // Message body
+    // Message body. Alerts can't show links, so only their text is kept.
     if !options.message.is_empty() {
         unsafe {
             pairs.push((
                 CFString::wrap_under_get_rule(kCFUserNotificationAlertMessageKey),
-                CFString::new(&options.message),
+                CFString::new(&parse_links(&options.message).0),
             ));
         }
     }
@@ -68,11 +80,11 @@ fn build_notification_dict(options: &XDialogOptions) -> CFDictionary<CFString, C
             kCFUserNotificationOtherButtonTitleKey,
         ]
     };
-    for (i, button) in options.buttons.iter().take(3).enumerate() {
+    for (i, index) in button_slots(options).into_iter().enumerate() {
         unsafe {
             pairs.push((
                 CFString::wrap_under_get_rule(button_keys[i]),
-                CFString::new(button),
+                CFString::new(&options.buttons[index]),
             ));
         }
     }
@@ -116,32 +128,107 @@ fn render_bar(mode: ProgressMode, frame: usize) -> String {
     }
 }
 
-/// Composes the dialog body: the caller's text, a blank line, then the progress bar.
-fn compose_progress_message(body: &str, bar: &str) -> String {
-    if body.is_empty() {
-        bar.to_string()
-    } else {
-        format!("{}\n\n{}", body, bar)
+/// A text bar can't change colour, so a paused or failed bar is marked with a symbol after it.
+fn state_marker(state: ProgressBarState) -> &'static str {
+    match state {
+        ProgressBarState::Normal => "",
+        ProgressBarState::Paused => " ⏸",
+        ProgressBarState::Error => " ⚠",
+    }
+}
+
+/// One of a progress dialog's bars: a text bar with its optional caption on the line above.
+struct TextBar {
+    caption: String,
+    mode: ProgressMode,
+    state: ProgressBarState,
+}
+
+impl TextBar {
+    fn new(caption: &str) -> Self {
+        // Default to determinate at 0, matching the other backends' initial progress state.
+        TextBar { caption: caption.to_string(), mode: ProgressMode::Determinate(0.0), state: ProgressBarState::Normal }
+    }
+
+    fn render(&self, frame: usize) -> String {
+        let bar = render_bar(self.mode, frame) + state_marker(self.state);
+        if self.caption.is_empty() {
+            bar
+        } else {
+            format!("{}\n{}", self.caption, bar)
+        }
     }
 }
 
+/// Composes the dialog body: the caller's text, the checklist, the status line, then the progress
+/// bars, each separated by a blank line. Empty parts are left out.
+fn compose_progress_message(body: &str, checklist: &str, status: &str, bars: &str) -> String {
+    [body, checklist, status, bars].iter().filter(|part| !part.is_empty()).copied().collect::<Vec<_>>().join("\n\n")
+}
+
 /// Mutable state shared between the animation thread that owns a progress dialog and the request
 /// handler, which mutates it in response to `SetProgress*`/`CloseWindow` from other threads.
 struct ProgressState {
     /// The live notification. Replaced if the dialog has to be recreated (see the keep-open path).
     notification: NotificationPtr,
     icon_flags: CFOptionFlags,
-    header: String,
+    title: String,
+    main_instruction: String,
     buttons: Vec<String>,
-    /// Body text set via `set_text`; the progress bar is appended below it on render.
+    /// Body text set via `set_text`; the progress bars are appended below it on render.
     body: String,
-    mode: ProgressMode,
+    /// Status line set via `set_status`, shown between the body text and the progress bars.
+    status: String,
+    bars: Vec<TextBar>,
+    /// Checklist steps, listed as text above the status line.
+    checklist: Checklist,
     /// Set when the body/value/mode changed so a determinate dialog re-renders on the next tick.
     dirty: bool,
     /// Set by `CloseWindow` to ask the animation thread to exit.
     closed: bool,
 }
 
+impl ProgressState {
+    /// The alert header: the main instruction, falling back to the title when it is empty.
+    fn header(&self) -> &str {
+        if self.main_instruction.is_empty() {
+            &self.title
+        } else {
+            &self.main_instruction
+        }
+    }
+
+    /// Change the bar at `index`, if there is one; it shows on the next tick.
+    fn update_bar<F: FnOnce(&mut TextBar)>(&mut self, index: usize, f: F) {
+        if let Some(bar) = self.bars.get_mut(index) {
+            f(bar);
+            self.dirty = true;
+        }
+    }
+
+    /// Apply a patch from `UpdateDialog`; it shows on the next tick.
+    fn apply_patch(&mut self, patch: DialogPatch) {
+        if let Some(title) = patch.title {
+            self.title = title;
+        }
+        if let Some(main_instruction) = patch.main_instruction {
+            self.main_instruction = main_instruction;
+        }
+        if let Some(message) = patch.message {
+            self.body = parse_links(&message).0;
+        }
+        if let Some(icon) = patch.icon {
+            self.icon_flags = icon_to_alert_level(&icon);
+        }
+        for (index, label) in patch.button_labels {
+            if let Some(button) = self.buttons.get_mut(index) {
+                *button = label;
+            }
+        }
+        self.dirty = true;
+    }
+}
+
 struct ProgressShared {
     state: Mutex<ProgressState>,
 }
@@ -192,7 +279,7 @@ impl DialogRequestHandler for MacCfDirectHandler {
                     };
 
                     if notification.is_null() || error != 0 {
-                        let _ = dialog_sender.send(XDialogResult::WindowClosed);
+                        let _ = dialog_sender.send(XDialogResult::WindowClosed.into());
                         return;
                     }
 
@@ -206,23 +293,34 @@ impl DialogRequestHandler for MacCfDirectHandler {
                     active.lock().unwrap().remove(&id);
 
                     let response = response_flags & 0x3;
-                    let result = if response == kCFUserNotificationDefaultResponse {
-                        XDialogResult::ButtonPressed(0)
+                    let slot = if response == kCFUserNotificationDefaultResponse {
+                        Some(0)
                     } else if response == kCFUserNotificationAlternateResponse {
-                        XDialogResult::ButtonPressed(1)
+                        Some(1)
                     } else if response == kCFUserNotificationOtherResponse {
-                        XDialogResult::ButtonPressed(2)
+                        Some(2)
                     } else {
-                        XDialogResult::WindowClosed
+                        None
+                    };
+                    let slots = button_slots(&options);
+                    let result = match slot {
+                        Some(slot) => XDialogResult::ButtonPressed(slots.get(slot).copied().unwrap_or(slot)),
+                        None => XDialogResult::WindowClosed,
                     };
 
-                    let _ = dialog_sender.send(result);
+                    let _ = dialog_sender.send(result.into());
 
                     unsafe { CFRelease(notification as *const _) };
                 });
 
                 Ok(())
             }
+            DialogMessageRequest::ShowInputWindow(_id, _options, _field, creation_sender) => {
+                let _ = creation_sender.send(Err(XDialogError::SystemError(
+                    "text input is not supported by the CFUserNotification backend".to_string(),
+                )));
+                Ok(())
+            }
             DialogMessageRequest::CloseWindow(id) => {
                 let guard = self.active.lock().unwrap();
                 match guard.get(&id) {
@@ -240,34 +338,56 @@ impl DialogRequestHandler for MacCfDirectHandler {
                 }
                 Ok(())
             }
-            DialogMessageRequest::ShowProgressWindow(id, options, creation_sender, on_button) => {
+            DialogMessageRequest::ShowProgressWindow(id, options, creation_sender, callbacks) => {
                 let active = Arc::clone(&self.active);
                 std::thread::spawn(move || {
-                    run_progress_dialog(id, options, on_button, active, creation_sender);
+                    run_progress_dialog(id, options, callbacks, active, creation_sender);
                 });
                 Ok(())
             }
-            DialogMessageRequest::SetProgressValue(id, value) => {
-                self.update_progress(id, |st| {
-                    st.mode = ProgressMode::Determinate(value);
-                    st.dirty = true;
-                });
+            DialogMessageRequest::SetProgressValue(id, bar, value) => {
+                self.update_progress(id, |st| st.update_bar(bar, |b| b.mode = ProgressMode::Determinate(value)));
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressIndeterminate(id, bar) => {
+                self.update_progress(id, |st| st.update_bar(bar, |b| b.mode = ProgressMode::Indeterminate));
                 Ok(())
             }
-            DialogMessageRequest::SetProgressIndeterminate(id) => {
+            DialogMessageRequest::SetProgressText(id, text) => {
                 self.update_progress(id, |st| {
-                    st.mode = ProgressMode::Indeterminate;
+                    st.body = parse_links(&text).0;
                     st.dirty = true;
                 });
                 Ok(())
             }
-            DialogMessageRequest::SetProgressText(id, text) => {
+            DialogMessageRequest::SetProgressStatus(id, text) => {
                 self.update_progress(id, |st| {
-                    st.body = text;
+                    st.status = text;
                     st.dirty = true;
                 });
                 Ok(())
             }
+            DialogMessageRequest::SetProgressState(id, bar, state) => {
+                self.update_progress(id, |st| st.update_bar(bar, |b| b.state = state));
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressCaption(id, bar, caption) => {
+                self.update_progress(id, |st| st.update_bar(bar, |b| b.caption = caption));
+                Ok(())
+            }
+            DialogMessageRequest::SetChecklistStep(id, index, state) => {
+                self.update_progress(id, |st| st.dirty |= st.checklist.set(index, state));
+                Ok(())
+            }
+            DialogMessageRequest::StartChecklistStep(id, index) => {
+                self.update_progress(id, |st| st.dirty |= st.checklist.start(index));
+                Ok(())
+            }
+            DialogMessageRequest::UpdateDialog(id, patch) => {
+                // Message alerts can't be changed once shown; only progress dialogs re-render.
+                self.update_progress(id, |st| st.apply_patch(patch));
+                Ok(())
+            }
             DialogMessageRequest::ExitEventLoop | DialogMessageRequest::None => Ok(()),
         }
     }
@@ -280,12 +400,12 @@ fn build_progress_dict(state: &ProgressState, frame: usize) -> CFDictionary<CFSt
     unsafe {
         pairs.push((
             CFString::wrap_under_get_rule(kCFUserNotificationAlertHeaderKey),
-            CFString::new(&state.header),
+            CFString::new(state.header()),
         ));
     }
 
-    let bar = render_bar(state.mode, frame);
-    let message = compose_progress_message(&state.body, &bar);
+    let bars = state.bars.iter().map(|bar| bar.render(frame)).collect::<Vec<_>>().join("\n\n");
+    let message = compose_progress_message(&state.body, &state.checklist.to_text(), &state.status, &bars);
     unsafe {
         pairs.push((
             CFString::wrap_under_get_rule(kCFUserNotificationAlertMessageKey),
@@ -317,25 +437,22 @@ fn build_progress_dict(state: &ProgressState, frame: usize) -> CFDictionary<CFSt
 fn run_progress_dialog(
     id: usize,
     options: XDialogOptions,
-    mut on_button: Option<ProgressButtonCallback>,
+    mut callbacks: ProgressCallbacks,
     active: Arc<Mutex<HashMap<usize, Active>>>,
     creation_sender: CreationSender,
 ) {
     let icon_flags = icon_to_alert_level(&options.icon);
-    let header = if options.main_instruction.is_empty() {
-        options.title.clone()
-    } else {
-        options.main_instruction.clone()
-    };
 
-    // Default to determinate at 0, matching the other backends' initial progress state.
     let mut state = ProgressState {
         notification: NotificationPtr(std::ptr::null_mut()),
         icon_flags,
-        header,
+        title: options.title.clone(),
+        main_instruction: options.main_instruction.clone(),
         buttons: options.buttons.clone(),
-        body: options.message.clone(),
-        mode: ProgressMode::Determinate(0.0),
+        body: parse_links(&options.message).0,
+        status: String::new(),
+        bars: options.progress_bar_captions().iter().map(|caption| TextBar::new(caption)).collect(),
+        checklist: Checklist::new(&options.checklist),
         dirty: false,
         closed: false,
     };
@@ -359,13 +476,13 @@ fn run_progress_dialog(
     let (dialog_sender, dialog_receiver) = oneshot::channel();
     let _ = creation_sender.send(Ok(dialog_receiver));
 
-    let result = run_progress_loop(&shared, &mut on_button, id);
+    let result = run_progress_loop(&shared, &mut callbacks, id);
 
     // Remove from the active map (under lock) before releasing the notification, so a concurrent
     // CloseWindow cannot cancel a notification we are about to free.
     active.lock().unwrap().remove(&id);
     let final_ptr = shared.state.lock().unwrap().notification.0;
-    let _ = dialog_sender.send(result);
+    let _ = dialog_sender.send(result.into());
     unsafe { CFRelease(final_ptr as *const _) };
 }
 
@@ -373,7 +490,7 @@ fn run_progress_dialog(
 /// awaiting the dialog (progress callers normally discard it).
 fn run_progress_loop(
     shared: &Arc<ProgressShared>,
-    on_button: &mut Option<ProgressButtonCallback>,
+    callbacks: &mut ProgressCallbacks,
     id: usize,
 ) -> XDialogResult {
     let mut frame: usize = 0;
@@ -384,7 +501,7 @@ fn run_progress_loop(
             if st.closed {
                 return XDialogResult::WindowClosed;
             }
-            let indeterminate = matches!(st.mode, ProgressMode::Indeterminate);
+            let indeterminate = st.bars.iter().any(|bar| matches!(bar.mode, ProgressMode::Indeterminate));
             // Indeterminate redraws every tick to animate; determinate only when something changed.
             if indeterminate || st.dirty {
                 let dict = build_progress_dict(&st, frame);
@@ -409,6 +526,12 @@ fn run_progress_loop(
 
         let response = response_flags & 0x3;
         if response == kCFUserNotificationCancelResponse {
+            // Unless we cancelled it for a CloseWindow, the close callback may keep the dialog open,
+            // which (like a kept-open button click) means recreating the notification.
+            let closed = shared.state.lock().unwrap().closed;
+            if !closed && callbacks.close_requested(id) && recreate_progress_notification(shared, frame) {
+                continue;
+            }
             return XDialogResult::WindowClosed;
         }
         let button_index = if response == kCFUserNotificationDefaultResponse {
@@ -421,13 +544,7 @@ fn run_progress_loop(
             return XDialogResult::WindowClosed;
         };
 
-        let keep_open = match on_button {
-            Some(cb) => {
-                let proxy = ProgressDialogProxy::non_owning(id);
-                (cb.0)(button_index, &proxy)
-            }
-            None => false,
-        };
+        let keep_open = callbacks.button_clicked(id, button_index).unwrap_or(false);
 
         if !keep_open {
             return XDialogResult::ButtonPressed(button_index);
@@ -441,8 +558,8 @@ fn run_progress_loop(
     }
 }
 
-/// Recreates a progress dialog's notification in place (used after a button click when the caller
-/// wants the dialog to stay open). Returns false if recreation failed or a close was requested.
+/// Recreates a progress dialog's notification in place (used after a button click or close when the
+/// caller wants the dialog to stay open). Returns false if recreation failed or a close was requested.
 fn recreate_progress_notification(shared: &Arc<ProgressShared>, frame: usize) -> bool {
     let mut st = shared.state.lock().unwrap();
     if st.closed {
@@ -4,10 +4,10 @@ use std::rc::Rc;
 use mina::prelude::*;
 use tiny_skia::PixmapMut;
 
-use super::component::{Component, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::component::{Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
 use super::renderer::{fill_rect, fill_rounded_rect, stroke_rounded_rect};
 use super::text::{measure_text_width, render_text, CachedLayout};
-use super::theme::SkiaTheme;
+use super::theme::{blend, SkiaTheme};
 
 #[derive(Animate, Clone, Debug, Default, PartialEq)]
 pub struct ButtonColorState {
@@ -43,6 +43,8 @@ pub struct SkiaButton {
     hovered: bool,
     pressed: bool,
     focused: bool,
+    /// Disabled buttons draw a dimmed label and ignore focus, hover and activation.
+    enabled: bool,
     dirty: bool,
     animating: bool,
     current_state: ButtonState,
@@ -52,34 +54,41 @@ pub struct SkiaButton {
 }
 
 impl SkiaButton {
-    pub fn new(label: &str, index: usize, theme: &SkiaTheme) -> Self {
+    /// Create a button reporting `index` when activated. A `primary` (default) button is styled as
+    /// the dialog's main action.
+    pub fn new(label: &str, index: usize, theme: &SkiaTheme, primary: bool) -> Self {
+        let (idle, hover, focused) = if primary {
+            (&theme.style_button_primary, &theme.style_button_primary_hover, &theme.style_button_primary_focused)
+        } else {
+            (&theme.style_button_inactive, &theme.style_button_hover, &theme.style_button_focused)
+        };
         let animator = animator!(ButtonColorState {
             default(ButtonState::Idle, {
-                border_radius: theme.style_button_inactive.border_radius,
-                border_width: theme.style_button_inactive.border_width,
-                border_r: theme.style_button_inactive.border_color.0,
-                border_g: theme.style_button_inactive.border_color.1,
-                border_b: theme.style_button_inactive.border_color.2,
-                fill_r: theme.style_button_inactive.background_color.0,
-                fill_g: theme.style_button_inactive.background_color.1,
-                fill_b: theme.style_button_inactive.background_color.2,
-                text_r: theme.style_button_inactive.text_color.0,
-                text_g: theme.style_button_inactive.text_color.1,
-                text_b: theme.style_button_inactive.text_color.2,
+                border_radius: idle.border_radius,
+                border_width: idle.border_width,
+                border_r: idle.border_color.0,
+                border_g: idle.border_color.1,
+                border_b: idle.border_color.2,
+                fill_r: idle.background_color.0,
+                fill_g: idle.background_color.1,
+                fill_b: idle.background_color.2,
+                text_r: idle.text_color.0,
+                text_g: idle.text_color.1,
+                text_b: idle.text_color.2,
             }),
             ButtonState::Idle => 0.15s to default,
             ButtonState::Hovered => 0.15s to {
-                border_radius: theme.style_button_hover.border_radius,
-                border_width: theme.style_button_hover.border_width,
-                border_r: theme.style_button_hover.border_color.0,
-                border_g: theme.style_button_hover.border_color.1,
-                border_b: theme.style_button_hover.border_color.2,
-                fill_r: theme.style_button_hover.background_color.0,
-                fill_g: theme.style_button_hover.background_color.1,
-                fill_b: theme.style_button_hover.background_color.2,
-                text_r: theme.style_button_hover.text_color.0,
-                text_g: theme.style_button_hover.text_color.1,
-                text_b: theme.style_button_hover.text_color.2,
+                border_radius: hover.border_radius,
+                border_width: hover.border_width,
+                border_r: hover.border_color.0,
+                border_g: hover.border_color.1,
+                border_b: hover.border_color.2,
+                fill_r: hover.background_color.0,
+                fill_g: hover.background_color.1,
+                fill_b: hover.background_color.2,
+                text_r: hover.text_color.0,
+                text_g: hover.text_color.1,
+                text_b: hover.text_color.2,
             },
             ButtonState::Pressed => 0.15s to {
                 border_radius: theme.style_button_pressed.border_radius,
@@ -95,17 +104,17 @@ impl SkiaButton {
                 text_b: theme.style_button_pressed.text_color.2,
             },
             ButtonState::Focused => 0.15s to {
-                border_radius: theme.style_button_focused.border_radius,
-                border_width: theme.style_button_focused.border_width,
-                border_r: theme.style_button_focused.border_color.0,
-                border_g: theme.style_button_focused.border_color.1,
-                border_b: theme.style_button_focused.border_color.2,
-                fill_r: theme.style_button_focused.background_color.0,
-                fill_g: theme.style_button_focused.background_color.1,
-                fill_b: theme.style_button_focused.background_color.2,
-                text_r: theme.style_button_focused.text_color.0,
-                text_g: theme.style_button_focused.text_color.1,
-                text_b: theme.style_button_focused.text_color.2,
+                border_radius: focused.border_radius,
+                border_width: focused.border_width,
+                border_r: focused.border_color.0,
+                border_g: focused.border_color.1,
+                border_b: focused.border_color.2,
+                fill_r: focused.background_color.0,
+                fill_g: focused.background_color.1,
+                fill_b: focused.background_color.2,
+                text_r: focused.text_color.0,
+                text_g: focused.text_color.1,
+                text_b: focused.text_color.2,
             },
         });
 
@@ -116,6 +125,7 @@ impl SkiaButton {
             hovered: false,
             pressed: false,
             focused: false,
+            enabled: true,
             dirty: true,
             animating: false,
             current_state: ButtonState::Idle,
@@ -125,7 +135,9 @@ impl SkiaButton {
     }
 
     fn update_state(&mut self) {
-        let state = if self.pressed {
+        let state = if !self.enabled {
+            ButtonState::Idle
+        } else if self.pressed {
             ButtonState::Pressed
         } else if self.hovered {
             ButtonState::Hovered
@@ -210,16 +222,14 @@ impl Component for SkiaButton {
             );
         }
 
+        let mut text_color = (colors.text_r, colors.text_g, colors.text_b);
+        if !self.enabled {
+            text_color = blend(text_color, (colors.fill_r, colors.fill_g, colors.fill_b), 0.6);
+        }
         let label_layout = self.label_cache.get(&self.label, false, BODY_SIZE * s, bw);
         let text_x = bx + (bw - label_layout.total_width) / 2.0;
         let text_y = by + (bh - label_layout.total_height) / 2.0;
-        render_text(
-            pm,
-            label_layout,
-            (colors.text_r, colors.text_g, colors.text_b),
-            text_x,
-            text_y,
-        );
+        render_text(pm, label_layout, text_color, text_x, text_y);
 
         self.dirty = false;
         Rect::new(bx, by, bw, bh)
@@ -243,7 +253,7 @@ impl Component for SkiaButton {
     }
 
     fn focusable(&self) -> bool {
-        true
+        self.enabled
     }
 
     fn set_hovered(&mut self, v: bool) {
@@ -279,6 +289,26 @@ impl Component for SkiaButton {
     }
 
     fn activation_index(&self) -> Option<usize> {
-        Some(self.index)
+        self.enabled.then_some(self.index)
+    }
+
+    fn apply(&mut self, u: &ControllerUpdate) -> bool {
+        match *u {
+            ControllerUpdate::ButtonEnabled(index, enabled) if index == self.index && enabled != self.enabled => {
+                self.enabled = enabled;
+                if !enabled {
+                    self.focused = false;
+                }
+                self.update_state();
+                self.dirty = true;
+                false
+            }
+            ControllerUpdate::ButtonLabel(index, label) if index == self.index && label != self.label => {
+                self.label = label.to_string();
+                self.dirty = true;
+                true // the button width follows its label → relayout
+            }
+            _ => false,
+        }
     }
 }
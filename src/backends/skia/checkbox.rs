@@ -0,0 +1,163 @@
+//! The verification checkbox ("Don't ask me again") shown at the left end of the button strip.
+
+use tiny_skia::PixmapMut;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
+
+use crate::model::XDialogResponse;
+
+use super::component::{Component, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::renderer::{fill_rect, fill_rounded_rect, stroke_line, stroke_rounded_rect};
+use super::text::{measure_text_width, render_text_clipped, CachedLayout};
+
+/// Side length of the check box in logical pixels.
+const BOX_SIZE: f32 = 16.0;
+/// Gap between the box and its label in logical pixels.
+const LABEL_GAP: f32 = 8.0;
+/// Inset of the box from the left edge, leaving room for the focus ring inside the bounds.
+const RING_INSET: f32 = 3.0;
+/// Height of the whole control in logical pixels.
+const CHECKBOX_HEIGHT: f32 = 20.0;
+
+pub struct Checkbox {
+    label: String,
+    checked: bool,
+    bounds: Rect,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    dirty: bool,
+    cache: CachedLayout,
+}
+
+impl Checkbox {
+    pub fn new(label: &str, checked: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            checked,
+            bounds: Rect::default(),
+            hovered: false,
+            pressed: false,
+            focused: false,
+            dirty: true,
+            cache: CachedLayout::default(),
+        }
+    }
+}
+
+impl Component for Checkbox {
+    fn role(&self) -> Role {
+        Role::FooterControl
+    }
+
+    fn measure(&mut self, _ctx: &LayoutCtx) -> Size {
+        Size {
+            w: RING_INSET + BOX_SIZE + LABEL_GAP + measure_text_width(&self.label, false, BODY_SIZE),
+            h: CHECKBOX_HEIGHT,
+        }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let (x, y, w, h) = (self.bounds.x * s, self.bounds.y * s, self.bounds.w * s, self.bounds.h * s);
+        fill_rect(pm, x, y, w, h, theme.color_background_alt);
+
+        // Box: accent-filled with a white tick when checked, outlined otherwise. Hover and focus
+        // both darken the outline to the accent colour.
+        let size = BOX_SIZE * s;
+        let (bx, by) = (x + RING_INSET * s, y + (h - size) / 2.0);
+        let radius = 3.0 * s;
+        if self.checked {
+            fill_rounded_rect(pm, bx, by, size, size, radius, theme.color_field_border_focused);
+            let stroke = 2.0 * s;
+            let (x0, y0) = (bx + size * 0.25, by + size * 0.5);
+            let (x1, y1) = (bx + size * 0.43, by + size * 0.7);
+            let (x2, y2) = (bx + size * 0.76, by + size * 0.3);
+            stroke_line(pm, x0, y0, x1, y1, (0xFF, 0xFF, 0xFF), stroke);
+            stroke_line(pm, x1, y1, x2, y2, (0xFF, 0xFF, 0xFF), stroke);
+        } else {
+            let border = if self.hovered || self.focused { theme.color_field_border_focused } else { theme.color_field_border };
+            fill_rounded_rect(pm, bx, by, size, size, radius, theme.color_field_background);
+            stroke_rounded_rect(pm, bx, by, size, size, radius, border, s);
+        }
+        if self.focused {
+            let (pad, ring) = (2.0 * s, size + 4.0 * s);
+            stroke_rounded_rect(pm, bx - pad, by - pad, ring, ring, radius + pad, theme.color_field_border_focused, s);
+        }
+
+        // The label is clipped (rather than wrapped) if the layout couldn't give it its full width.
+        let text_x = x + (RING_INSET + BOX_SIZE + LABEL_GAP) * s;
+        let layout = self.cache.get(&self.label, false, BODY_SIZE * s, f32::INFINITY);
+        let text_y = y + (h - layout.total_height) / 2.0;
+        let clip = Rect::new(text_x, y, (x + w - text_x).max(0.0), h);
+        render_text_clipped(pm, layout, theme.color_body_text, text_x, text_y, clip);
+
+        self.dirty = false;
+        Rect::new(x, y, w, h)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_hovered(&mut self, v: bool) {
+        if self.hovered != v {
+            self.hovered = v;
+            self.dirty = true;
+        }
+    }
+
+    fn set_pressed(&mut self, v: bool) {
+        self.pressed = v;
+    }
+
+    fn set_focused(&mut self, v: bool) {
+        if self.focused != v {
+            self.focused = v;
+            self.dirty = true;
+        }
+    }
+
+    fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    fn handle_key(&mut self, k: &KeyInput) -> bool {
+        if matches!(k.key, Key::Named(NamedKey::Space)) {
+            self.checked = !self.checked;
+            self.dirty = true;
+            return true;
+        }
+        false
+    }
+
+    fn pointer_pressed(&mut self, _x: f32, _y: f32) {
+        self.checked = !self.checked;
+        self.dirty = true;
+    }
+
+    fn cursor_icon(&self, _x: f32, _y: f32) -> CursorIcon {
+        CursorIcon::Pointer
+    }
+
+    fn collect(&self, response: &mut XDialogResponse) {
+        response.checked = Some(self.checked);
+    }
+}
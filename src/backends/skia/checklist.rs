@@ -0,0 +1,123 @@
+//! The steps of a multi-phase operation, listed in the content column of a progress dialog, each
+//! with a marker for its state: an outline while pending, an accent ring while running, and a tick
+//! or a cross once done or failed.
+
+use tiny_skia::PixmapMut;
+
+use crate::checklist::Checklist;
+use crate::ChecklistStepState;
+
+use super::component::{Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::renderer::{fill_circle, fill_rect, stroke_circle, stroke_line};
+use super::text::{measure_text_width, render_text_clipped, CachedLayout};
+
+/// Diameter of each step marker in logical pixels.
+const MARKER_SIZE: f32 = 16.0;
+/// Gap between a marker and its label in logical pixels.
+const LABEL_GAP: f32 = 8.0;
+/// Height of each step row in logical pixels.
+const ROW_HEIGHT: f32 = 24.0;
+
+pub struct SkiaChecklist {
+    checklist: Checklist,
+    bounds: Rect,
+    dirty: bool,
+    caches: Vec<CachedLayout>,
+}
+
+impl SkiaChecklist {
+    pub fn new(steps: &[String]) -> Self {
+        Self {
+            checklist: Checklist::new(steps),
+            bounds: Rect::default(),
+            dirty: true,
+            caches: steps.iter().map(|_| CachedLayout::default()).collect(),
+        }
+    }
+}
+
+impl Component for SkiaChecklist {
+    fn role(&self) -> Role {
+        Role::Content
+    }
+
+    fn measure(&mut self, _ctx: &LayoutCtx) -> Size {
+        // Measured bold, so a label doesn't outgrow the layout while its step is running.
+        let label_w = self.checklist.steps().iter().map(|(label, _)| measure_text_width(label, true, BODY_SIZE)).fold(0.0, f32::max);
+        Size {
+            w: MARKER_SIZE + LABEL_GAP + label_w,
+            h: ROW_HEIGHT * self.checklist.steps().len() as f32,
+        }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let (x, y, w, h) = (self.bounds.x * s, self.bounds.y * s, self.bounds.w * s, self.bounds.h * s);
+        fill_rect(pm, x, y, w, h, theme.color_background);
+
+        let radius = MARKER_SIZE * s / 2.0;
+        let cx = x + radius;
+        let text_x = x + (MARKER_SIZE + LABEL_GAP) * s;
+        let stroke = 2.0 * s;
+        let white = (0xFF, 0xFF, 0xFF);
+        for (i, (label, state)) in self.checklist.steps().iter().enumerate() {
+            let row_y = y + ROW_HEIGHT * s * i as f32;
+            let cy = row_y + ROW_HEIGHT * s / 2.0;
+            let (left, top, size) = (cx - radius, cy - radius, MARKER_SIZE * s);
+
+            match state {
+                ChecklistStepState::Pending => stroke_circle(pm, cx, cy, radius - s / 2.0, theme.color_field_border, s),
+                ChecklistStepState::Running => {
+                    stroke_circle(pm, cx, cy, radius - s, theme.color_progress_foreground, stroke);
+                    fill_circle(pm, cx, cy, radius * 0.45, theme.color_progress_foreground);
+                }
+                ChecklistStepState::Done => {
+                    fill_circle(pm, cx, cy, radius, theme.color_progress_foreground);
+                    let (x0, y0) = (left + size * 0.28, top + size * 0.52);
+                    let (x1, y1) = (left + size * 0.44, top + size * 0.68);
+                    let (x2, y2) = (left + size * 0.72, top + size * 0.34);
+                    stroke_line(pm, x0, y0, x1, y1, white, stroke);
+                    stroke_line(pm, x1, y1, x2, y2, white, stroke);
+                }
+                ChecklistStepState::Failed => {
+                    fill_circle(pm, cx, cy, radius, theme.color_progress_error_foreground);
+                    let (near, far) = (size * 0.32, size * 0.68);
+                    stroke_line(pm, left + near, top + near, left + far, top + far, white, stroke);
+                    stroke_line(pm, left + far, top + near, left + near, top + far, white, stroke);
+                }
+            }
+
+            let bold = *state == ChecklistStepState::Running;
+            // Clipped to its row, like the radio buttons' labels, if the layout couldn't give it its full width.
+            let layout = self.caches[i].get(label, bold, BODY_SIZE * s, f32::INFINITY);
+            let clip = Rect::new(text_x, row_y, (x + w - text_x).max(0.0), ROW_HEIGHT * s);
+            render_text_clipped(pm, layout, theme.color_body_text, text_x, cy - layout.total_height / 2.0, clip);
+        }
+
+        self.dirty = false;
+        Rect::new(x, y, w, h)
+    }
+
+    fn apply(&mut self, u: &ControllerUpdate) -> bool {
+        let changed = match *u {
+            ControllerUpdate::ChecklistStep(index, state) => self.checklist.set(index, state),
+            ControllerUpdate::ChecklistStart(index) => self.checklist.start(index),
+            _ => false,
+        };
+        self.dirty |= changed;
+        false // the rows keep their size
+    }
+}
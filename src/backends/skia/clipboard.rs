@@ -0,0 +1,79 @@
+//! Best-effort access to the desktop clipboard for the text field.
+//!
+//! winit exposes no clipboard, and linking the X11/Wayland clipboard protocols directly would pull
+//! in a sizeable dependency tree for a single dialog feature. Instead this shells out to the
+//! standard helper tools (`wl-clipboard` on Wayland, `xclip` or `xsel` on X11), which nearly every
+//! desktop has installed. If none is available paste does nothing and copy is dropped.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long a paste may block the event loop waiting for the helper tool.
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+fn read_commands() -> &'static [&'static [&'static str]] {
+    if is_wayland() {
+        &[&["wl-paste", "--no-newline"], &["xclip", "-selection", "clipboard", "-o"], &["xsel", "--clipboard", "--output"]]
+    } else {
+        &[&["xclip", "-selection", "clipboard", "-o"], &["xsel", "--clipboard", "--output"], &["wl-paste", "--no-newline"]]
+    }
+}
+
+fn write_commands() -> &'static [&'static [&'static str]] {
+    if is_wayland() {
+        &[&["wl-copy"], &["xclip", "-selection", "clipboard", "-i"], &["xsel", "--clipboard", "--input"]]
+    } else {
+        &[&["xclip", "-selection", "clipboard", "-i"], &["xsel", "--clipboard", "--input"], &["wl-copy"]]
+    }
+}
+
+/// Read the clipboard as text, or `None` if no helper is installed or the clipboard holds no text.
+pub fn read() -> Option<String> {
+    for cmd in read_commands() {
+        let Ok(child) = Command::new(cmd[0]).args(&cmd[1..]).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::null()).spawn()
+        else {
+            continue; // not installed, try the next one
+        };
+
+        // Collect the output on a helper thread so a stuck tool can't hang the event loop. On
+        // timeout the thread is abandoned; it exits on its own once the tool does.
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(child.wait_with_output());
+        });
+        return match rx.recv_timeout(READ_TIMEOUT) {
+            Ok(Ok(output)) if output.status.success() => String::from_utf8(output.stdout).ok(),
+            Ok(_) => None,
+            Err(_) => {
+                warn!("xdialog: timed out reading the clipboard with {}", cmd[0]);
+                None
+            }
+        };
+    }
+    None
+}
+
+/// Replace the clipboard contents with `text`.
+pub fn write(text: &str) {
+    for cmd in write_commands() {
+        let Ok(mut child) = Command::new(cmd[0]).args(&cmd[1..]).stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        // X11 helpers stay alive to serve the selection until another client takes it over, so
+        // reap the child off the event-loop thread.
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        return;
+    }
+}
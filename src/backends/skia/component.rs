@@ -10,6 +10,11 @@
 //! via [`PaintCtx::scale`], so the layout math is resolution-independent.
 
 use tiny_skia::PixmapMut;
+use winit::keyboard::Key;
+use winit::window::CursorIcon;
+
+use crate::model::{XDialogIcon, XDialogResponse};
+use crate::{ChecklistStepState, ProgressBarState};
 
 use super::theme::SkiaTheme;
 
@@ -65,12 +70,18 @@ pub enum Role {
     Background,
     /// The top-left main icon.
     Icon,
-    /// Part of the vertically-stacked content column (title, progress, body).
+    /// Part of the vertically-stacked content column (title, checklist, progress, body).
     Content,
     /// The bottom button strip fill (painted under the buttons).
     Footer,
     /// An interactive button in the bottom row.
     Button,
+    /// A control at the left end of the button strip (the verification checkbox). Moves to its own
+    /// row above the buttons when the two don't fit side by side.
+    FooterControl,
+    /// The footer note: small text (and an optional small icon) spanning the window width below
+    /// the button strip.
+    FooterNote,
 }
 
 /// Context passed to [`Component::paint`].
@@ -90,13 +101,44 @@ pub struct LayoutCtx<'a> {
 
 /// A controller-driven update broadcast to every component via [`Component::apply`]. Components
 /// that don't care return `false`; this avoids the dialog holding type-specific handles.
+#[derive(Clone, Copy)]
 pub enum ControllerUpdate<'a> {
-    /// Set the determinate progress value (0.0–1.0).
-    ProgressValue(f32),
-    /// Switch the progress bar to its indeterminate animation.
-    ProgressIndeterminate,
+    /// Set the determinate value (0.0–1.0) of the progress bar with the given index.
+    ProgressValue(usize, f32),
+    /// Switch the progress bar with the given index to its indeterminate animation.
+    ProgressIndeterminate(usize),
+    /// Change the state (and so the colours) of the progress bar with the given index.
+    ProgressState(usize, ProgressBarState),
+    /// Replace the caption above the progress bar with the given index; empty hides it.
+    ProgressCaption(usize, &'a str),
+    /// Set the state of the checklist step with the given index.
+    ChecklistStep(usize, ChecklistStepState),
+    /// Start the checklist step with the given index, finishing the ones before it.
+    ChecklistStart(usize),
     /// Replace the body text.
     BodyText(&'a str),
+    /// Replace the status line under the progress bar.
+    StatusText(&'a str),
+    /// Replace the main instruction (the title label); empty hides it.
+    MainInstruction(&'a str),
+    /// Replace the main icon; `XDialogIcon::None` hides it.
+    Icon(&'a XDialogIcon),
+    /// Relabel the button with the given result index.
+    ButtonLabel(usize, &'a str),
+    /// Show (`Some`) or clear (`None`) the inline validation error under the text field.
+    InputError(Option<&'a str>),
+    /// Enable or disable the button with the given result index.
+    ButtonEnabled(usize, bool),
+}
+
+/// A key press routed to the focused component (via [`Component::handle_key`]) before the dialog
+/// applies its own navigation keys.
+pub struct KeyInput<'a> {
+    pub key: &'a Key,
+    /// The text the key produces, if any (already accounts for Shift / the keyboard layout).
+    pub text: Option<&'a str>,
+    pub ctrl: bool,
+    pub shift: bool,
 }
 
 /// A self-contained, self-painting piece of a dialog.
@@ -104,7 +146,8 @@ pub enum ControllerUpdate<'a> {
 /// The dialog never inspects concrete types: layout uses [`Component::role`], painting uses
 /// [`Component::is_dirty`]/[`Component::paint`], input uses the interaction methods, and dynamic
 /// changes flow through [`Component::apply`]. Default implementations make most components (the
-/// static ones) trivial — only buttons and the progress bar override behaviour.
+/// static ones) trivial — only the interactive ones (buttons, the text field) and the progress bar
+/// override behaviour.
 pub trait Component {
     /// The layout slot this component occupies.
     fn role(&self) -> Role;
@@ -141,7 +184,7 @@ pub trait Component {
         false
     }
 
-    // ── interaction (default: inert; only Button overrides) ─────────────
+    // ── interaction (default: inert; buttons and fields override) ───────
     /// Whether keyboard focus can land here.
     fn focusable(&self) -> bool {
         false
@@ -159,6 +202,36 @@ pub trait Component {
     fn activation_index(&self) -> Option<usize> {
         None
     }
+    /// Whether the dialog should focus this component when it opens, rather than the default
+    /// (last) button.
+    fn takes_initial_focus(&self) -> bool {
+        false
+    }
+    /// Handle a key press while focused. Returns `true` if the key was consumed, in which case the
+    /// dialog skips its own handling (focus navigation, activation, Escape).
+    fn handle_key(&mut self, _k: &KeyInput) -> bool {
+        false
+    }
+    /// The primary mouse button went down at logical (`x`, `y`) inside this component.
+    fn pointer_pressed(&mut self, _x: f32, _y: f32) {}
+    /// The pointer moved to logical (`x`, `y`) inside this component.
+    fn pointer_moved(&mut self, _x: f32, _y: f32) {}
+    /// The pointer moved to logical (`x`, `y`) while this component holds the press.
+    fn pointer_dragged(&mut self, _x: f32, _y: f32) {}
+    /// Whether user input changed this component's measured size since the last call (eg. a
+    /// disclosure was expanded), so the dialog must relayout. The input-driven counterpart of
+    /// [`Component::apply`]'s return value.
+    fn take_relayout(&mut self) -> bool {
+        false
+    }
+    /// The mouse cursor to show while the pointer hovers this component at logical (`x`, `y`).
+    fn cursor_icon(&self, _x: f32, _y: f32) -> CursorIcon {
+        CursorIcon::Default
+    }
+
+    // ── result (default: contributes nothing) ───────────────────────────
+    /// Record this component's final state (eg. entered text) into the dialog's response.
+    fn collect(&self, _response: &mut XDialogResponse) {}
 
     /// Apply a controller update. Returns `true` if it changed the component's measured size and
     /// therefore requires a relayout.
@@ -0,0 +1,180 @@
+//! Draws [`XDialogIcon::Custom`](crate::XDialogIcon::Custom) images. Each image is decoded once and
+//! rasterized at every physical pixel size it is drawn at, so it stays crisp at any scale factor:
+//! an SVG is drawn straight at the size, and a bitmap is scaled down in halving steps before a
+//! final bicubic pass. The decoded images and their rasters are kept in a small process-wide cache.
+
+use std::sync::Mutex;
+
+use log::warn;
+use tiny_skia::{FilterQuality, IntSize, Pixmap, PixmapPaint, PixmapMut, Transform};
+
+use crate::icon::{IconData, IconSource};
+
+use super::svg::SvgImage;
+
+/// How many images the cache keeps, least recently drawn evicted first.
+const CACHE_IMAGES: usize = 8;
+/// How many sizes of each image the cache keeps (eg. the main icon and the footer icon, at two
+/// scale factors).
+const CACHE_SIZES: usize = 4;
+
+enum Decoded {
+    Raster(Pixmap),
+    Svg(SvgImage),
+    /// The image couldn't be decoded (already logged), so nothing is drawn.
+    Invalid,
+}
+
+struct CachedImage {
+    data: IconData,
+    decoded: Decoded,
+    /// Rasters by size in physical pixels, least recently drawn first.
+    sizes: Vec<(u32, Pixmap)>,
+}
+
+static CACHE: Mutex<Vec<CachedImage>> = Mutex::new(Vec::new());
+
+/// Draw `data` into the `size` × `size` square at `x`, `y` (in physical pixels), scaled to fit and centred.
+pub fn draw_custom_icon(pixmap: &mut PixmapMut, data: &IconData, x: f32, y: f32, size: f32) {
+    let size_px = size.round() as u32;
+    if size_px == 0 {
+        return;
+    }
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    // Move the image to the back, as the most recently drawn.
+    let image = match cache.iter().position(|c| c.data == *data) {
+        Some(index) => cache.remove(index),
+        None => CachedImage { data: data.clone(), decoded: decode(data), sizes: Vec::new() },
+    };
+    if cache.len() >= CACHE_IMAGES {
+        cache.remove(0);
+    }
+    cache.push(image);
+
+    if let Some(raster) = cache.last_mut().and_then(|image| image.raster(size_px)) {
+        pixmap.draw_pixmap(x.round() as i32, y.round() as i32, raster.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+    }
+}
+
+impl CachedImage {
+    fn raster(&mut self, size: u32) -> Option<&Pixmap> {
+        match self.sizes.iter().position(|(s, _)| *s == size) {
+            Some(index) => {
+                let entry = self.sizes.remove(index);
+                self.sizes.push(entry);
+            }
+            None => {
+                let raster = match &self.decoded {
+                    Decoded::Raster(source) => scale_to_fit(source, size)?,
+                    Decoded::Svg(svg) => {
+                        let mut raster = Pixmap::new(size, size)?;
+                        svg.render(&mut raster.as_mut(), 0.0, 0.0, size as f32);
+                        raster
+                    }
+                    Decoded::Invalid => return None,
+                };
+                if self.sizes.len() >= CACHE_SIZES {
+                    self.sizes.remove(0);
+                }
+                self.sizes.push((size, raster));
+            }
+        }
+        self.sizes.last().map(|(_, raster)| raster)
+    }
+}
+
+fn decode(data: &IconData) -> Decoded {
+    let decoded = match data.source() {
+        IconSource::Rgba { width, height, pixels } => {
+            rgba_pixmap(*width, *height, pixels).map(Decoded::Raster).ok_or_else(|| "the image is too large".to_string())
+        }
+        IconSource::Png(bytes) => Pixmap::decode_png(bytes).map(Decoded::Raster).map_err(|e| e.to_string()),
+        IconSource::Svg(text) => SvgImage::parse(text).map(Decoded::Svg),
+    };
+    decoded.unwrap_or_else(|e| {
+        warn!("xdialog: failed to decode custom icon {:?}: {}", data, e);
+        Decoded::Invalid
+    })
+}
+
+/// A pixmap (which is premultiplied) of straight RGBA pixels.
+fn rgba_pixmap(width: u32, height: u32, pixels: &[u8]) -> Option<Pixmap> {
+    let premultiplied = pixels
+        .chunks_exact(4)
+        .flat_map(|p| {
+            let a = p[3] as u32;
+            let mul = |c: u8| ((c as u32 * a + 127) / 255) as u8;
+            [mul(p[0]), mul(p[1]), mul(p[2]), p[3]]
+        })
+        .collect();
+    Pixmap::from_vec(premultiplied, IntSize::from_wh(width, height)?)
+}
+
+/// `source` scaled to fit a `size` × `size` square and centred in it, keeping its aspect ratio.
+fn scale_to_fit(source: &Pixmap, size: u32) -> Option<Pixmap> {
+    // A single filtered pass only samples a few source pixels around each target pixel, which
+    // aliases when shrinking a lot, so halve the image (averaging 2×2 blocks) until it's within
+    // twice the target size first.
+    let mut halved: Option<Pixmap> = None;
+    let current = loop {
+        let current = halved.as_ref().unwrap_or(source);
+        if current.width().max(current.height()) < size * 4 {
+            break current;
+        }
+        halved = Some(scale(current, current.width().div_ceil(2), current.height().div_ceil(2), FilterQuality::Bilinear)?);
+    };
+
+    let fit = size as f32 / current.width().max(current.height()) as f32;
+    let (w, h) = ((current.width() as f32 * fit).round().max(1.0), (current.height() as f32 * fit).round().max(1.0));
+    let mut target = Pixmap::new(size, size)?;
+    let paint = PixmapPaint { quality: FilterQuality::Bicubic, ..Default::default() };
+    let (sx, sy) = (w / current.width() as f32, h / current.height() as f32);
+    let transform = Transform::from_row(sx, 0.0, 0.0, sy, (size as f32 - w) / 2.0, (size as f32 - h) / 2.0);
+    target.draw_pixmap(0, 0, current.as_ref(), &paint, transform, None);
+    Some(target)
+}
+
+fn scale(source: &Pixmap, width: u32, height: u32, quality: FilterQuality) -> Option<Pixmap> {
+    let mut target = Pixmap::new(width, height)?;
+    let transform = Transform::from_scale(width as f32 / source.width() as f32, height as f32 / source.height() as f32);
+    target.draw_pixmap(0, 0, source.as_ref(), &PixmapPaint { quality, ..Default::default() }, transform, None);
+    Some(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn red_square(size: u32) -> Vec<u8> {
+        [255, 0, 0, 255].repeat((size * size) as usize)
+    }
+
+    #[test]
+    fn bitmaps_are_scaled_to_fit_and_centred() {
+        // 4 × 2: a wide image fills the width and is centred vertically.
+        let data = IconData::from_rgba(4, 2, [0, 0, 255, 255].repeat(8)).unwrap();
+        let mut image = CachedImage { decoded: decode(&data), data, sizes: Vec::new() };
+        let raster = image.raster(16).unwrap();
+        assert_eq!(raster.pixel(8, 1).unwrap().alpha(), 0);
+        let center = raster.pixel(8, 8).unwrap();
+        assert_eq!((center.red(), center.blue(), center.alpha()), (0, 255, 255));
+    }
+
+    #[test]
+    fn png_icons_decode_and_each_size_is_cached() {
+        let source = rgba_pixmap(64, 64, &red_square(64)).unwrap();
+        let data = IconData::from_png(source.encode_png().unwrap());
+        let mut image = CachedImage { decoded: decode(&data), data, sizes: Vec::new() };
+        assert_eq!(image.raster(16).unwrap().pixel(8, 8).unwrap().red(), 255);
+        image.raster(32).unwrap();
+        image.raster(16).unwrap();
+        assert_eq!(image.sizes.iter().map(|(s, _)| *s).collect::<Vec<_>>(), [32, 16]);
+    }
+
+    #[test]
+    fn undecodable_icons_draw_nothing() {
+        let data = IconData::from_png(vec![1, 2, 3]);
+        let mut image = CachedImage { decoded: decode(&data), data, sizes: Vec::new() };
+        assert!(image.raster(16).is_none());
+    }
+}
@@ -6,6 +6,9 @@
 //! unimplemented key, or "no preference" — falls back to the hard-coded Ubuntu light theme, so
 //! this is purely additive.
 //!
+//! The name of the user's icon theme is read from the same interface, where the desktop publishes
+//! it (see [`detect_icon_theme`]).
+//!
 //! On non-Linux platforms detection is a no-op that always reports no preference.
 
 use crate::model::XDialogTheme;
@@ -51,18 +54,33 @@ fn detect_appearance() -> DesktopAppearance {
     DesktopAppearance::default()
 }
 
+/// The name of the user's icon theme, if the settings portal exposes it. This isn't one of the
+/// standardized keys, but GNOME (and other GTK desktops) and KDE Plasma publish their own settings
+/// through the portal too.
 #[cfg(target_os = "linux")]
-fn detect_via_portal() -> Option<DesktopAppearance> {
-    use zbus::blocking::{Connection, Proxy};
+pub fn detect_icon_theme() -> Option<String> {
+    use zbus::zvariant::Value;
 
-    let conn = Connection::session().ok()?;
-    let proxy = Proxy::new(
-        &conn,
-        "org.freedesktop.portal.Desktop",
-        "/org/freedesktop/portal/desktop",
-        "org.freedesktop.portal.Settings",
-    )
-    .ok()?;
+    let conn = zbus::blocking::Connection::session().ok()?;
+    let proxy = settings_proxy(&conn)?;
+    [("org.gnome.desktop.interface", "icon-theme"), ("org.kde.kdeglobals.Icons", "Theme")].into_iter().find_map(|(ns, key)| {
+        let value = read_namespaced_setting(&proxy, ns, key)?;
+        match deep_unwrap(&value) {
+            Value::Str(name) if !name.is_empty() => Some(name.to_string()),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect_icon_theme() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn detect_via_portal() -> Option<DesktopAppearance> {
+    let conn = zbus::blocking::Connection::session().ok()?;
+    let proxy = settings_proxy(&conn)?;
 
     Some(DesktopAppearance {
         color_scheme: read_color_scheme(&proxy),
@@ -70,6 +88,17 @@ fn detect_via_portal() -> Option<DesktopAppearance> {
     })
 }
 
+#[cfg(target_os = "linux")]
+fn settings_proxy(conn: &zbus::blocking::Connection) -> Option<zbus::blocking::Proxy<'static>> {
+    zbus::blocking::Proxy::new(
+        conn,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Settings",
+    )
+    .ok()
+}
+
 #[cfg(target_os = "linux")]
 fn read_color_scheme(proxy: &zbus::blocking::Proxy) -> ColorScheme {
     use zbus::zvariant::Value;
@@ -108,15 +137,20 @@ fn read_accent_color(proxy: &zbus::blocking::Proxy) -> Option<(u8, u8, u8)> {
     Some((to_u8(r), to_u8(g), to_u8(b)))
 }
 
-/// Read a key from the `org.freedesktop.appearance` namespace, trying the modern `ReadOne`
-/// (portal Settings v2) first and falling back to the older, double-wrapped `Read`.
+/// Read a key from the `org.freedesktop.appearance` namespace.
 #[cfg(target_os = "linux")]
 fn read_setting(proxy: &zbus::blocking::Proxy, key: &str) -> Option<zbus::zvariant::OwnedValue> {
-    const NS: &str = "org.freedesktop.appearance";
-    if let Ok(v) = proxy.call::<_, _, zbus::zvariant::OwnedValue>("ReadOne", &(NS, key)) {
+    read_namespaced_setting(proxy, "org.freedesktop.appearance", key)
+}
+
+/// Read a key, trying the modern `ReadOne` (portal Settings v2) first and falling back to the
+/// older, double-wrapped `Read`.
+#[cfg(target_os = "linux")]
+fn read_namespaced_setting(proxy: &zbus::blocking::Proxy, ns: &str, key: &str) -> Option<zbus::zvariant::OwnedValue> {
+    if let Ok(v) = proxy.call::<_, _, zbus::zvariant::OwnedValue>("ReadOne", &(ns, key)) {
         return Some(v);
     }
-    proxy.call::<_, _, zbus::zvariant::OwnedValue>("Read", &(NS, key)).ok()
+    proxy.call::<_, _, zbus::zvariant::OwnedValue>("Read", &(ns, key)).ok()
 }
 
 /// `Read` wraps the value in an extra variant layer versus `ReadOne`; unwrap any nesting so
@@ -0,0 +1,225 @@
+//! The "Show details" disclosure: a toggle which reveals a block of secondary text (a stack trace,
+//! a log excerpt) underneath it. Expanding or collapsing changes the component's height, so it
+//! asks the dialog for a relayout, which resizes the window.
+
+use tiny_skia::PixmapMut;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
+
+use super::component::{Component, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::renderer::{fill_rect, stroke_line, stroke_rounded_rect};
+use super::text::{measure_text_width, render_text, CachedLayout};
+
+const SHOW_TEXT: &str = "Show details";
+const HIDE_TEXT: &str = "Hide details";
+/// Height of the toggle row in logical pixels.
+const HEADER_HEIGHT: f32 = 24.0;
+/// Width reserved for the chevron at the start of the toggle row, in logical pixels.
+const CHEVRON_WIDTH: f32 = 20.0;
+/// Gap between the toggle row and the details text in logical pixels.
+const DETAILS_GAP: f32 = 4.0;
+
+pub struct Disclosure {
+    details: String,
+    expanded: bool,
+    /// Set when the height changed through user input, until the dialog relayouts.
+    relayout: bool,
+    bounds: Rect,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    dirty: bool,
+    header_cache: CachedLayout,
+    details_cache: CachedLayout,
+}
+
+impl Disclosure {
+    pub fn new(details: &str) -> Self {
+        Self {
+            details: details.to_string(),
+            expanded: false,
+            relayout: false,
+            bounds: Rect::default(),
+            hovered: false,
+            pressed: false,
+            focused: false,
+            dirty: true,
+            header_cache: CachedLayout::default(),
+            details_cache: CachedLayout::default(),
+        }
+    }
+
+    fn header_text(&self) -> &'static str {
+        if self.expanded {
+            HIDE_TEXT
+        } else {
+            SHOW_TEXT
+        }
+    }
+
+    fn header_width(&self) -> f32 {
+        CHEVRON_WIDTH + measure_text_width(self.header_text(), false, BODY_SIZE) + 4.0
+    }
+
+    fn set_expanded(&mut self, expanded: bool) {
+        if self.expanded != expanded {
+            self.expanded = expanded;
+            self.relayout = true;
+            self.dirty = true;
+        }
+    }
+}
+
+impl Component for Disclosure {
+    fn role(&self) -> Role {
+        Role::Content
+    }
+
+    fn measure(&mut self, ctx: &LayoutCtx) -> Size {
+        // Only the toggle counts towards the natural width: long unwrapped lines in the details
+        // would otherwise widen the window, and expanding shouldn't change its width.
+        let w = self.header_width();
+        if !self.expanded || !ctx.available_width.is_finite() {
+            return Size { w, h: HEADER_HEIGHT };
+        }
+        let details = self.details_cache.get(&self.details, false, BODY_SIZE, ctx.available_width);
+        Size {
+            w,
+            h: HEADER_HEIGHT + DETAILS_GAP + details.total_height,
+        }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let (x, y, w, h) = (self.bounds.x * s, self.bounds.y * s, self.bounds.w * s, self.bounds.h * s);
+        fill_rect(pm, x, y, w, h, theme.color_background);
+
+        // Toggle row: a chevron (pointing right when collapsed, down when expanded) and its label,
+        // both in the accent colour, with a focus ring around the pair.
+        let header_h = HEADER_HEIGHT * s;
+        let color = theme.color_field_border_focused;
+        let (cx, cy, arm) = (x + CHEVRON_WIDTH * s / 2.0, y + header_h / 2.0, 4.0 * s);
+        if self.expanded {
+            stroke_line(pm, cx - arm, cy - arm / 2.0, cx, cy + arm / 2.0, color, 1.5 * s);
+            stroke_line(pm, cx, cy + arm / 2.0, cx + arm, cy - arm / 2.0, color, 1.5 * s);
+        } else {
+            stroke_line(pm, cx - arm / 2.0, cy - arm, cx + arm / 2.0, cy, color, 1.5 * s);
+            stroke_line(pm, cx + arm / 2.0, cy, cx - arm / 2.0, cy + arm, color, 1.5 * s);
+        }
+        let header_w = self.header_width() * s;
+        let header = self.header_cache.get(self.header_text(), false, BODY_SIZE * s, f32::INFINITY);
+        render_text(pm, header, color, x + CHEVRON_WIDTH * s, cy - header.total_height / 2.0);
+        if self.hovered {
+            let underline_y = cy + header.total_height / 2.0 - s;
+            fill_rect(pm, x + CHEVRON_WIDTH * s, underline_y, header.total_width, s, color);
+        }
+        if self.focused {
+            stroke_rounded_rect(pm, x + s / 2.0, y + s / 2.0, header_w - s, header_h - s, 3.0 * s, color, s);
+        }
+
+        if self.expanded {
+            let details = self.details_cache.get(&self.details, false, BODY_SIZE * s, w);
+            render_text(pm, details, theme.color_body_text, x, y + header_h + DETAILS_GAP * s);
+        }
+
+        self.dirty = false;
+        Rect::new(x, y, w, h)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_hovered(&mut self, v: bool) {
+        if self.hovered != v {
+            self.hovered = v;
+            self.dirty = true;
+        }
+    }
+
+    fn set_pressed(&mut self, v: bool) {
+        self.pressed = v;
+    }
+
+    fn set_focused(&mut self, v: bool) {
+        if self.focused != v {
+            self.focused = v;
+            self.dirty = true;
+        }
+    }
+
+    fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    fn handle_key(&mut self, k: &KeyInput) -> bool {
+        match k.key {
+            Key::Named(NamedKey::Enter | NamedKey::Space) => self.set_expanded(!self.expanded),
+            Key::Named(NamedKey::ArrowRight) if !self.expanded => self.set_expanded(true),
+            Key::Named(NamedKey::ArrowLeft) if self.expanded => self.set_expanded(false),
+            _ => return false,
+        }
+        true
+    }
+
+    fn pointer_pressed(&mut self, _x: f32, y: f32) {
+        if y < self.bounds.y + HEADER_HEIGHT {
+            self.set_expanded(!self.expanded);
+        }
+    }
+
+    fn cursor_icon(&self, _x: f32, y: f32) -> CursorIcon {
+        if y < self.bounds.y + HEADER_HEIGHT {
+            CursorIcon::Pointer
+        } else {
+            CursorIcon::Default
+        }
+    }
+
+    fn take_relayout(&mut self) -> bool {
+        std::mem::take(&mut self.relayout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(d: &mut Disclosure, key: NamedKey) -> bool {
+        let key = Key::Named(key);
+        d.handle_key(&KeyInput { key: &key, text: None, ctrl: false, shift: false })
+    }
+
+    #[test]
+    fn keys_toggle_and_request_one_relayout() {
+        let mut d = Disclosure::new("trace");
+        assert!(!d.take_relayout());
+        assert!(press(&mut d, NamedKey::Space));
+        assert!(d.expanded);
+        assert!(d.take_relayout());
+        assert!(!d.take_relayout());
+
+        // Right only expands and Left only collapses; otherwise they fall through to the dialog.
+        assert!(!press(&mut d, NamedKey::ArrowRight));
+        assert!(press(&mut d, NamedKey::ArrowLeft));
+        assert!(!d.expanded);
+        assert!(!press(&mut d, NamedKey::Tab));
+    }
+}
@@ -6,20 +6,26 @@ use std::time::{Duration, Instant};
 use softbuffer::{Rect as DamageRect, Surface};
 use tiny_skia::Pixmap;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
-use winit::event::Modifiers;
+use winit::event::{KeyEvent, Modifiers};
 use winit::event_loop::ActiveEventLoop;
 use winit::keyboard::{Key, NamedKey};
-use winit::window::{Window, WindowAttributes};
+use winit::window::{CursorIcon, Window, WindowAttributes};
 
 use crate::model::*;
-use crate::{ProgressButtonCallback, ProgressDialogProxy};
+use crate::{ChecklistStepState, InputField, ProgressBarState, ProgressCallbacks};
 
 use super::background::{Background, Footer};
 use super::button::SkiaButton;
-use super::component::{Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role};
+use super::checkbox::Checkbox;
+use super::details::Disclosure;
+use super::component::{Component, ControllerUpdate, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size};
+use super::field::TextField;
+use super::footer_note::FooterNote;
 use super::icon::Icon;
 use super::label::{Label, LabelKind};
+use super::checklist::SkiaChecklist;
 use super::progress::SkiaProgressBar;
+use super::radio::RadioGroup;
 use super::theme::SkiaTheme;
 
 pub enum KeyAction {
@@ -39,13 +45,25 @@ pub struct SkiaDialog {
     /// Single persistent internal RGBA buffer; converted to the softbuffer ARGB surface at present.
     pixmap: Option<Pixmap>,
     theme: SkiaTheme,
-    /// Components in z-order: Background, Footer?, Icon?, Title?, Progress?, Body?, Button(s).
+    /// Components in z-order: Background, Footer?, Icon, Title, Checklist?, Progress bar(s) and
+    /// status?, Body, Details?, Field?, Radio group?, Checkbox?, Button(s), Footer note?.
     components: Vec<Box<dyn Component>>,
     /// Index (into `components`) of the focused component, if any.
     focused: Option<usize>,
     shift_held: bool,
-    result_sender: Option<oneshot::Sender<XDialogResult>>,
-    button_callback: Option<ProgressButtonCallback>,
+    ctrl_held: bool,
+    /// Last pointer position in logical pixels.
+    cursor: (f32, f32),
+    cursor_icon: CursorIcon,
+    result_sender: Option<oneshot::Sender<XDialogResponse>>,
+    /// Result index of the button Enter activates wherever the focus is.
+    default_button: Option<usize>,
+    /// Reported when the dialog is dismissed with Escape or the window's close button.
+    dismiss_result: XDialogResult,
+    /// The button and close callbacks of a progress dialog.
+    callbacks: ProgressCallbacks,
+    /// The text field's validation state, for input dialogs.
+    input: Option<InputState>,
     scale_factor: f64,
     /// Forces every component to repaint next frame (resize / relayout / pixmap realloc).
     repaint_all: bool,
@@ -57,33 +75,66 @@ pub struct SkiaDialog {
     last_logical_size: (f32, f32),
 }
 
+/// Validation bookkeeping for an input dialog's text field.
+struct InputState {
+    field: InputField,
+    /// Result index of the affirmative button (the default button, else the last one), which
+    /// validation can disable.
+    affirmative: Option<usize>,
+    /// The text most recently passed to the validator (to skip re-validating on caret movement)
+    /// and whether it passed.
+    last_text: Option<String>,
+    valid: bool,
+    /// Errors are only shown once the user has edited the field; until then an invalid initial
+    /// value just disables the affirmative button.
+    touched: bool,
+}
+
 impl SkiaDialog {
     pub fn new(
         event_loop: &ActiveEventLoop,
         options: XDialogOptions,
         theme: &SkiaTheme,
         has_progress: bool,
-        result_sender: oneshot::Sender<XDialogResult>,
-        button_callback: Option<ProgressButtonCallback>,
+        input: Option<InputField>,
+        result_sender: oneshot::Sender<XDialogResponse>,
+        callbacks: ProgressCallbacks,
     ) -> Self {
         // Build components in paint/z-order.
         let mut components: Vec<Box<dyn Component>> = Vec::new();
         components.push(Box::new(Background::new()));
 
-        if !options.buttons.is_empty() {
+        if !options.buttons.is_empty() || !options.verification_text.is_empty() {
             components.push(Box::new(Footer::new()));
         }
-        if options.icon != XDialogIcon::None {
-            components.push(Box::new(Icon::new(options.icon.clone())));
-        }
-        if !options.main_instruction.is_empty() {
-            components.push(Box::new(Label::new(LabelKind::Title, &options.main_instruction)));
+        // The icon and the labels are always present, taking no space while empty, so that a
+        // `DialogPatch` can fill them in later.
+        components.push(Box::new(Icon::new(options.icon.clone())));
+        components.push(Box::new(Label::new(LabelKind::Title, &options.main_instruction)));
+        if has_progress && !options.checklist.is_empty() {
+            components.push(Box::new(SkiaChecklist::new(&options.checklist)));
         }
         if has_progress {
-            components.push(Box::new(SkiaProgressBar::new()));
+            for (index, caption) in options.progress_bar_captions().iter().enumerate() {
+                components.push(Box::new(SkiaProgressBar::new(index, caption)));
+            }
+            components.push(Box::new(Label::new(LabelKind::Status, "")));
         }
-        if !options.message.is_empty() {
-            components.push(Box::new(Label::new(LabelKind::Body, &options.message)));
+        components.push(Box::new(Label::new(LabelKind::Body, &options.message)));
+        if !options.expanded_details.is_empty() {
+            components.push(Box::new(Disclosure::new(&options.expanded_details)));
+        }
+        if let Some(field) = input.as_ref() {
+            components.push(Box::new(TextField::new(&field.initial_text, field.validator.is_some(), field.masked)));
+        }
+        if let Some(selected) = options.initial_radio_index() {
+            components.push(Box::new(RadioGroup::new(&options.radio_buttons, selected)));
+        }
+
+        // The verification checkbox precedes the buttons, so it comes first in the Tab order and a
+        // button keeps the initial focus.
+        if !options.verification_text.is_empty() {
+            components.push(Box::new(Checkbox::new(&options.verification_text, options.verification_checked)));
         }
 
         // Buttons, honouring the theme's button order.
@@ -92,8 +143,12 @@ impl SkiaDialog {
         } else {
             options.buttons.iter().enumerate().collect()
         };
+        let default_button = options.default_button_index();
         for (index, label) in button_iter {
-            components.push(Box::new(SkiaButton::new(label, index, theme)));
+            components.push(Box::new(SkiaButton::new(label, index, theme, default_button == Some(index))));
+        }
+        if !options.footer_text.is_empty() {
+            components.push(Box::new(FooterNote::new(options.footer_icon.clone(), &options.footer_text)));
         }
 
         // Compute the initial (logical, scale-independent) window size for the window attributes.
@@ -134,16 +189,34 @@ impl SkiaDialog {
             components,
             focused: None,
             shift_held: false,
+            ctrl_held: false,
+            cursor: (0.0, 0.0),
+            cursor_icon: CursorIcon::Default,
             result_sender: Some(result_sender),
-            button_callback,
+            default_button,
+            dismiss_result: options.dismiss_result(),
+            callbacks,
+            input: input.map(|field| InputState {
+                field,
+                affirmative: default_button.or(options.buttons.len().checked_sub(1)),
+                last_text: None,
+                valid: true,
+                touched: false,
+            }),
             scale_factor,
             repaint_all: true,
             last_surface_size: (0, 0),
             last_logical_size: (win_w, win_h),
         };
 
-        // Focus the last focusable component (button) by default.
-        if let Some(idx) = dialog.last_focusable() {
+        // An invalid initial value disables the affirmative button straight away.
+        dialog.validate_input();
+
+        // Focus the component that asks for it (the text field), else the default button, else the
+        // last focusable one (button).
+        let initial = dialog.components.iter().position(|c| c.takes_initial_focus());
+        let default = default_button.and_then(|ai| dialog.components.iter().position(|c| c.activation_index() == Some(ai)));
+        if let Some(idx) = initial.or(default).or_else(|| dialog.last_focusable()) {
             dialog.focused = Some(idx);
             dialog.components[idx].set_focused(true);
         }
@@ -154,6 +227,11 @@ impl SkiaDialog {
         dialog
     }
 
+    /// Whether the button reporting `index` is present and enabled.
+    fn can_activate(&self, index: usize) -> bool {
+        self.components.iter().any(|c| c.activation_index() == Some(index))
+    }
+
     fn last_focusable(&self) -> Option<usize> {
         self.components
             .iter()
@@ -171,19 +249,69 @@ impl SkiaDialog {
         self.components.iter().any(|c| c.is_animating())
     }
 
+    /// Deliver the dialog's result, together with the final state of its interactive components.
     pub fn send_result(&mut self, result: XDialogResult) {
         if let Some(sender) = self.result_sender.take() {
-            let _ = sender.send(result);
+            let mut response = XDialogResponse::from(result);
+            for c in self.components.iter() {
+                c.collect(&mut response);
+            }
+            let _ = sender.send(response);
         }
     }
 
+    /// Run the input validator if the field text changed since the last run, enabling/disabling
+    /// the affirmative button and updating the inline error. Returns whether the text is valid.
+    fn validate_input(&mut self) -> bool {
+        // Without a validator there's nothing to check, and no reason to copy the text out of the
+        // field (which may hold a password).
+        if self.input.as_ref().is_none_or(|i| i.field.validator.is_none()) {
+            return true;
+        }
+        let mut response = XDialogResponse::from(XDialogResult::WindowClosed);
+        for c in self.components.iter() {
+            c.collect(&mut response);
+        }
+        let Some(input) = self.input.as_mut() else {
+            return true;
+        };
+        let text = response.text.unwrap_or_default();
+        if input.last_text.as_deref() == Some(text.as_str()) {
+            return input.valid;
+        }
+        input.touched |= input.last_text.is_some();
+        let outcome = input.field.validate(&text);
+        input.valid = outcome.is_ok();
+        input.last_text = Some(text);
+
+        let shown_error = if input.touched { outcome.as_ref().err().map(String::as_str) } else { None };
+        let mut updates = vec![ControllerUpdate::InputError(shown_error)];
+        if let Some(index) = input.affirmative {
+            updates.push(ControllerUpdate::ButtonEnabled(index, outcome.is_ok()));
+        }
+        let mut relayout = false;
+        for update in &updates {
+            for c in self.components.iter_mut() {
+                relayout |= c.apply(update);
+            }
+        }
+        if relayout {
+            self.layout();
+        }
+        outcome.is_ok()
+    }
+
     /// Handle a button click on this dialog. If a progress button callback is registered, invoke
     /// it with a non-owning proxy and return whether the dialog should stay open. Otherwise deliver
     /// the click as a `ButtonPressed` result and return `false` (the dialog should close).
     pub fn on_button_clicked(&mut self, id: usize, index: usize) -> bool {
-        if let Some(cb) = self.button_callback.as_mut() {
-            let proxy = ProgressDialogProxy::non_owning(id);
-            (cb.0)(index, &proxy)
+        // The affirmative button of an input dialog only closes it with valid text.
+        let affirmative = self.input.as_ref().and_then(|i| i.affirmative);
+        if affirmative == Some(index) && !self.validate_input() {
+            return true;
+        }
+        if let Some(keep_open) = self.callbacks.button_clicked(id, index) {
+            keep_open
         } else {
             self.send_result(XDialogResult::ButtonPressed(index));
             false
@@ -195,20 +323,80 @@ impl SkiaDialog {
         self.window.set_visible(false);
     }
 
+    /// Apply a [`DialogPatch`], laying out once for all of its changes.
+    pub fn apply_patch(&mut self, patch: &DialogPatch) {
+        if let Some(title) = patch.title.as_ref() {
+            self.window.set_title(title);
+        }
+        let mut updates = Vec::new();
+        if let Some(text) = patch.main_instruction.as_deref() {
+            updates.push(ControllerUpdate::MainInstruction(text));
+        }
+        if let Some(text) = patch.message.as_deref() {
+            updates.push(ControllerUpdate::BodyText(text));
+        }
+        if let Some(icon) = patch.icon.as_ref() {
+            updates.push(ControllerUpdate::Icon(icon));
+        }
+        for (index, label) in patch.button_labels.iter() {
+            updates.push(ControllerUpdate::ButtonLabel(*index, label));
+        }
+        let mut relayout = false;
+        for update in updates.iter() {
+            relayout |= self.broadcast(update);
+        }
+        if relayout {
+            self.layout();
+            // Components moved even if the window size didn't (eg. the text shifting over for a new
+            // icon), and moving doesn't mark them dirty.
+            self.repaint_all = true;
+        }
+    }
+
     pub fn set_body_text(&mut self, text: &str) {
         if self.broadcast(&ControllerUpdate::BodyText(text)) {
             self.layout();
         }
     }
 
-    pub fn set_progress_value(&mut self, value: f32) {
-        if self.broadcast(&ControllerUpdate::ProgressValue(value)) {
+    pub fn set_status_text(&mut self, text: &str) {
+        if self.broadcast(&ControllerUpdate::StatusText(text)) {
+            self.layout();
+        }
+    }
+
+    pub fn set_progress_value(&mut self, bar: usize, value: f32) {
+        if self.broadcast(&ControllerUpdate::ProgressValue(bar, value)) {
+            self.layout();
+        }
+    }
+
+    pub fn set_progress_state(&mut self, bar: usize, state: ProgressBarState) {
+        if self.broadcast(&ControllerUpdate::ProgressState(bar, state)) {
+            self.layout();
+        }
+    }
+
+    pub fn set_progress_indeterminate(&mut self, bar: usize) {
+        if self.broadcast(&ControllerUpdate::ProgressIndeterminate(bar)) {
             self.layout();
         }
     }
 
-    pub fn set_progress_indeterminate(&mut self) {
-        if self.broadcast(&ControllerUpdate::ProgressIndeterminate) {
+    pub fn set_checklist_step(&mut self, index: usize, state: ChecklistStepState) {
+        if self.broadcast(&ControllerUpdate::ChecklistStep(index, state)) {
+            self.layout();
+        }
+    }
+
+    pub fn start_checklist_step(&mut self, index: usize) {
+        if self.broadcast(&ControllerUpdate::ChecklistStart(index)) {
+            self.layout();
+        }
+    }
+
+    pub fn set_progress_caption(&mut self, bar: usize, caption: &str) {
+        if self.broadcast(&ControllerUpdate::ProgressCaption(bar, caption)) {
             self.layout();
         }
     }
@@ -247,31 +435,52 @@ impl SkiaDialog {
     pub fn handle_cursor_moved(&mut self, position: PhysicalPosition<f64>) {
         let lx = (position.x / self.scale_factor) as f32;
         let ly = (position.y / self.scale_factor) as f32;
+        self.cursor = (lx, ly);
 
         let mut any_hovered = false;
+        let mut icon = CursorIcon::Default;
         for c in self.components.iter_mut() {
-            c.set_hovered(c.bounds().contains(lx, ly));
+            let inside = c.bounds().contains(lx, ly);
+            c.set_hovered(inside);
+            if inside {
+                c.pointer_moved(lx, ly);
+            }
             if c.is_hovered() {
                 any_hovered = true;
+                icon = c.cursor_icon(lx, ly);
             }
+            // A held press keeps tracking the pointer (eg. drag-selecting text past the field edge).
+            if c.is_pressed() {
+                c.pointer_dragged(lx, ly);
+            }
+        }
+        if icon != self.cursor_icon {
+            self.cursor_icon = icon;
+            self.window.set_cursor(icon);
         }
 
-        // Suppress the focus ring while hovering, so only one button is highlighted.
+        // Suppress a focused button's focus ring while hovering, so only one button is highlighted.
+        // Other focusable components (the text field) keep their focus visuals.
         if let Some(fi) = self.focused {
-            self.components[fi].set_focused(!any_hovered);
+            if self.components[fi].activation_index().is_some() || !any_hovered {
+                self.components[fi].set_focused(!any_hovered);
+            }
         }
     }
 
     pub fn handle_mouse_pressed(&mut self) {
+        let (lx, ly) = self.cursor;
         let mut pressed_idx = None;
         for (i, c) in self.components.iter_mut().enumerate() {
             if c.is_hovered() {
                 c.set_pressed(true);
+                c.pointer_pressed(lx, ly);
                 pressed_idx = Some(i);
             }
         }
+        self.relayout_after_input();
         // Transfer focus to the pressed component.
-        if let Some(new_fi) = pressed_idx {
+        if let Some(new_fi) = pressed_idx.filter(|&i| self.components[i].focusable()) {
             if self.focused != Some(new_fi) {
                 if let Some(old_fi) = self.focused {
                     self.components[old_fi].set_focused(false);
@@ -293,15 +502,40 @@ impl SkiaDialog {
         clicked
     }
 
-    pub fn handle_close_requested(&mut self) {
-        self.send_result(XDialogResult::WindowClosed);
+    /// Handle the user closing this dialog (its close button, or Escape). A progress dialog's close
+    /// callback may veto it; returns whether the dialog should stay open.
+    pub fn handle_close_requested(&mut self, id: usize) -> bool {
+        if self.callbacks.close_requested(id) {
+            return true;
+        }
+        let result = self.dismiss_result.clone();
+        self.send_result(result);
+        false
     }
 
     pub fn handle_modifiers_changed(&mut self, modifiers: &Modifiers) {
         self.shift_held = modifiers.state().shift_key();
+        self.ctrl_held = modifiers.state().control_key();
     }
 
-    pub fn handle_key_pressed(&mut self, key: &Key) -> KeyAction {
+    pub fn handle_key_pressed(&mut self, event: &KeyEvent) -> KeyAction {
+        let key = &event.logical_key;
+
+        // The focused component (eg. the text field) gets first refusal.
+        if let Some(idx) = self.focused {
+            let input = KeyInput {
+                key,
+                text: event.text.as_deref(),
+                ctrl: self.ctrl_held,
+                shift: self.shift_held,
+            };
+            if self.components[idx].handle_key(&input) {
+                self.validate_input();
+                self.relayout_after_input();
+                return KeyAction::None;
+            }
+        }
+
         let has_focusable = self.components.iter().any(|c| c.focusable());
         if !has_focusable {
             if matches!(key, Key::Named(NamedKey::Escape)) {
@@ -312,11 +546,26 @@ impl SkiaDialog {
 
         match key {
             Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                // Enter activates the default button wherever the focus is; Space only the focused
+                // one.
+                if matches!(key, Key::Named(NamedKey::Enter)) {
+                    if let Some(ai) = self.default_button.filter(|&ai| self.can_activate(ai)) {
+                        return KeyAction::ActivateButton(ai);
+                    }
+                }
                 if let Some(idx) = self.focused {
                     if let Some(ai) = self.components[idx].activation_index() {
                         return KeyAction::ActivateButton(ai);
                     }
                 }
+                // Enter in the text field submits via the affirmative button, unless validation
+                // has disabled it.
+                if matches!(key, Key::Named(NamedKey::Enter)) {
+                    let affirmative = self.input.as_ref().and_then(|i| i.affirmative);
+                    if let Some(ai) = affirmative.filter(|&ai| self.can_activate(ai)) {
+                        return KeyAction::ActivateButton(ai);
+                    }
+                }
                 KeyAction::None
             }
             Key::Named(NamedKey::Tab) => {
@@ -372,6 +621,17 @@ impl SkiaDialog {
         self.needs_redraw()
     }
 
+    /// Relayout if a key press or click changed a component's size (eg. expanding the details).
+    fn relayout_after_input(&mut self) {
+        let mut relayout = false;
+        for c in self.components.iter_mut() {
+            relayout |= c.take_relayout();
+        }
+        if relayout {
+            self.layout();
+        }
+    }
+
     /// Re-run layout (after body-text changes alter the content height) and request the new size.
     fn layout(&mut self) {
         let (win_w, win_h) = layout_components(&mut self.components, &self.theme);
@@ -535,8 +795,14 @@ fn layout_components(components: &mut [Box<dyn Component>], theme: &SkiaTheme) -
     let gap = theme.default_content_margin as f32;
     let icon_size = theme.main_icon_size as f32;
 
-    let has_icon = components.iter().any(|c| c.role() == Role::Icon);
-    let has_buttons = components.iter().any(|c| c.role() == Role::Button);
+    let has_icon = {
+        let ctx = LayoutCtx {
+            theme,
+            available_width: f32::INFINITY,
+        };
+        components.iter_mut().any(|c| c.role() == Role::Icon && c.measure(&ctx).w > 0.0)
+    };
+    let has_footer = components.iter().any(|c| c.role() == Role::Footer);
 
     // 1. Natural content width: measure Content components at "infinite" width (no wrap).
     let mut natural_w: f32 = 0.0;
@@ -568,7 +834,10 @@ fn layout_components(components: &mut [Box<dyn Component>], theme: &SkiaTheme) -
             if c.role() == Role::Content {
                 let h = c.measure(&ctx).h;
                 c.set_bounds(Rect::new(text_x, y, text_w, h));
-                y += h + gap;
+                // Empty content (a label without text) takes no space, not even the gap.
+                if h > 0.0 {
+                    y += h + gap;
+                }
             }
         }
     }
@@ -579,29 +848,90 @@ fn layout_components(components: &mut [Box<dyn Component>], theme: &SkiaTheme) -
         content_region_h = content_region_h.max(gap + icon_size + gap);
     }
     let win_w = final_width;
-    let win_h = content_region_h + if has_buttons { theme.button_panel_height as f32 } else { 0.0 };
 
-    // 5. Position the background, icon and footer by role.
+    // 5. Footer controls sit left of the buttons, or in an extra row above them when too wide.
+    let control_row_h = layout_footer_controls(components, theme, win_w, content_region_h, text_w);
+    let footer_h = if has_footer { theme.button_panel_height as f32 + control_row_h } else { 0.0 };
+
+    // 6. The footer note spans the window width below the button strip.
+    let note_y = content_region_h + footer_h;
+    let mut note_h = 0.0;
+    {
+        let ctx = LayoutCtx {
+            theme,
+            available_width: win_w,
+        };
+        for c in components.iter_mut() {
+            if c.role() == Role::FooterNote {
+                note_h = c.measure(&ctx).h;
+                c.set_bounds(Rect::new(0.0, note_y, win_w, note_h));
+            }
+        }
+    }
+    let win_h = note_y + note_h;
+
+    // 7. Position the background, icon and footer by role.
     for c in components.iter_mut() {
         match c.role() {
             Role::Background => c.set_bounds(Rect::new(0.0, 0.0, win_w, win_h)),
-            Role::Icon => c.set_bounds(Rect::new(gap, gap, icon_size, icon_size)),
-            Role::Footer => c.set_bounds(Rect::new(
-                0.0,
-                content_region_h,
-                win_w,
-                theme.button_panel_height as f32,
-            )),
+            Role::Icon if has_icon => c.set_bounds(Rect::new(gap, gap, icon_size, icon_size)),
+            Role::Icon => c.set_bounds(Rect::default()),
+            Role::Footer => c.set_bounds(Rect::new(0.0, content_region_h, win_w, footer_h)),
             _ => {}
         }
     }
 
-    // 6. Lay out the button row, right-aligned within the footer.
-    layout_button_row(components, theme, win_w, content_region_h, text_w);
+    // 8. Lay out the button row, right-aligned within the footer.
+    layout_button_row(components, theme, win_w, content_region_h + control_row_h, text_w);
 
     (win_w, win_h)
 }
 
+/// Left-align the footer controls in the footer strip whose top is at `panel_y`: vertically centred
+/// beside the buttons when they fit, otherwise stacked in rows above them. Returns the height of
+/// the extra rows (zero when the controls share the button row).
+fn layout_footer_controls(
+    components: &mut [Box<dyn Component>],
+    theme: &SkiaTheme,
+    win_w: f32,
+    panel_y: f32,
+    text_w: f32,
+) -> f32 {
+    let panel_h = theme.button_panel_height as f32;
+    let margin = theme.button_panel_margin as f32;
+    let spacing = theme.button_panel_spacing as f32;
+    let x = theme.default_content_margin as f32;
+
+    let ctx = LayoutCtx {
+        theme,
+        available_width: text_w,
+    };
+    let mut buttons_w: f32 = 0.0;
+    let mut controls: Vec<(usize, Size)> = Vec::new();
+    for (i, c) in components.iter_mut().enumerate() {
+        match c.role() {
+            Role::Button => buttons_w += c.measure(&ctx).w + spacing,
+            Role::FooterControl => controls.push((i, c.measure(&ctx))),
+            _ => {}
+        }
+    }
+
+    let beside_w = win_w - x - margin - buttons_w;
+    if let [(ci, size)] = controls[..] {
+        if size.w <= beside_w {
+            components[ci].set_bounds(Rect::new(x, panel_y + (panel_h - size.h) / 2.0, size.w, size.h));
+            return 0.0;
+        }
+    }
+
+    let mut y = panel_y + margin;
+    for (ci, size) in controls {
+        components[ci].set_bounds(Rect::new(x, y, size.w.min(win_w - x * 2.0), size.h));
+        y += size.h + margin;
+    }
+    y - panel_y - margin
+}
+
 /// Right-align the button row within the footer strip whose top is at `panel_y`.
 fn layout_button_row(
     components: &mut [Box<dyn Component>],
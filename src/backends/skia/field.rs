@@ -0,0 +1,630 @@
+//! The single-line text field of an input dialog.
+//!
+//! Editing state lives in [`EditBuffer`] — a plain string plus caret/anchor byte offsets that always
+//! sit on grapheme boundaries — so the editing rules can be unit-tested without a window.
+//! [`TextField`] wraps it as a [`Component`]: it paints the box, the (horizontally scrolled) text,
+//! the selection and a steady caret, maps keys and pointer input onto buffer operations, and shows
+//! an optional inline validation error underneath. In password mode it draws bullets instead of the
+//! text, offers a show-password toggle, refuses to copy, and wipes its buffers when dropped.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use tiny_skia::PixmapMut;
+use unicode_segmentation::UnicodeSegmentation;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
+use zeroize::Zeroize;
+
+use crate::model::XDialogResponse;
+use crate::XDialogSecret;
+
+use super::clipboard;
+use super::component::{Component, ControllerUpdate, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::renderer::{fill_circle, fill_rect, fill_rounded_rect, stroke_eye, stroke_line, stroke_rounded_rect};
+use super::text::{render_text_clipped, CachedLayout};
+
+/// Height of the field box in logical pixels.
+const FIELD_HEIGHT: f32 = 32.0;
+/// Horizontal padding between the box edge and the text, in logical pixels.
+const FIELD_PADDING: f32 = 8.0;
+const FIELD_RADIUS: f32 = 4.0;
+/// Font size of the inline validation error in logical pixels.
+const ERROR_SIZE: f32 = 12.0;
+/// Gap between the box and the error line in logical pixels.
+const ERROR_GAP: f32 = 4.0;
+/// Width of the show-password toggle at the right end of a masked field, in logical pixels.
+const REVEAL_WIDTH: f32 = 32.0;
+/// The character a masked field draws for each grapheme of its text.
+const BULLET: char = '\u{2022}';
+
+/// The text being edited plus the caret (`caret`) and the fixed end of the selection (`anchor`),
+/// both byte offsets on grapheme boundaries. The selection is empty when they are equal.
+#[derive(Debug, Default)]
+pub struct EditBuffer {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+impl EditBuffer {
+    /// A buffer holding `text` with all of it selected, so typing replaces it.
+    pub fn new(text: &str) -> Self {
+        let text = sanitize(text);
+        Self { caret: text.len(), anchor: 0, text }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// The selected byte range (possibly empty), ordered.
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+    }
+
+    /// Replace the selection with `s` (control characters removed) and place the caret after it.
+    pub fn insert(&mut self, s: &str) {
+        let mut s = sanitize(s);
+        let range = self.selection();
+        let needed = self.text.len() - range.len() + s.len();
+        if needed > self.text.capacity() {
+            // Grow by hand rather than letting `replace_range` reallocate, so the old allocation is
+            // wiped instead of being freed with (possibly secret) text still in it.
+            let mut grown = String::with_capacity(needed.max(self.text.capacity() * 2));
+            grown.push_str(&self.text);
+            self.text.zeroize();
+            self.text = grown;
+        }
+        self.text.replace_range(range.clone(), &s);
+        self.caret = range.start + s.len();
+        self.anchor = self.caret;
+        s.zeroize();
+    }
+
+    /// One bullet per grapheme: what a masked field displays instead of the text.
+    pub fn masked_text(&self) -> String {
+        BULLET.to_string().repeat(self.text.graphemes(true).count())
+    }
+
+    /// Map a byte offset in the text to the matching offset in [`EditBuffer::masked_text`].
+    pub fn masked_index(&self, index: usize) -> usize {
+        self.text[..index].graphemes(true).count() * BULLET.len_utf8()
+    }
+
+    /// Map a byte offset in [`EditBuffer::masked_text`] back to one in the text.
+    pub fn unmasked_index(&self, index: usize) -> usize {
+        let n = index / BULLET.len_utf8();
+        self.text.grapheme_indices(true).nth(n).map_or(self.text.len(), |(i, _)| i)
+    }
+
+    /// Delete the selection, or the grapheme (or word, with `word`) before the caret.
+    pub fn backspace(&mut self, word: bool) {
+        if self.caret == self.anchor {
+            self.caret = if word { self.word_left() } else { self.grapheme_left() };
+        }
+        self.insert("");
+    }
+
+    /// Delete the selection, or the grapheme (or word, with `word`) after the caret.
+    pub fn delete(&mut self, word: bool) {
+        if self.caret == self.anchor {
+            self.caret = if word { self.word_right() } else { self.grapheme_right() };
+        }
+        self.insert("");
+    }
+
+    /// Move the caret one grapheme (or word) left. Without `extend`, a non-empty selection collapses
+    /// to its start instead.
+    pub fn move_left(&mut self, word: bool, extend: bool) {
+        let target = if !extend && self.caret != self.anchor && !word {
+            self.selection().start
+        } else if word {
+            self.word_left()
+        } else {
+            self.grapheme_left()
+        };
+        self.set_caret(target, extend);
+    }
+
+    /// Move the caret one grapheme (or word) right. Without `extend`, a non-empty selection
+    /// collapses to its end instead.
+    pub fn move_right(&mut self, word: bool, extend: bool) {
+        let target = if !extend && self.caret != self.anchor && !word {
+            self.selection().end
+        } else if word {
+            self.word_right()
+        } else {
+            self.grapheme_right()
+        };
+        self.set_caret(target, extend);
+    }
+
+    /// Move the caret to `index` (snapped to a grapheme boundary), keeping the anchor when `extend`.
+    pub fn set_caret(&mut self, index: usize, extend: bool) {
+        self.caret = self.snap(index.min(self.text.len()));
+        if !extend {
+            self.anchor = self.caret;
+        }
+    }
+
+    /// The nearest grapheme boundary at or before `index`.
+    fn snap(&self, index: usize) -> usize {
+        if index >= self.text.len() {
+            return self.text.len();
+        }
+        self.text.grapheme_indices(true).map(|(i, _)| i).take_while(|&i| i <= index).last().unwrap_or(0)
+    }
+
+    fn grapheme_left(&self) -> usize {
+        self.text[..self.caret].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+    }
+
+    fn grapheme_right(&self) -> usize {
+        self.text[self.caret..].graphemes(true).next().map_or(self.caret, |g| self.caret + g.len())
+    }
+
+    /// Start of the word before the caret (skipping any whitespace directly before it).
+    fn word_left(&self) -> usize {
+        let before = &self.text[..self.caret];
+        let trimmed = before.trim_end();
+        trimmed.rfind(char::is_whitespace).map_or(0, |i| i + trimmed[i..].chars().next().unwrap().len_utf8())
+    }
+
+    /// End of the word after the caret (skipping any whitespace directly after it).
+    fn word_right(&self) -> usize {
+        let after = &self.text[self.caret..];
+        let skipped = after.len() - after.trim_start().len();
+        let rest = &after[skipped..];
+        self.caret + skipped + rest.find(char::is_whitespace).unwrap_or(rest.len())
+    }
+}
+
+impl Drop for EditBuffer {
+    fn drop(&mut self) {
+        self.text.zeroize();
+    }
+}
+
+/// Make `s` fit a single-line field: line breaks and tabs become spaces, other control characters
+/// (eg. those produced by Ctrl+key combinations) are dropped. The result is never longer than `s`,
+/// so it is allocated once up front rather than reallocated (leaving partial copies behind) as it
+/// grows.
+fn sanitize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    out.extend(s.chars().filter(|&c| c != '\r').map(|c| if c == '\n' || c == '\t' { ' ' } else { c }).filter(|c| !c.is_control()));
+    out
+}
+
+pub struct TextField {
+    buffer: EditBuffer,
+    /// Password mode: the text is drawn as bullets (unless `revealed`), can't be copied out, and is
+    /// reported as a secret rather than plain text.
+    masked: bool,
+    revealed: bool,
+    /// Whether to reserve a line under the box for validation errors. Reserving it up front keeps the
+    /// window size stable as errors come and go.
+    has_error_line: bool,
+    error: Option<String>,
+    bounds: Rect,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    dirty: bool,
+    /// Horizontal text scroll in physical pixels, chosen at paint time to keep the caret visible.
+    scroll: f32,
+    /// Scale factor of the last paint, for mapping pointer positions onto the shaped text.
+    scale: f32,
+    cache: CachedLayout,
+    error_cache: CachedLayout,
+}
+
+impl TextField {
+    pub fn new(initial_text: &str, has_error_line: bool, masked: bool) -> Self {
+        Self {
+            buffer: EditBuffer::new(initial_text),
+            masked,
+            revealed: false,
+            has_error_line,
+            error: None,
+            bounds: Rect::default(),
+            hovered: false,
+            pressed: false,
+            focused: false,
+            dirty: true,
+            scroll: 0.0,
+            scale: 1.0,
+            cache: CachedLayout::default(),
+            error_cache: CachedLayout::default(),
+        }
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.masked && !self.revealed
+    }
+
+    /// The string actually shaped and drawn: the text itself, or one bullet per grapheme. Takes the
+    /// buffer rather than `self` so the result can be passed to `self.cache` without copying the text.
+    fn display_text(buffer: &EditBuffer, hidden: bool) -> Cow<'_, str> {
+        if hidden {
+            Cow::Owned(buffer.masked_text())
+        } else {
+            Cow::Borrowed(buffer.text())
+        }
+    }
+
+    /// Map a byte offset in the text to one in [`TextField::display_text`].
+    fn to_display(&self, index: usize) -> usize {
+        if self.is_hidden() {
+            self.buffer.masked_index(index)
+        } else {
+            index
+        }
+    }
+
+    /// The logical-pixel rectangle of the show-password toggle (masked fields only).
+    fn reveal_toggle_bounds(&self) -> Option<Rect> {
+        self.masked.then(|| Rect::new(self.bounds.x + self.bounds.w - REVEAL_WIDTH, self.bounds.y, REVEAL_WIDTH, FIELD_HEIGHT))
+    }
+
+    /// The caret byte index for a pointer at logical `x`.
+    fn index_at(&mut self, x: f32) -> usize {
+        let s = self.scale;
+        let local = (x - self.bounds.x - FIELD_PADDING) * s + self.scroll;
+        let display = Self::display_text(&self.buffer, self.is_hidden());
+        let index = self.cache.get(&display, false, BODY_SIZE * s, f32::INFINITY).hit_test(local);
+        if self.is_hidden() {
+            self.buffer.unmasked_index(index)
+        } else {
+            index
+        }
+    }
+
+    /// Paste the clipboard text over the selection.
+    fn paste(&mut self) {
+        if let Some(mut text) = clipboard::read() {
+            self.buffer.insert(&text);
+            text.zeroize();
+        }
+    }
+
+    /// Copy the selection to the clipboard (and, with `cut`, delete it). Masked fields refuse.
+    fn copy(&mut self, cut: bool) {
+        if self.masked {
+            return;
+        }
+        clipboard::write(self.buffer.selected_text());
+        if cut {
+            self.buffer.insert("");
+        }
+    }
+}
+
+impl Drop for TextField {
+    fn drop(&mut self) {
+        // Wipes any plaintext shaped while the password was revealed (see `CacheEntry`'s `Drop`).
+        self.cache.clear();
+    }
+}
+
+impl Component for TextField {
+    fn role(&self) -> Role {
+        Role::Content
+    }
+
+    fn measure(&mut self, ctx: &LayoutCtx) -> Size {
+        let error_h = if self.has_error_line { ERROR_GAP + ERROR_SIZE * 1.2 } else { 0.0 };
+        // Fill whatever width the column offers, but don't widen the window by itself.
+        let w = if ctx.available_width.is_finite() { ctx.available_width } else { 0.0 };
+        Size { w, h: FIELD_HEIGHT + error_h }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        self.scale = s;
+        let theme = ctx.theme;
+        let (x, y, w, h) = (self.bounds.x * s, self.bounds.y * s, self.bounds.w * s, self.bounds.h * s);
+        let box_h = FIELD_HEIGHT * s;
+        fill_rect(pm, x, y, w, h, theme.color_background);
+
+        // Box: background plus a border that thickens and takes the accent colour when focused.
+        let radius = FIELD_RADIUS * s;
+        fill_rounded_rect(pm, x, y, w, box_h, radius, theme.color_field_background);
+        let (border, border_w) = if self.focused {
+            (theme.color_field_border_focused, 2.0 * s)
+        } else if self.error.is_some() {
+            (theme.color_error_text, s)
+        } else {
+            (theme.color_field_border, s)
+        };
+        stroke_rounded_rect(pm, x, y, w, box_h, radius, border, border_w);
+
+        // Show-password toggle: an eye at the right end, struck through while the text is visible.
+        let reveal_w = if let Some(toggle) = self.reveal_toggle_bounds() {
+            let (cx, cy) = ((toggle.x + toggle.w / 2.0) * s, (toggle.y + toggle.h / 2.0) * s);
+            let color = theme.color_field_border;
+            stroke_eye(pm, cx, cy, 16.0 * s, 10.0 * s, color, 1.5 * s);
+            fill_circle(pm, cx, cy, 2.5 * s, color);
+            if self.revealed {
+                stroke_line(pm, cx - 7.0 * s, cy + 6.0 * s, cx + 7.0 * s, cy - 6.0 * s, color, 1.5 * s);
+            }
+            toggle.w * s
+        } else {
+            0.0
+        };
+
+        // Text area, inset by the padding. Everything inside is clipped to it.
+        let pad = FIELD_PADDING * s;
+        let inner = Rect::new(x + pad, y + 2.0 * s, (w - pad * 2.0 - reveal_w).max(0.0), box_h - 4.0 * s);
+        let selection = self.to_display(self.buffer.selection().start)..self.to_display(self.buffer.selection().end);
+        let caret = self.to_display(self.buffer.caret());
+        let display = Self::display_text(&self.buffer, self.is_hidden());
+        let layout = self.cache.get(&display, false, BODY_SIZE * s, f32::INFINITY);
+
+        // Scroll just enough to keep the caret in view, and never past the end of the text.
+        let caret_x = layout.caret_x(caret);
+        if caret_x - self.scroll > inner.w {
+            self.scroll = caret_x - inner.w;
+        } else if caret_x < self.scroll {
+            self.scroll = caret_x;
+        }
+        self.scroll = self.scroll.min((layout.total_width - inner.w).max(0.0)).max(0.0);
+
+        let text_x = inner.x - self.scroll;
+        let text_y = y + (box_h - layout.total_height) / 2.0;
+        if !selection.is_empty() && self.focused {
+            let x0 = (text_x + layout.caret_x(selection.start)).max(inner.x);
+            let x1 = (text_x + layout.caret_x(selection.end)).min(inner.x + inner.w);
+            if x1 > x0 {
+                fill_rect(pm, x0, text_y, x1 - x0, layout.total_height, theme.color_selection);
+            }
+        }
+        render_text_clipped(pm, layout, theme.color_body_text, text_x, text_y, inner);
+        if self.focused {
+            let cx = (text_x + caret_x).round().clamp(inner.x, inner.x + inner.w - s);
+            fill_rect(pm, cx, text_y, s.max(1.0), layout.total_height, theme.color_body_text);
+        }
+
+        if let Some(error) = self.error.as_deref() {
+            let layout = self.error_cache.get(error, false, ERROR_SIZE * s, w);
+            let clip = Rect::new(x, y + box_h, w, h - box_h);
+            render_text_clipped(pm, layout, theme.color_error_text, x, y + box_h + ERROR_GAP * s, clip);
+        }
+
+        self.dirty = false;
+        Rect::new(x, y, w, h)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_hovered(&mut self, v: bool) {
+        self.hovered = v;
+    }
+
+    fn set_pressed(&mut self, v: bool) {
+        self.pressed = v;
+    }
+
+    fn set_focused(&mut self, v: bool) {
+        if self.focused != v {
+            self.focused = v;
+            self.dirty = true;
+        }
+    }
+
+    fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    fn takes_initial_focus(&self) -> bool {
+        true
+    }
+
+    fn handle_key(&mut self, k: &KeyInput) -> bool {
+        // Word-wise movement would reveal where the spaces are, so in a masked field Ctrl+arrow
+        // and Ctrl+Backspace/Delete act on the whole text instead.
+        let word = k.ctrl && !self.masked;
+        let whole = k.ctrl && self.masked;
+        let b = &mut self.buffer;
+        match k.key {
+            Key::Named(NamedKey::ArrowLeft | NamedKey::Home) if whole || matches!(k.key, Key::Named(NamedKey::Home)) => {
+                b.set_caret(0, k.shift)
+            }
+            Key::Named(NamedKey::ArrowRight | NamedKey::End) if whole || matches!(k.key, Key::Named(NamedKey::End)) => {
+                b.set_caret(usize::MAX, k.shift)
+            }
+            Key::Named(NamedKey::ArrowLeft) => b.move_left(word, k.shift),
+            Key::Named(NamedKey::ArrowRight) => b.move_right(word, k.shift),
+            Key::Named(NamedKey::Backspace) if whole => {
+                b.set_caret(0, true);
+                b.insert("");
+            }
+            Key::Named(NamedKey::Backspace) => b.backspace(word),
+            Key::Named(NamedKey::Delete) if k.shift => self.copy(true),
+            Key::Named(NamedKey::Delete) if whole => {
+                b.set_caret(usize::MAX, true);
+                b.insert("");
+            }
+            Key::Named(NamedKey::Delete) => b.delete(word),
+            Key::Named(NamedKey::Insert) if k.shift => self.paste(),
+            Key::Named(NamedKey::Insert) if k.ctrl => self.copy(false),
+            Key::Character(c) if k.ctrl => match c.to_lowercase().as_str() {
+                "a" => b.select_all(),
+                "c" => self.copy(false),
+                "x" => self.copy(true),
+                "v" => self.paste(),
+                _ => return false,
+            },
+            // Printable keys insert their text; Tab, Enter and Escape fall through to the dialog.
+            Key::Character(_) | Key::Named(NamedKey::Space) if !k.ctrl => match k.text {
+                Some(text) if !sanitize(text).is_empty() => b.insert(text),
+                _ => return false,
+            },
+            _ => return false,
+        }
+        self.dirty = true;
+        true
+    }
+
+    fn pointer_pressed(&mut self, x: f32, y: f32) {
+        if self.reveal_toggle_bounds().is_some_and(|r| r.contains(x, y)) {
+            self.revealed = !self.revealed;
+            if !self.revealed {
+                // Drop the shaped plaintext rather than keeping it cached behind the bullets.
+                self.cache.clear();
+            }
+        } else {
+            let index = self.index_at(x);
+            self.buffer.set_caret(index, false);
+        }
+        self.dirty = true;
+    }
+
+    fn pointer_dragged(&mut self, x: f32, _y: f32) {
+        let index = self.index_at(x);
+        if index != self.buffer.caret() {
+            self.buffer.set_caret(index, true);
+            self.dirty = true;
+        }
+    }
+
+    fn cursor_icon(&self, x: f32, y: f32) -> CursorIcon {
+        if self.reveal_toggle_bounds().is_some_and(|r| r.contains(x, y)) {
+            CursorIcon::Pointer
+        } else {
+            CursorIcon::Text
+        }
+    }
+
+    fn collect(&self, response: &mut XDialogResponse) {
+        if self.masked {
+            response.secret = Some(XDialogSecret::new(self.buffer.text().to_string()));
+        } else {
+            response.text = Some(self.buffer.text().to_string());
+        }
+    }
+
+    fn apply(&mut self, u: &ControllerUpdate) -> bool {
+        if let ControllerUpdate::InputError(error) = u {
+            let error = error.map(str::to_string);
+            if self.error != error {
+                self.error = error;
+                self.dirty = true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_selects_everything() {
+        let mut b = EditBuffer::new("hello");
+        assert_eq!(b.selected_text(), "hello");
+        b.insert("x");
+        assert_eq!(b.text(), "x");
+        assert_eq!(b.caret(), 1);
+    }
+
+    #[test]
+    fn moves_by_grapheme() {
+        // "é" as e + combining acute is one grapheme spanning three bytes.
+        let mut b = EditBuffer::new("ae\u{301}b");
+        b.set_caret(0, false);
+        b.move_right(false, false);
+        assert_eq!(b.caret(), 1);
+        b.move_right(false, false);
+        assert_eq!(b.caret(), 4);
+        b.backspace(false);
+        assert_eq!(b.text(), "ab");
+    }
+
+    #[test]
+    fn collapses_selection_before_moving() {
+        let mut b = EditBuffer::new("abc");
+        b.move_left(false, false);
+        assert_eq!((b.caret(), b.selection()), (0, 0..0));
+        b.move_right(false, true);
+        b.move_right(false, true);
+        assert_eq!(b.selected_text(), "ab");
+        b.move_right(false, false);
+        assert_eq!((b.caret(), b.selection()), (2, 2..2));
+    }
+
+    #[test]
+    fn word_navigation_and_deletion() {
+        let mut b = EditBuffer::new("one two  three");
+        b.set_caret(usize::MAX, false);
+        b.move_left(true, false);
+        assert_eq!(b.caret(), 9);
+        b.move_left(true, false);
+        assert_eq!(b.caret(), 4);
+        b.move_right(true, true);
+        assert_eq!(b.selected_text(), "two");
+        b.set_caret(usize::MAX, false);
+        b.backspace(true);
+        assert_eq!(b.text(), "one two  ");
+        b.set_caret(0, false);
+        b.delete(true);
+        assert_eq!(b.text(), " two  ");
+    }
+
+    #[test]
+    fn insert_strips_line_breaks_and_controls() {
+        let mut b = EditBuffer::new("");
+        b.insert("a\r\nb\tc\u{1}");
+        assert_eq!(b.text(), "a b c");
+    }
+
+    #[test]
+    fn masked_indices_map_per_grapheme() {
+        let b = EditBuffer::new("ae\u{301}b");
+        assert_eq!(b.masked_text(), "\u{2022}\u{2022}\u{2022}");
+        assert_eq!(b.masked_index(4), 6);
+        assert_eq!(b.unmasked_index(6), 4);
+        assert_eq!(b.unmasked_index(9), 5);
+    }
+
+    #[test]
+    fn set_caret_snaps_inside_grapheme() {
+        let mut b = EditBuffer::new("e\u{301}x");
+        b.set_caret(2, false);
+        assert_eq!(b.caret(), 0);
+        b.set_caret(usize::MAX, false);
+        assert_eq!(b.caret(), 4);
+    }
+}
@@ -0,0 +1,106 @@
+//! The footer note: a secondary line of small text ("Your data will not be lost") with an optional
+//! small icon, in its own strip below the button strip.
+
+use tiny_skia::PixmapMut;
+
+use crate::model::XDialogIcon;
+
+use super::component::{Component, LayoutCtx, PaintCtx, Rect, Role, Size};
+use super::icons;
+use super::renderer::fill_rect;
+use super::text::{render_text, CachedLayout};
+
+/// Font size of the note in logical pixels.
+const NOTE_SIZE: f32 = 12.0;
+/// Side length of the note icon in logical pixels.
+const NOTE_ICON_SIZE: f32 = 16.0;
+/// Gap between the icon and the text in logical pixels.
+const ICON_GAP: f32 = 6.0;
+
+/// Spans the full window width, like the button strip above it. Measures to the wrapped text plus
+/// the theme's panel margin above and below, so the layout only has to stack it.
+pub struct FooterNote {
+    icon: XDialogIcon,
+    text: String,
+    bounds: Rect,
+    dirty: bool,
+    cache: CachedLayout,
+}
+
+impl FooterNote {
+    pub fn new(icon: XDialogIcon, text: &str) -> Self {
+        Self {
+            icon,
+            text: text.to_string(),
+            bounds: Rect::default(),
+            dirty: true,
+            cache: CachedLayout::default(),
+        }
+    }
+
+    /// Offset of the text from the content margin, leaving room for the icon when there is one.
+    fn text_offset(&self) -> f32 {
+        if self.icon == XDialogIcon::None {
+            0.0
+        } else {
+            NOTE_ICON_SIZE + ICON_GAP
+        }
+    }
+}
+
+impl Component for FooterNote {
+    fn role(&self) -> Role {
+        Role::FooterNote
+    }
+
+    fn measure(&mut self, ctx: &LayoutCtx) -> Size {
+        let x = ctx.theme.default_content_margin as f32;
+        let margin = ctx.theme.button_panel_margin as f32;
+        let text_w = (ctx.available_width - x * 2.0 - self.text_offset()).max(1.0);
+        let layout = self.cache.get(&self.text, false, NOTE_SIZE, text_w);
+        let content_h = layout.total_height.max(if self.icon == XDialogIcon::None { 0.0 } else { NOTE_ICON_SIZE });
+        Size {
+            w: ctx.available_width,
+            h: content_h + margin * 2.0,
+        }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let w = pm.width() as f32;
+        let (y, h) = (self.bounds.y * s, self.bounds.h * s);
+        fill_rect(pm, 0.0, y, w, h, theme.color_background_alt);
+        // A hairline separates the note from the buttons, whose strip has the same fill.
+        fill_rect(pm, 0.0, y, w, s.round().max(1.0), theme.color_field_border);
+
+        let x = (self.bounds.x + theme.default_content_margin as f32) * s;
+        let top = y + theme.button_panel_margin as f32 * s;
+        let offset = self.text_offset() * s;
+        let text_w = (self.bounds.w * s - theme.default_content_margin as f32 * s * 2.0 - offset).max(1.0);
+        let layout = self.cache.get(&self.text, false, NOTE_SIZE * s, text_w);
+
+        // The icon is centred on the first line of text.
+        if self.icon != XDialogIcon::None {
+            let size = NOTE_ICON_SIZE * s;
+            let icon_y = top + ((layout.line_height - size) / 2.0).max(0.0);
+            icons::draw_icon(pm, &self.icon, x, icon_y, size, theme);
+        }
+        render_text(pm, layout, theme.color_body_text, x + offset, top);
+
+        self.dirty = false;
+        Rect::new(0.0, y, w, h)
+    }
+}
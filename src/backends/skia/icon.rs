@@ -1,15 +1,16 @@
-//! The main dialog icon (information / warning / error), drawn top-left.
+//! The main dialog icon (information / warning / error, or a custom image), drawn top-left.
 
 use tiny_skia::{Pixmap, PixmapMut, PixmapPaint, Transform};
 
 use crate::model::XDialogIcon;
 
-use super::component::{Component, LayoutCtx, PaintCtx, Rect, Role, Size};
+use super::component::{Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size};
 use super::icons;
 use super::renderer::fill_rect;
 
-/// Wraps [`icons::draw_icon`] as a component. Measures to the theme's `main_icon_size` and is
-/// static (only repaints on resize/relayout).
+/// Wraps [`icons::draw_icon`] as a component. Measures to the theme's `main_icon_size` (or to
+/// nothing for `XDialogIcon::None`) and only repaints on resize/relayout or when
+/// [`ControllerUpdate::Icon`] replaces it.
 pub struct Icon {
     icon: XDialogIcon,
     bounds: Rect,
@@ -37,6 +38,9 @@ impl Component for Icon {
     }
 
     fn measure(&mut self, ctx: &LayoutCtx) -> Size {
+        if self.icon == XDialogIcon::None {
+            return Size::default();
+        }
         let s = ctx.theme.main_icon_size as f32;
         Size { w: s, h: s }
     }
@@ -71,7 +75,7 @@ impl Component for Icon {
                 // Rasterize onto a transparent tile; the anti-aliased edges then composite correctly
                 // over the background just filled.
                 if let Some(mut tile) = Pixmap::new(size_px, size_px) {
-                    icons::draw_icon(&mut tile.as_mut(), &self.icon, 0.0, 0.0, size_px as f32);
+                    icons::draw_icon(&mut tile.as_mut(), &self.icon, 0.0, 0.0, size_px as f32, ctx.theme);
                     self.tile = Some((size_px, tile));
                 }
             }
@@ -90,4 +94,16 @@ impl Component for Icon {
         self.dirty = false;
         Rect::new(x, y, w, h)
     }
+
+    fn apply(&mut self, u: &ControllerUpdate) -> bool {
+        match u {
+            ControllerUpdate::Icon(icon) if **icon != self.icon => {
+                self.icon = (*icon).clone();
+                self.tile = None;
+                self.dirty = true;
+                true // the text moves over when the icon appears or disappears
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,358 @@
+//! Finds the standard dialog icons (`dialog-information`, `dialog-warning`, ...) in the user's icon
+//! theme, so they match the rest of the desktop. This follows the freedesktop.org Icon Theme
+//! Specification: a theme's `index.theme` lists its icon directories and the sizes they hold, an
+//! icon missing from a theme is looked for in the themes it inherits and then in `hicolor`, and the
+//! PNG or SVG file closest to the size wanted is used, a PNG over an SVG of the same fit.
+//!
+//! Themes draw some SVG icons in `currentColor`, expecting the toolkit to recolour them, and those
+//! (and any with text, as no fonts are loaded for icons) would draw wrongly, so they're skipped.
+//!
+//! The theme is read once at startup by [`init`]. Without it (or when the icon isn't found) the
+//! caller draws its own vector icon.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::icon::IconData;
+
+use super::desktop;
+
+/// The themes to search, in order: the user's theme, the themes it inherits, and `hicolor`.
+static THEMES: OnceLock<Vec<Theme>> = OnceLock::new();
+/// An icon's name and size in physical pixels.
+type IconKey = (&'static str, u32);
+/// Icons already looked up, including those no theme has.
+static FOUND: Mutex<Vec<(IconKey, Option<IconData>)>> = Mutex::new(Vec::new());
+
+/// Read the user's icon theme and the themes it inherits. Later calls have no effect.
+pub fn init() {
+    THEMES.get_or_init(|| {
+        let bases = base_dirs();
+        let name = desktop::detect_icon_theme().or_else(configured_theme_name);
+        theme_chain(name.as_deref(), &bases)
+    });
+}
+
+/// The icon `name` from the user's theme, for drawing `size` physical pixels across. `None` before
+/// [`init`], or if no theme has the icon as a PNG or as an SVG which can be drawn as is.
+pub fn themed_icon(name: &'static str, size: u32) -> Option<IconData> {
+    let themes = THEMES.get()?;
+    let mut found = FOUND.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, icon)) = found.iter().find(|(key, _)| *key == (name, size)) {
+        return icon.clone();
+    }
+    let icon = find_icon(themes, &base_dirs(), name, size).and_then(|path| load_icon(&path));
+    found.push(((name, size), icon.clone()));
+    icon
+}
+
+fn load_icon(path: &Path) -> Option<IconData> {
+    let icon = match path.extension()?.to_str()? {
+        "png" => std::fs::read(path).ok().map(IconData::from_png),
+        "svg" => match std::fs::read_to_string(path) {
+            Ok(text) if !svg_draws_as_is(&text) => {
+                log::debug!("xdialog: not using icon {}, which needs recolouring or text", path.display());
+                return None;
+            }
+            text => text.ok().map(IconData::from_svg),
+        },
+        _ => None,
+    };
+    if icon.is_none() {
+        log::warn!("xdialog: failed to read icon {}", path.display());
+    }
+    icon
+}
+
+/// Whether an SVG icon draws as its theme intends without a toolkit's help: it isn't coloured with
+/// `currentColor` (symbolic icons, which would come out black) and has no text.
+fn svg_draws_as_is(text: &str) -> bool {
+    !text.to_ascii_lowercase().contains("currentcolor") && !text.contains("<text")
+}
+
+/// The directories icon themes are installed in, most important first.
+fn base_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| home.as_ref().map(|h| h.join(".local/share")));
+    let data_dirs = std::env::var("XDG_DATA_DIRS").ok().filter(|d| !d.is_empty());
+    let data_dirs = data_dirs.as_deref().unwrap_or("/usr/local/share:/usr/share");
+
+    let mut dirs: Vec<PathBuf> = home.map(|h| h.join(".icons")).into_iter().collect();
+    dirs.extend(data_home.map(|d| d.join("icons")));
+    dirs.extend(data_dirs.split(':').filter(|d| !d.is_empty()).map(|d| Path::new(d).join("icons")));
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+    dirs
+}
+
+/// The icon theme set in the desktop's configuration files, for when the settings portal doesn't
+/// say: KDE Plasma's `kdeglobals` (Breeze unless changed), or else GTK's `settings.ini`.
+fn configured_theme_name() -> Option<String> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    let read = |file: &str| std::fs::read_to_string(config_home.join(file)).ok().map(|text| KeyFile::parse(&text));
+
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    if desktop.split(':').any(|d| d.eq_ignore_ascii_case("KDE")) {
+        let theme = read("kdeglobals").and_then(|f| f.get("Icons", "Theme").map(str::to_string));
+        return Some(theme.unwrap_or_else(|| "breeze".to_string()));
+    }
+    ["gtk-4.0/settings.ini", "gtk-3.0/settings.ini"]
+        .iter()
+        .find_map(|file| read(file)?.get("Settings", "gtk-icon-theme-name").map(str::to_string))
+}
+
+/// The themes to search for `name`: it, the themes it inherits (depth first, as the spec's lookup
+/// recurses), and `hicolor`, skipping any that aren't installed.
+fn theme_chain(name: Option<&str>, bases: &[PathBuf]) -> Vec<Theme> {
+    fn add(name: &str, bases: &[PathBuf], chain: &mut Vec<Theme>) {
+        if chain.len() >= 16 || chain.iter().any(|t| t.name == name) {
+            return;
+        }
+        if let Some(theme) = Theme::load(name, bases) {
+            let parents = theme.parents.clone();
+            chain.push(theme);
+            for parent in parents {
+                add(&parent, bases, chain);
+            }
+        }
+    }
+    let mut chain = Vec::new();
+    if let Some(name) = name {
+        add(name, bases, &mut chain);
+    }
+    add("hicolor", bases, &mut chain);
+    chain
+}
+
+/// The file of icon `name` for `size` physical pixels: the first theme that has it decides, taking
+/// a directory made for that size if it can, and otherwise the one closest to it, with a PNG
+/// preferred to an SVG of the same fit. Failing that, an unthemed icon directly in a base directory.
+fn find_icon(themes: &[Theme], bases: &[PathBuf], name: &str, size: u32) -> Option<PathBuf> {
+    const EXTENSIONS: [&str; 2] = ["png", "svg"];
+    for theme in themes {
+        // Ranked by distance from the size, then PNG (0) before SVG (1).
+        let mut closest: Option<((u32, usize), PathBuf)> = None;
+        for dir in &theme.dirs {
+            for root in &theme.roots {
+                for (rank, ext) in EXTENSIONS.iter().enumerate() {
+                    let path = root.join(&dir.path).join(format!("{}.{}", name, ext));
+                    if !path.is_file() {
+                        continue;
+                    }
+                    let key = (dir.distance(size), rank);
+                    if key == (0, 0) {
+                        return Some(path);
+                    }
+                    if closest.as_ref().is_none_or(|(k, _)| key < *k) {
+                        closest = Some((key, path));
+                    }
+                }
+            }
+        }
+        if let Some((_, path)) = closest {
+            return Some(path);
+        }
+    }
+    bases.iter().flat_map(|base| EXTENSIONS.map(|ext| base.join(format!("{}.{}", name, ext)))).find(|p| p.is_file())
+}
+
+/// An installed icon theme.
+struct Theme {
+    name: String,
+    /// The theme's directory in each base directory it is installed in.
+    roots: Vec<PathBuf>,
+    dirs: Vec<IconDir>,
+    parents: Vec<String>,
+}
+
+impl Theme {
+    /// Read the theme `name` from the first `index.theme` found for it in `bases`.
+    fn load(name: &str, bases: &[PathBuf]) -> Option<Theme> {
+        let roots: Vec<PathBuf> = bases.iter().map(|b| b.join(name)).filter(|r| r.is_dir()).collect();
+        let index = roots.iter().find_map(|r| std::fs::read_to_string(r.join("index.theme")).ok())?;
+        let index = KeyFile::parse(&index);
+        let list = |key: &str| -> Vec<String> {
+            let value = index.get("Icon Theme", key).unwrap_or_default();
+            value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+        };
+        let dirs = list("Directories").into_iter().chain(list("ScaledDirectories")).filter_map(|d| IconDir::parse(&index, d)).collect();
+        Some(Theme { name: name.to_string(), roots, dirs, parents: list("Inherits") })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SizeType {
+    Fixed,
+    Scaled,
+    Threshold,
+}
+
+/// A directory of icons in a theme, and the sizes (in logical pixels, at `scale`) it holds.
+#[derive(Debug)]
+struct IconDir {
+    path: String,
+    size: u32,
+    scale: u32,
+    size_type: SizeType,
+    min_size: u32,
+    max_size: u32,
+    threshold: u32,
+}
+
+impl IconDir {
+    fn parse(index: &KeyFile, path: String) -> Option<IconDir> {
+        let number = |key: &str| index.get(&path, key).and_then(|v| v.trim().parse::<u32>().ok());
+        let size = number("Size")?;
+        let size_type = match index.get(&path, "Type") {
+            Some("Fixed") => SizeType::Fixed,
+            Some("Scalable") => SizeType::Scaled,
+            _ => SizeType::Threshold,
+        };
+        Some(IconDir {
+            size,
+            scale: number("Scale").unwrap_or(1).max(1),
+            size_type,
+            min_size: number("MinSize").unwrap_or(size),
+            max_size: number("MaxSize").unwrap_or(size),
+            threshold: number("Threshold").unwrap_or(2),
+            path,
+        })
+    }
+
+    /// How far the icons in this directory are from `size` physical pixels; 0 if the directory is
+    /// made for that size. (The spec's `DirectorySizeDistance`, with the requested scale folded
+    /// into the size.) The numbers come from the theme's files, so the arithmetic saturates rather
+    /// than overflowing on nonsense values.
+    fn distance(&self, size: u32) -> u32 {
+        let (min, max) = match self.size_type {
+            SizeType::Fixed => (self.size, self.size),
+            SizeType::Scaled => (self.min_size, self.max_size),
+            SizeType::Threshold => (self.size.saturating_sub(self.threshold), self.size.saturating_add(self.threshold)),
+        };
+        let (min, max) = (min.saturating_mul(self.scale), max.saturating_mul(self.scale));
+        if size < min {
+            min - size
+        } else {
+            size.saturating_sub(max)
+        }
+    }
+}
+
+/// A desktop-entry style key file (`index.theme`, `kdeglobals`, GTK's `settings.ini`): groups in
+/// `[brackets]` of `key=value` lines.
+struct KeyFile {
+    groups: HashMap<String, HashMap<String, String>>,
+}
+
+impl KeyFile {
+    fn parse(text: &str) -> KeyFile {
+        let mut groups: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut group = String::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                group = name.to_string();
+            } else if let Some((key, value)) = line.split_once('=') {
+                groups.entry(group.clone()).or_default().insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+        KeyFile { groups }
+    }
+
+    fn get(&self, group: &str, key: &str) -> Option<&str> {
+        self.groups.get(group)?.get(key).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A base directory with a "Test" theme inheriting "Parent", and "hicolor".
+    fn install_themes() -> PathBuf {
+        let base = std::env::temp_dir().join(format!("xdialog-icon-theme-{}", std::process::id()));
+        let write = |path: &str, text: &str| {
+            let path = base.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        write(
+            "Test/index.theme",
+            "[Icon Theme]\nName=Test\nInherits=Parent\nDirectories=16x16/status,48x48/status,48x48@2/status\n\n\
+             [16x16/status]\nSize=16\nType=Fixed\n\n[48x48/status]\nSize=48\nType=Fixed\n\n\
+             [48x48@2/status]\nSize=48\nScale=2\nType=Fixed\n",
+        );
+        write("Test/16x16/status/dialog-warning.png", "");
+        write("Test/48x48/status/dialog-warning.png", "");
+        write("Test/48x48@2/status/dialog-warning.png", "");
+        write("Test/48x48/status/dialog-error.png", "");
+        write(
+            "Parent/index.theme",
+            "[Icon Theme]\nName=Parent\nDirectories=scalable/status,48x48/status\n\n\
+             [scalable/status]\nSize=64\nMinSize=8\nMaxSize=512\nType=Scalable\n\n[48x48/status]\nSize=48\nType=Fixed\n",
+        );
+        write("Parent/scalable/status/dialog-information.svg", "");
+        write("Parent/scalable/status/dialog-error.svg", "");
+        write("Parent/scalable/status/dialog-question.svg", "");
+        write("Parent/48x48/status/dialog-question.png", "");
+        write("hicolor/index.theme", "[Icon Theme]\nName=Hicolor\nDirectories=\n");
+        base
+    }
+
+    #[test]
+    fn icons_come_from_the_closest_size_in_the_first_theme_that_has_them() {
+        let base = install_themes();
+        let bases = [base.clone()];
+        let themes = theme_chain(Some("Test"), &bases);
+        assert_eq!(themes.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["Test", "Parent", "hicolor"]);
+
+        let find = |name, size| find_icon(&themes, &bases, name, size).map(|p| p.strip_prefix(&base).unwrap().to_path_buf());
+        assert_eq!(find("dialog-warning", 16), Some("Test/16x16/status/dialog-warning.png".into()));
+        assert_eq!(find("dialog-warning", 96), Some("Test/48x48@2/status/dialog-warning.png".into()));
+        assert_eq!(find("dialog-warning", 24), Some("Test/16x16/status/dialog-warning.png".into()));
+        // The user's theme wins even at the wrong size.
+        assert_eq!(find("dialog-error", 16), Some("Test/48x48/status/dialog-error.png".into()));
+        assert_eq!(find("dialog-information", 48), Some("Parent/scalable/status/dialog-information.svg".into()));
+        // A PNG of the size beats an SVG listed first, but not a closer SVG.
+        assert_eq!(find("dialog-question", 48), Some("Parent/48x48/status/dialog-question.png".into()));
+        assert_eq!(find("dialog-question", 128), Some("Parent/scalable/status/dialog-question.svg".into()));
+        assert_eq!(find("dialog-password", 48), None);
+
+        // Without a theme of its own, only hicolor is searched.
+        assert_eq!(theme_chain(None, &bases).len(), 1);
+        std::fs::remove_dir_all(base).unwrap();
+    }
+
+    #[test]
+    fn svg_icons_needing_recolouring_or_text_are_skipped() {
+        assert!(svg_draws_as_is(r##"<svg viewBox="0 0 16 16"><style>.a{fill:#e01b24}</style><path class="a" d="M0 0h16v16z"/></svg>"##));
+        assert!(!svg_draws_as_is(r#"<svg viewBox="0 0 16 16"><path fill="currentColor" d="M0 0h16v16z"/></svg>"#));
+        assert!(!svg_draws_as_is(r#"<svg viewBox="0 0 16 16"><style>path{fill:CurrentColor}</style></svg>"#));
+        assert!(!svg_draws_as_is(r#"<svg viewBox="0 0 16 16"><text>!</text></svg>"#));
+    }
+
+    #[test]
+    fn directory_sizes_follow_their_type() {
+        let index = KeyFile::parse(
+            "[a]\nSize=32\n\n[b]\nSize=32\nType=Fixed\nScale=2\n\n[c]\nSize=32\nType=Scalable\nMinSize=16\nMaxSize=64",
+        );
+        let dir = |name: &str| IconDir::parse(&index, name.to_string()).unwrap();
+        assert_eq!([dir("a").distance(30), dir("a").distance(29), dir("a").distance(40)], [0, 1, 6]);
+        assert_eq!([dir("b").distance(64), dir("b").distance(32)], [0, 32]);
+        assert_eq!([dir("c").distance(8), dir("c").distance(48), dir("c").distance(100)], [8, 0, 36]);
+        assert!(IconDir::parse(&index, "missing".to_string()).is_none());
+
+        // Malformed values saturate instead of overflowing.
+        let index = KeyFile::parse("[huge]\nSize=4294967295\nScale=4294967295\nThreshold=4294967295");
+        let huge = IconDir::parse(&index, "huge".to_string()).unwrap();
+        assert_eq!(huge.distance(48), 0);
+    }
+}
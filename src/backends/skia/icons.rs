@@ -1,71 +1,173 @@
-use tiny_skia::PixmapMut;
+use tiny_skia::{PathBuilder, PixmapMut};
 
-use super::renderer::{fill_circle, fill_rounded_rect, stroke_line};
+use super::custom_icon::draw_custom_icon;
+use super::icon_theme::themed_icon;
+use super::renderer::{fill_circle, fill_path, fill_rounded_rect, stroke_line, stroke_path};
+use super::theme::SkiaTheme;
 
 use crate::model::XDialogIcon;
 
-/// Draw an icon directly onto the target pixmap at the given position and size.
-pub fn draw_icon(pixmap: &mut PixmapMut, icon: &XDialogIcon, x: f32, y: f32, size: f32) {
+const GLYPH: (u8, u8, u8) = (0xFF, 0xFF, 0xFF);
+const WARNING_GLYPH: (u8, u8, u8) = (0x3D, 0x3D, 0x3D);
+
+/// Draw an icon directly onto the target pixmap at the given position and size. The standard
+/// icons come from the user's icon theme where it has them, and are otherwise drawn here in the
+/// theme's icon colours.
+pub fn draw_icon(pixmap: &mut PixmapMut, icon: &XDialogIcon, x: f32, y: f32, size: f32, theme: &SkiaTheme) {
+    let themed_name = match icon {
+        XDialogIcon::Information => "dialog-information",
+        XDialogIcon::Error => "dialog-error",
+        XDialogIcon::Warning => "dialog-warning",
+        XDialogIcon::Question => "dialog-question",
+        XDialogIcon::Shield => "security-high",
+        XDialogIcon::Success => "emblem-ok",
+        XDialogIcon::None | XDialogIcon::Custom(_) => "",
+    };
+    if let Some(data) = Some(themed_name).filter(|n| !n.is_empty()).and_then(|n| themed_icon(n, size.round() as u32)) {
+        draw_custom_icon(pixmap, &data, x, y, size);
+        return;
+    }
     match icon {
         XDialogIcon::None => {}
-        XDialogIcon::Information => draw_info_icon(pixmap, x, y, size),
-        XDialogIcon::Error => draw_error_icon(pixmap, x, y, size),
-        XDialogIcon::Warning => draw_warning_icon(pixmap, x, y, size),
+        XDialogIcon::Information => draw_info_icon(pixmap, x, y, size, theme.color_icon_information),
+        XDialogIcon::Error => draw_error_icon(pixmap, x, y, size, theme.color_icon_error),
+        XDialogIcon::Warning => draw_warning_icon(pixmap, x, y, size, theme.color_icon_warning),
+        XDialogIcon::Question => draw_question_icon(pixmap, x, y, size, theme.color_icon_question),
+        XDialogIcon::Shield => draw_shield_icon(pixmap, x, y, size, theme.color_icon_shield),
+        XDialogIcon::Success => draw_success_icon(pixmap, x, y, size, theme.color_icon_success),
+        XDialogIcon::Custom(data) => draw_custom_icon(pixmap, data, x, y, size),
     }
 }
 
-/// Blue circle with white "i"
-fn draw_info_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32) {
+/// Circle (blue by default) with white "i"
+fn draw_info_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, color: (u8, u8, u8)) {
     let cx = x + s / 2.0;
     let cy = y + s / 2.0;
     let radius = s / 2.0 - 1.0;
 
-    fill_circle(pixmap, cx, cy, radius, (0x21, 0x96, 0xF3));
+    fill_circle(pixmap, cx, cy, radius, color);
 
     // Dot
     let dot_radius = s * 0.07;
     let dot_y = cy - s * 0.2;
-    fill_circle(pixmap, cx, dot_y, dot_radius, (0xFF, 0xFF, 0xFF));
+    fill_circle(pixmap, cx, dot_y, dot_radius, GLYPH);
 
     // Body
     let body_w = s * 0.1;
     let body_h = s * 0.3;
     let body_x = cx - body_w / 2.0;
     let body_y = cy - s * 0.05;
-    fill_rounded_rect(pixmap, body_x, body_y, body_w, body_h, body_w / 2.0, (0xFF, 0xFF, 0xFF));
+    fill_rounded_rect(pixmap, body_x, body_y, body_w, body_h, body_w / 2.0, GLYPH);
 }
 
-/// Red circle with white "X"
-fn draw_error_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32) {
+/// Circle (red by default) with white "X"
+fn draw_error_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, color: (u8, u8, u8)) {
     let cx = x + s / 2.0;
     let cy = y + s / 2.0;
     let radius = s / 2.0 - 1.0;
 
-    fill_circle(pixmap, cx, cy, radius, (0xD7, 0x5A, 0x4A));
+    fill_circle(pixmap, cx, cy, radius, color);
 
     let arm = s * 0.18;
     let stroke_w = s * 0.08;
-    stroke_line(pixmap, cx - arm, cy - arm, cx + arm, cy + arm, (0xFF, 0xFF, 0xFF), stroke_w);
-    stroke_line(pixmap, cx + arm, cy - arm, cx - arm, cy + arm, (0xFF, 0xFF, 0xFF), stroke_w);
+    stroke_line(pixmap, cx - arm, cy - arm, cx + arm, cy + arm, GLYPH, stroke_w);
+    stroke_line(pixmap, cx + arm, cy - arm, cx - arm, cy + arm, GLYPH, stroke_w);
 }
 
-/// Yellow/orange circle with dark "!"
-fn draw_warning_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32) {
+/// Circle (yellow by default) with dark "!"
+fn draw_warning_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, color: (u8, u8, u8)) {
     let cx = x + s / 2.0;
     let cy = y + s / 2.0;
     let radius = s / 2.0 - 1.0;
 
-    fill_circle(pixmap, cx, cy, radius, (0xFF, 0xC1, 0x07));
+    fill_circle(pixmap, cx, cy, radius, color);
 
     // Body
     let body_w = s * 0.1;
     let body_h = s * 0.28;
     let body_x = cx - body_w / 2.0;
     let body_y = cy - s * 0.25;
-    fill_rounded_rect(pixmap, body_x, body_y, body_w, body_h, body_w / 2.0, (0x3D, 0x3D, 0x3D));
+    fill_rounded_rect(pixmap, body_x, body_y, body_w, body_h, body_w / 2.0, WARNING_GLYPH);
 
     // Dot
     let dot_radius = s * 0.065;
     let dot_y = cy + s * 0.18;
-    fill_circle(pixmap, cx, dot_y, dot_radius, (0x3D, 0x3D, 0x3D));
+    fill_circle(pixmap, cx, dot_y, dot_radius, WARNING_GLYPH);
+}
+
+/// Circle (indigo by default) with white "?"
+fn draw_question_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, color: (u8, u8, u8)) {
+    let cx = x + s / 2.0;
+    let cy = y + s / 2.0;
+    let radius = s / 2.0 - 1.0;
+
+    fill_circle(pixmap, cx, cy, radius, color);
+
+    // Hook: over the top and round to the right, then down into the stem.
+    let mut pb = PathBuilder::new();
+    pb.move_to(cx - s * 0.13, cy - s * 0.11);
+    pb.cubic_to(cx - s * 0.13, cy - s * 0.27, cx + s * 0.14, cy - s * 0.28, cx + s * 0.14, cy - s * 0.11);
+    pb.cubic_to(cx + s * 0.14, cy - s * 0.02, cx, cy - s * 0.02, cx, cy + s * 0.07);
+    if let Some(path) = pb.finish() {
+        stroke_path(pixmap, &path, GLYPH, s * 0.09);
+    }
+
+    // Dot
+    let dot_radius = s * 0.065;
+    let dot_y = cy + s * 0.2;
+    fill_circle(pixmap, cx, dot_y, dot_radius, GLYPH);
+}
+
+/// Shield quartered in blue and yellow by default, like the Windows "needs administrator" shield.
+fn draw_shield_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, (first, second): ((u8, u8, u8), (u8, u8, u8))) {
+    let cx = x + s / 2.0;
+    let half_w = s * 0.36;
+    let (top, shoulder, middle, bottom) = (y + s * 0.06, y + s * 0.17, y + s * 0.45, y + s * 0.95);
+
+    // The outline: a slightly bowed top edge, straight sides down to the middle, then curving in
+    // to the point at the bottom.
+    let mut pb = PathBuilder::new();
+    pb.move_to(cx, top);
+    pb.quad_to(cx + half_w * 0.5, shoulder, cx + half_w, shoulder);
+    pb.line_to(cx + half_w, middle);
+    pb.cubic_to(cx + half_w, y + s * 0.72, cx + half_w * 0.35, y + s * 0.87, cx, bottom);
+    pb.cubic_to(cx - half_w * 0.35, y + s * 0.87, cx - half_w, y + s * 0.72, cx - half_w, middle);
+    pb.line_to(cx - half_w, shoulder);
+    pb.quad_to(cx - half_w * 0.5, shoulder, cx, top);
+    pb.close();
+    if let Some(path) = pb.finish() {
+        fill_path(pixmap, &path, first);
+    }
+
+    // The top-right and bottom-left quarters in the second colour.
+    let mut pb = PathBuilder::new();
+    pb.move_to(cx, top);
+    pb.quad_to(cx + half_w * 0.5, shoulder, cx + half_w, shoulder);
+    pb.line_to(cx + half_w, middle);
+    pb.line_to(cx, middle);
+    pb.close();
+    pb.move_to(cx, middle);
+    pb.line_to(cx - half_w, middle);
+    pb.cubic_to(cx - half_w, y + s * 0.72, cx - half_w * 0.35, y + s * 0.87, cx, bottom);
+    pb.close();
+    if let Some(path) = pb.finish() {
+        fill_path(pixmap, &path, second);
+    }
+}
+
+/// Circle (green by default) with white check mark
+fn draw_success_icon(pixmap: &mut PixmapMut, x: f32, y: f32, s: f32, color: (u8, u8, u8)) {
+    let cx = x + s / 2.0;
+    let cy = y + s / 2.0;
+    let radius = s / 2.0 - 1.0;
+
+    fill_circle(pixmap, cx, cy, radius, color);
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(cx - s * 0.2, cy + s * 0.01);
+    pb.line_to(cx - s * 0.06, cy + s * 0.15);
+    pb.line_to(cx + s * 0.21, cy - s * 0.13);
+    if let Some(path) = pb.finish() {
+        stroke_path(pixmap, &path, GLYPH, s * 0.09);
+    }
 }
@@ -1,39 +1,89 @@
-//! Wrapped text — both the dialog title and the body message, distinguished by a [`LabelKind`]
-//! flag rather than two near-identical types.
+//! Wrapped text — the dialog title and the body message, distinguished by a [`LabelKind`] flag
+//! rather than near-identical types — and the single-line status of a progress dialog.
+//!
+//! Body text may contain hyperlinks. Each link is underlined in the accent colour, highlighted
+//! while hovered, and a Tab stop: Tab and Shift+Tab step through the links before leaving the
+//! label, and Enter, Space or a click opens the focused one.
 
 use tiny_skia::PixmapMut;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
 
-use super::component::{Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE, TITLE_SIZE};
-use super::renderer::fill_rect;
-use super::text::{render_text, CachedLayout};
+use crate::hyperlink::{open_link, parse_links, MessageLink};
+
+use super::component::{Component, ControllerUpdate, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE, TITLE_SIZE};
+use super::renderer::{fill_rect, stroke_rounded_rect};
+use super::text::{layout_rich_text, middle_ellipsis, render_text, CachedLayout, TextLayout, TextSpan};
 use super::theme::SkiaTheme;
 
 /// Which kind of label this is — selects font, size and colour, and whether it reacts to
-/// [`ControllerUpdate::BodyText`].
+/// [`ControllerUpdate::MainInstruction`], [`ControllerUpdate::BodyText`] or
+/// [`ControllerUpdate::StatusText`].
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum LabelKind {
     Title,
     Body,
+    /// The status line under a progress bar: a single line, shortened with a middle ellipsis, so
+    /// that frequent updates (eg. the file being copied) never resize the window. It takes no space
+    /// until the first status is set, and keeps its line from then on.
+    Status,
 }
 
 pub struct Label {
     kind: LabelKind,
+    /// The text as displayed, with any link markup removed.
     text: String,
+    links: Vec<MessageLink>,
+    /// Logical rectangles covering each link's text, one per line it spans.
+    link_rects: Vec<Vec<Rect>>,
+    hovered_link: Option<usize>,
+    /// The link with keyboard focus, while the label is focused.
+    focused_link: Option<usize>,
     bounds: Rect,
     dirty: bool,
     /// Shaped text for the paint pass, reused until the text or physical layout changes.
     cache: CachedLayout,
+    /// The styled paint layout of text with links, keyed on its physical size and wrap width.
+    rich: Option<(f32, f32, TextLayout)>,
+    /// Whether a status label has had text, and so keeps its line.
+    reserved: bool,
+    /// A status label's text as shortened to fit, keyed on the physical size and width it fits.
+    fitted: Option<(f32, f32, String)>,
 }
 
 impl Label {
     pub fn new(kind: LabelKind, text: &str) -> Self {
-        Self {
+        let mut label = Self {
             kind,
-            text: text.to_string(),
+            text: String::new(),
+            links: Vec::new(),
+            link_rects: Vec::new(),
+            hovered_link: None,
+            focused_link: None,
             bounds: Rect::default(),
             dirty: true,
             cache: CachedLayout::default(),
-        }
+            rich: None,
+            reserved: false,
+            fitted: None,
+        };
+        label.set_text(text);
+        label
+    }
+
+    /// Set the text, extracting the links from body text. Titles and statuses are shown as written.
+    fn set_text(&mut self, text: &str) {
+        (self.text, self.links) = match self.kind {
+            LabelKind::Title | LabelKind::Status => (text.to_string(), Vec::new()),
+            LabelKind::Body => parse_links(text),
+        };
+        self.reserved |= !self.text.is_empty();
+        self.fitted = None;
+        self.link_rects.clear();
+        self.hovered_link = None;
+        self.focused_link = self.focused_link.filter(|_| !self.links.is_empty()).map(|i| i.min(self.links.len() - 1));
+        self.rich = None;
+        self.dirty = true;
     }
 
     fn bold(&self) -> bool {
@@ -43,14 +93,77 @@ impl Label {
     fn logical_size(&self) -> f32 {
         match self.kind {
             LabelKind::Title => TITLE_SIZE,
-            LabelKind::Body => BODY_SIZE,
+            LabelKind::Body | LabelKind::Status => BODY_SIZE,
         }
     }
 
     fn color(&self, theme: &SkiaTheme) -> (u8, u8, u8) {
         match self.kind {
             LabelKind::Title => theme.color_title_text,
-            LabelKind::Body => theme.color_body_text,
+            LabelKind::Body | LabelKind::Status => theme.color_body_text,
+        }
+    }
+
+    /// The link under logical (`x`, `y`), if any.
+    fn link_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.link_rects.iter().position(|rects| rects.iter().any(|r| r.contains(x, y)))
+    }
+
+    fn set_hovered_link(&mut self, link: Option<usize>) {
+        if self.hovered_link != link {
+            self.hovered_link = link;
+            self.dirty = true;
+        }
+    }
+
+    fn set_focused_link(&mut self, link: Option<usize>) {
+        if self.focused_link != link {
+            self.focused_link = link;
+            self.dirty = true;
+        }
+    }
+
+    /// Paint the text with its links underlined in the accent colour, the hovered link on a highlight
+    /// and a focus ring around the focused one.
+    fn paint_links(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx, x: f32, y: f32, w: f32) {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let phys_size = self.logical_size() * s;
+        let color = self.color(theme);
+        if !matches!(self.rich, Some((size, width, _)) if size == phys_size && width == w) {
+            let plain = |text| TextSpan { text, color: None, underline: false };
+            let mut spans = Vec::with_capacity(self.links.len() * 2 + 1);
+            let mut at = 0;
+            for link in self.links.iter() {
+                spans.push(plain(&self.text[at..link.range.start]));
+                spans.push(TextSpan {
+                    text: &self.text[link.range.clone()],
+                    color: Some(theme.color_field_border_focused),
+                    underline: true,
+                });
+                at = link.range.end;
+            }
+            spans.push(plain(&self.text[at..]));
+            self.rich = Some((phys_size, w, layout_rich_text(&spans, self.bold(), phys_size, w)));
+        }
+        let Some((_, _, layout)) = self.rich.as_mut() else {
+            return;
+        };
+
+        let link_rects = |layout: &TextLayout, i: usize| -> Vec<Rect> {
+            let rects = layout.range_rects(self.links[i].range.clone());
+            rects.into_iter().map(|r| Rect::new(x + r.x, y + r.y, r.w, r.h)).collect()
+        };
+        if let Some(i) = self.hovered_link {
+            for r in link_rects(layout, i) {
+                fill_rect(pm, r.x, r.y, r.w, r.h, theme.color_selection);
+            }
+        }
+        render_text(pm, layout, color, x, y);
+        if let Some(i) = self.focused_link {
+            for r in link_rects(layout, i) {
+                stroke_rounded_rect(pm, r.x, r.y, r.w, r.h, 2.0 * s, theme.color_field_border_focused, s);
+            }
         }
     }
 }
@@ -61,6 +174,18 @@ impl Component for Label {
     }
 
     fn measure(&mut self, ctx: &LayoutCtx) -> Size {
+        // A status is one line whatever its text, and never widens the window.
+        if self.kind == LabelKind::Status {
+            if !self.reserved {
+                return Size::default();
+            }
+            let line = self.cache.get("…", false, BODY_SIZE, f32::INFINITY);
+            return Size { w: 0.0, h: line.total_height };
+        }
+        // An empty label takes no space, so the layout skips it until an update gives it text.
+        if self.text.is_empty() {
+            return Size::default();
+        }
         // Measure in logical pixels (resolution-independent); paint re-lays-out at physical size.
         // Goes through the shared cache so repeated relayouts (e.g. an unchanged title on every
         // body-text update) reuse the shaped layout instead of re-shaping.
@@ -73,6 +198,16 @@ impl Component for Label {
 
     fn set_bounds(&mut self, b: Rect) {
         self.bounds = b;
+        // The layout pass just measured at this width, so this is a cache hit.
+        if !self.links.is_empty() {
+            let layout = self.cache.get(&self.text, self.bold(), self.logical_size(), b.w);
+            let offset = |r: Rect| Rect::new(b.x + r.x, b.y + r.y, r.w, r.h);
+            self.link_rects = self
+                .links
+                .iter()
+                .map(|link| layout.range_rects(link.range.clone()).into_iter().map(offset).collect())
+                .collect();
+        }
     }
 
     fn bounds(&self) -> Rect {
@@ -95,20 +230,90 @@ impl Component for Label {
         // Clear our own bounds to the background, then render the text wrapped at physical width.
         fill_rect(pm, x, y, w, h, ctx.theme.color_background);
         let color = self.color(ctx.theme);
-        let layout = self.cache.get(&self.text, self.bold(), phys_size, w);
-        render_text(pm, layout, color, x, y);
+        if self.kind == LabelKind::Status {
+            if !matches!(self.fitted, Some((size, width, _)) if size == phys_size && width == w) {
+                self.fitted = Some((phys_size, w, middle_ellipsis(&self.text, false, phys_size, w)));
+            }
+            if let Some((_, _, fitted)) = self.fitted.as_ref() {
+                let layout = self.cache.get(fitted, false, phys_size, f32::INFINITY);
+                render_text(pm, layout, color, x, y);
+            }
+        } else if self.links.is_empty() {
+            let layout = self.cache.get(&self.text, self.bold(), phys_size, w);
+            render_text(pm, layout, color, x, y);
+        } else {
+            self.paint_links(pm, ctx, x, y, w);
+        }
         self.dirty = false;
         Rect::new(x, y, w, h)
     }
 
+    fn focusable(&self) -> bool {
+        !self.links.is_empty()
+    }
+
+    fn set_hovered(&mut self, v: bool) {
+        if !v {
+            self.set_hovered_link(None);
+        }
+    }
+
+    fn set_focused(&mut self, v: bool) {
+        let link = if v { Some(self.focused_link.unwrap_or(0)) } else { None };
+        self.set_focused_link(link.filter(|_| !self.links.is_empty()));
+    }
+
+    fn is_hovered(&self) -> bool {
+        self.hovered_link.is_some()
+    }
+
+    fn handle_key(&mut self, k: &KeyInput) -> bool {
+        let Some(current) = self.focused_link else {
+            return false;
+        };
+        match k.key {
+            // Tab moves between the links, and on to the next component after the last one.
+            Key::Named(NamedKey::Tab) if !k.shift && current + 1 < self.links.len() => {
+                self.set_focused_link(Some(current + 1))
+            }
+            Key::Named(NamedKey::Tab) if k.shift && current > 0 => self.set_focused_link(Some(current - 1)),
+            Key::Named(NamedKey::Enter | NamedKey::Space) => open_link(&self.links[current].href),
+            _ => return false,
+        }
+        true
+    }
+
+    fn pointer_pressed(&mut self, x: f32, y: f32) {
+        if let Some(i) = self.link_at(x, y) {
+            self.set_focused_link(Some(i));
+            open_link(&self.links[i].href);
+        }
+    }
+
+    fn pointer_moved(&mut self, x: f32, y: f32) {
+        self.set_hovered_link(self.link_at(x, y));
+    }
+
+    fn cursor_icon(&self, x: f32, y: f32) -> CursorIcon {
+        if self.link_at(x, y).is_some() {
+            CursorIcon::Pointer
+        } else {
+            CursorIcon::Default
+        }
+    }
+
     fn apply(&mut self, u: &ControllerUpdate) -> bool {
-        if let ControllerUpdate::BodyText(text) = u {
-            if self.kind == LabelKind::Body {
-                self.text = text.to_string();
-                self.dirty = true;
-                return true; // height may have changed → relayout
+        match (*u, self.kind) {
+            (ControllerUpdate::MainInstruction(text), LabelKind::Title) | (ControllerUpdate::BodyText(text), LabelKind::Body) => {
+                self.set_text(text);
+                true // height may have changed → relayout
+            }
+            (ControllerUpdate::StatusText(text), LabelKind::Status) => {
+                let was_reserved = self.reserved;
+                self.set_text(text);
+                !was_reserved && self.reserved // only the first status makes room for its line
             }
+            _ => false,
         }
-        false
     }
 }
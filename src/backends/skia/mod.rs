@@ -1,10 +1,18 @@
 mod background;
 mod button;
+mod checkbox;
+mod checklist;
+mod clipboard;
 mod component;
+mod custom_icon;
 mod desktop;
+mod details;
 mod dialog;
+mod field;
 mod font;
+mod footer_note;
 mod icon;
+mod icon_theme;
 mod icons;
 #[cfg(feature = "skia-instrumentation")]
 mod instrument;
@@ -14,7 +22,9 @@ pub mod pixels;
 #[cfg(not(feature = "skia-instrumentation"))]
 mod pixels;
 mod progress;
+mod radio;
 mod renderer;
+mod svg;
 mod text;
 mod theme;
 
@@ -29,6 +39,7 @@ use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoopBuilder};
 use winit::window::WindowId;
 
 use crate::model::*;
+use crate::ProgressCallbacks;
 
 use super::XDialogBackendImpl;
 
@@ -73,26 +84,38 @@ impl AppState {
             }
             DialogMessageRequest::ShowMessageWindow(id, data, creation) => {
                 let (sender, receiver) = oneshot::channel();
-                let d = dialog::SkiaDialog::new(event_loop, data, &self.theme, false, sender, None);
+                let d = dialog::SkiaDialog::new(event_loop, data, &self.theme, false, None, sender, ProgressCallbacks::default());
                 self.window_to_id.insert(d.window.id(), id);
                 self.dialogs.insert(id, d);
                 let _ = creation.send(Ok(receiver));
             }
-            DialogMessageRequest::ShowProgressWindow(id, data, creation, on_button) => {
+            DialogMessageRequest::ShowInputWindow(id, data, field, creation) => {
                 let (sender, receiver) = oneshot::channel();
-                let d = dialog::SkiaDialog::new(event_loop, data, &self.theme, true, sender, on_button);
+                let d = dialog::SkiaDialog::new(event_loop, data, &self.theme, false, Some(field), sender, ProgressCallbacks::default());
                 self.window_to_id.insert(d.window.id(), id);
                 self.dialogs.insert(id, d);
                 let _ = creation.send(Ok(receiver));
             }
-            DialogMessageRequest::SetProgressIndeterminate(id) => {
+            DialogMessageRequest::ShowProgressWindow(id, data, creation, callbacks) => {
+                let (sender, receiver) = oneshot::channel();
+                let d = dialog::SkiaDialog::new(event_loop, data, &self.theme, true, None, sender, callbacks);
+                self.window_to_id.insert(d.window.id(), id);
+                self.dialogs.insert(id, d);
+                let _ = creation.send(Ok(receiver));
+            }
+            DialogMessageRequest::UpdateDialog(id, patch) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.apply_patch(&patch);
+                }
+            }
+            DialogMessageRequest::SetProgressIndeterminate(id, bar) => {
                 if let Some(d) = self.dialogs.get_mut(&id) {
-                    d.set_progress_indeterminate();
+                    d.set_progress_indeterminate(bar);
                 }
             }
-            DialogMessageRequest::SetProgressValue(id, value) => {
+            DialogMessageRequest::SetProgressValue(id, bar, value) => {
                 if let Some(d) = self.dialogs.get_mut(&id) {
-                    d.set_progress_value(value);
+                    d.set_progress_value(bar, value);
                 }
             }
             DialogMessageRequest::SetProgressText(id, text) => {
@@ -100,6 +123,31 @@ impl AppState {
                     d.set_body_text(&text);
                 }
             }
+            DialogMessageRequest::SetProgressStatus(id, text) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.set_status_text(&text);
+                }
+            }
+            DialogMessageRequest::SetProgressState(id, bar, state) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.set_progress_state(bar, state);
+                }
+            }
+            DialogMessageRequest::SetProgressCaption(id, bar, caption) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.set_progress_caption(bar, &caption);
+                }
+            }
+            DialogMessageRequest::SetChecklistStep(id, index, state) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.set_checklist_step(index, state);
+                }
+            }
+            DialogMessageRequest::StartChecklistStep(id, index) => {
+                if let Some(d) = self.dialogs.get_mut(&id) {
+                    d.start_checklist_step(index);
+                }
+            }
         }
     }
 
@@ -224,10 +272,12 @@ impl ApplicationHandler<DialogMessageRequest> for AppState {
 
         match event {
             WindowEvent::CloseRequested => {
-                dialog.handle_close_requested();
-                let wid = dialog.window.id();
-                self.dialogs.remove(&dialog_id);
-                self.window_to_id.remove(&wid);
+                let keep_open = dialog.handle_close_requested(dialog_id);
+                if !keep_open {
+                    let wid = dialog.window.id();
+                    self.dialogs.remove(&dialog_id);
+                    self.window_to_id.remove(&wid);
+                }
             }
             WindowEvent::RedrawRequested => {
                 dialog.render_and_present();
@@ -265,7 +315,7 @@ impl ApplicationHandler<DialogMessageRequest> for AppState {
             WindowEvent::KeyboardInput { event, is_synthetic, .. }
                 if event.state == ElementState::Pressed && !is_synthetic =>
             {
-                match dialog.handle_key_pressed(&event.logical_key) {
+                match dialog.handle_key_pressed(&event) {
                     KeyAction::ActivateButton(index) => {
                         if !event.repeat {
                             let keep_open = dialog.on_button_clicked(dialog_id, index);
@@ -278,8 +328,7 @@ impl ApplicationHandler<DialogMessageRequest> for AppState {
                         }
                     }
                     KeyAction::Close => {
-                        if !event.repeat {
-                            dialog.handle_close_requested();
+                        if !event.repeat && !dialog.handle_close_requested(dialog_id) {
                             let wid = dialog.window.id();
                             self.dialogs.remove(&dialog_id);
                             self.window_to_id.remove(&wid);
@@ -303,7 +352,10 @@ impl SkiaBackend {
                 DialogMessageRequest::ShowMessageWindow(_id, _options, creation) => {
                     let _ = creation.send(Err(crate::XDialogError::NoBackendAvailable));
                 }
-                DialogMessageRequest::ShowProgressWindow(_id, _options, creation, _on_button) => {
+                DialogMessageRequest::ShowInputWindow(_id, _options, _field, creation) => {
+                    let _ = creation.send(Err(crate::XDialogError::NoBackendAvailable));
+                }
+                DialogMessageRequest::ShowProgressWindow(_id, _options, creation, _callbacks) => {
                     let _ = creation.send(Err(crate::XDialogError::NoBackendAvailable));
                 }
                 _ => {}
@@ -348,6 +400,8 @@ impl XDialogBackendImpl for SkiaBackend {
         // Resolve the desktop appearance (light/dark + accent) once at startup; any failure
         // falls back to the hard-coded Ubuntu light theme.
         let appearance = desktop::resolve_appearance(xdialog_theme);
+        // Likewise read the icon theme, for the standard icons; without one they're drawn by hand.
+        icon_theme::init();
         let mut state = AppState::new(theme::get_theme(&appearance));
 
         // Start process CPU/RSS sampling for the duration of the run; `report()` stops it.
@@ -4,9 +4,12 @@ use mina::prelude::*;
 use tiny_skia::PixmapMut;
 
 use super::component::{
-    Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size, PROGRESS_HEIGHT,
+    Component, ControllerUpdate, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE, PROGRESS_HEIGHT,
 };
 use super::renderer::{fill_rect, fill_rounded_rect};
+use super::text::{middle_ellipsis, render_text, CachedLayout};
+use super::theme::blend;
+use crate::ProgressBarState;
 
 #[derive(Animate, Clone, Debug, Default, PartialEq)]
 pub struct ProgressState {
@@ -22,6 +25,27 @@ struct PillPos {
     pos: f32,
 }
 
+/// Progress of the colour transition after a state change: 0 = the previous state's colours, 1 = the
+/// new state's.
+#[derive(Animate, Clone, Debug, Default, PartialEq)]
+struct ColorMix {
+    t: f32,
+}
+
+/// State colour transition length, seconds. Keep in sync with `STATE_TIMELINE`.
+const STATE_TRANSITION: f32 = 0.25;
+
+static STATE_TIMELINE: LazyLock<ColorMixTimeline> = LazyLock::new(|| {
+    timeline!(
+        ColorMix 0.25s Easing::OutCubic
+        from { t: 0.0 }
+        to   { t: 1.0 }
+    )
+});
+
+/// Space between a caption and its bar, in logical pixels.
+const CAPTION_GAP: f32 = 4.0;
+
 /// Indeterminate cycle length, seconds. Keep in sync with the `timeline!` duration below.
 const INDETERMINATE_CYCLE: f32 = 3.0;
 /// Constant capsule length during travel, as a fraction of the track beyond the circle diameter.
@@ -42,25 +66,46 @@ static INDETERMINATE_TIMELINE: LazyLock<PillPosTimeline> = LazyLock::new(|| {
     )
 });
 
+/// One progress bar, with an optional single-line caption above it. A dialog stacks one per entry
+/// in `XDialogOptions::progress_bars`, each reacting only to the updates addressed to its `index`.
 pub struct SkiaProgressBar {
+    index: usize,
+    caption: String,
+    /// The caption as shortened to fit, keyed on the physical size and width it fits.
+    fitted: Option<(f32, f32, String)>,
+    cache: CachedLayout,
     pub state: ProgressState,
     pub is_indeterminate: bool,
     current_time: f32,
     /// Indeterminate capsule position; only meaningful while `is_indeterminate`.
     pill: PillPos,
     value_animator: Option<ProgressStateTimeline>,
+    /// The state shown (or being transitioned to), and the one transitioned from.
+    bar_state: ProgressBarState,
+    previous_state: ProgressBarState,
+    color_mix: ColorMix,
+    /// Time into the colour transition; `None` once it has finished.
+    state_time: Option<f32>,
     bounds: Rect,
     dirty: bool,
 }
 
 impl SkiaProgressBar {
-    pub fn new() -> Self {
+    pub fn new(index: usize, caption: &str) -> Self {
         Self {
+            index,
+            caption: caption.to_string(),
+            fitted: None,
+            cache: CachedLayout::default(),
             state: ProgressState::default(),
             is_indeterminate: false,
             current_time: 0.0,
             pill: PillPos::default(),
             value_animator: None,
+            bar_state: ProgressBarState::Normal,
+            previous_state: ProgressBarState::Normal,
+            color_mix: ColorMix { t: 1.0 },
+            state_time: None,
             bounds: Rect::default(),
             dirty: true,
         }
@@ -84,8 +129,68 @@ impl SkiaProgressBar {
         self.pill = PillPos::default();
     }
 
-    /// Advance the timeline by `elapsed_secs`; returns whether the visible state changed.
+    /// Replace the caption; returns whether that shows or hides it, changing the bar's height.
+    fn set_caption(&mut self, caption: &str) -> bool {
+        let relayout = self.caption.is_empty() != caption.is_empty();
+        self.caption = caption.to_string();
+        self.fitted = None;
+        self.dirty = true;
+        relayout
+    }
+
+    /// The caption's height, plus the gap below it, in logical pixels. 0 without a caption.
+    fn caption_height(&mut self) -> f32 {
+        if self.caption.is_empty() {
+            return 0.0;
+        }
+        self.cache.get("…", false, BODY_SIZE, f32::INFINITY).total_height + CAPTION_GAP
+    }
+
+    /// Fade from the current state's colours to `state`'s. The value is kept.
+    fn set_state(&mut self, state: ProgressBarState) {
+        if state == self.bar_state {
+            return;
+        }
+        self.previous_state = self.bar_state;
+        self.bar_state = state;
+        self.color_mix = ColorMix::default();
+        self.state_time = Some(0.0);
+        self.dirty = true;
+    }
+
+    /// The (track, bar) colours to paint, mid-transition between two states.
+    fn colors(&self, ctx: &PaintCtx) -> ((u8, u8, u8), (u8, u8, u8)) {
+        let (from_track, from_bar) = ctx.theme.progress_colors(self.previous_state);
+        let (to_track, to_bar) = ctx.theme.progress_colors(self.bar_state);
+        let t = self.color_mix.t.clamp(0.0, 1.0);
+        (blend(from_track, to_track, t), blend(from_bar, to_bar, t))
+    }
+
+    /// Advance the timelines by `elapsed_secs`; returns whether the visible state changed.
     fn advance(&mut self, elapsed_secs: f32) -> bool {
+        let colors_changed = self.advance_state(elapsed_secs);
+        let bar_changed = self.advance_bar(elapsed_secs);
+        colors_changed || bar_changed
+    }
+
+    /// Advance the colour transition, if any.
+    fn advance_state(&mut self, elapsed_secs: f32) -> bool {
+        let Some(time) = self.state_time.as_mut() else {
+            return false;
+        };
+        // Advance first, then sample, so the last tick lands exactly on the new colours.
+        *time += elapsed_secs;
+        let before = self.color_mix.t;
+        STATE_TIMELINE.update(&mut self.color_mix, *time);
+        if *time >= STATE_TRANSITION {
+            self.color_mix.t = 1.0;
+            self.state_time = None;
+        }
+        self.color_mix.t != before
+    }
+
+    /// Advance the value animation or the indeterminate capsule.
+    fn advance_bar(&mut self, elapsed_secs: f32) -> bool {
         if self.is_indeterminate {
             let before = self.pill.pos;
             INDETERMINATE_TIMELINE.update(&mut self.pill, self.current_time);
@@ -117,10 +222,11 @@ impl Component for SkiaProgressBar {
     }
 
     fn measure(&mut self, _ctx: &LayoutCtx) -> Size {
-        // No intrinsic width (it stretches to the content column); fixed logical height.
+        // No intrinsic width (it stretches to the content column, and a long caption is shortened
+        // to fit); fixed logical height.
         Size {
             w: 0.0,
-            h: PROGRESS_HEIGHT,
+            h: self.caption_height() + PROGRESS_HEIGHT,
         }
     }
 
@@ -146,12 +252,26 @@ impl Component for SkiaProgressBar {
         );
         // Clear our own bounds to the background.
         fill_rect(pm, x, y, w, h, ctx.theme.color_background);
+        let (track, bar) = self.colors(ctx);
+
+        if !self.caption.is_empty() {
+            let size = BODY_SIZE * s;
+            if !matches!(self.fitted, Some((fs, fw, _)) if fs == size && fw == w) {
+                self.fitted = Some((size, w, middle_ellipsis(&self.caption, false, size, w)));
+            }
+            if let Some((_, _, fitted)) = self.fitted.as_ref() {
+                let layout = self.cache.get(fitted, false, size, f32::INFINITY);
+                render_text(pm, layout, ctx.theme.color_body_text, x, y);
+            }
+        }
+        // The bar fills the bottom of the bounds, under the caption.
+        let (y, h) = (y + h - PROGRESS_HEIGHT * s, PROGRESS_HEIGHT * s);
 
         if self.is_indeterminate {
             // Fully-rounded "pill" track and a stretchy capsule. A circle has diameter == bar
             // height; passing radius = h/2 yields a circle when width == h and a stadium when wider.
             let r = h / 2.0;
-            fill_rounded_rect(pm, x, y, w, h, r, ctx.theme.color_progress_background);
+            fill_rounded_rect(pm, x, y, w, h, r, track);
 
             let pos = self.pill.pos.clamp(0.0, 1.0);
             let d = h; // circle diameter (== bar height)
@@ -163,11 +283,11 @@ impl Component for SkiaProgressBar {
             let cx = (d - len / 2.0) + pos * (w - 2.0 * d + len);
             let left = (cx - len / 2.0).max(0.0);
             let right = (cx + len / 2.0).min(w);
-            fill_rounded_rect(pm, x + left, y, right - left, h, r, ctx.theme.color_progress_foreground);
+            fill_rounded_rect(pm, x + left, y, right - left, h, r, bar);
         } else {
             let radius = 2.0 * s;
             // Background track.
-            fill_rounded_rect(pm, x, y, w, h, radius, ctx.theme.color_progress_background);
+            fill_rounded_rect(pm, x, y, w, h, radius, track);
 
             // Foreground bar. Clamp to [0, 1] as a render-layer guard so the bar can never draw past
             // the track regardless of how the state was set (the public API also clamps the input).
@@ -182,7 +302,7 @@ impl Component for SkiaProgressBar {
                     bar_w,
                     h,
                     radius,
-                    ctx.theme.color_progress_foreground,
+                    bar,
                 );
             }
         }
@@ -200,21 +320,67 @@ impl Component for SkiaProgressBar {
     }
 
     fn is_animating(&self) -> bool {
-        self.is_indeterminate || self.value_animator.is_some()
+        self.is_indeterminate || self.value_animator.is_some() || self.state_time.is_some()
     }
 
     fn apply(&mut self, u: &ControllerUpdate) -> bool {
-        match u {
-            ControllerUpdate::ProgressValue(v) => {
-                self.set_value(*v);
+        match *u {
+            ControllerUpdate::ProgressValue(index, v) if index == self.index => {
+                self.set_value(v);
                 self.dirty = true;
             }
-            ControllerUpdate::ProgressIndeterminate => {
+            ControllerUpdate::ProgressIndeterminate(index) if index == self.index => {
                 self.set_indeterminate();
                 self.dirty = true;
             }
-            ControllerUpdate::BodyText(_) => {}
+            ControllerUpdate::ProgressState(index, state) if index == self.index => self.set_state(state),
+            ControllerUpdate::ProgressCaption(index, caption) if index == self.index => return self.set_caption(caption),
+            _ => {}
         }
-        false // progress changes never alter layout
+        false // only showing or hiding the caption alters layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_change_fades_colours_and_keeps_the_value() {
+        let mut bar = SkiaProgressBar::new(0, "");
+        bar.apply(&ControllerUpdate::ProgressValue(0, 0.6));
+        while bar.is_animating() {
+            bar.tick(0.1);
+        }
+
+        bar.apply(&ControllerUpdate::ProgressState(0, ProgressBarState::Error));
+        assert!(bar.is_animating());
+        assert!(bar.tick(0.1));
+        assert!(bar.color_mix.t > 0.0 && bar.color_mix.t < 1.0);
+        bar.tick(0.2);
+        assert!(!bar.is_animating());
+        assert_eq!(bar.color_mix.t, 1.0);
+        assert_eq!(bar.state.x2, 0.6);
+
+        // Setting the same state again doesn't restart the fade.
+        bar.apply(&ControllerUpdate::ProgressState(0, ProgressBarState::Error));
+        assert!(!bar.is_animating());
+    }
+    #[test]
+    fn bars_only_take_updates_addressed_to_them() {
+        let mut current = SkiaProgressBar::new(0, "Current file");
+        let mut overall = SkiaProgressBar::new(1, "");
+        for bar in [&mut current, &mut overall] {
+            bar.apply(&ControllerUpdate::ProgressValue(1, 0.5));
+            bar.apply(&ControllerUpdate::ProgressIndeterminate(0));
+        }
+        assert!(current.is_indeterminate && current.value_animator.is_none());
+        assert!(!overall.is_indeterminate && overall.value_animator.is_some());
+
+        // Only showing or hiding a caption changes the bar's height.
+        assert!(overall.apply(&ControllerUpdate::ProgressCaption(1, "Overall")));
+        assert!(!overall.apply(&ControllerUpdate::ProgressCaption(1, "Overall (2 of 3)")));
+        assert!(!current.apply(&ControllerUpdate::ProgressCaption(1, "")));
+        assert!(current.apply(&ControllerUpdate::ProgressCaption(0, "")));
     }
 }
@@ -0,0 +1,215 @@
+//! A group of mutually exclusive choices, stacked in the content column above the buttons.
+//!
+//! The group is a single Tab stop: Tab moves focus into the group (onto the selected option) and
+//! out again, while the arrow keys move the selection within it, as in native radio groups.
+
+use tiny_skia::PixmapMut;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
+
+use crate::model::XDialogResponse;
+
+use super::component::{Component, KeyInput, LayoutCtx, PaintCtx, Rect, Role, Size, BODY_SIZE};
+use super::renderer::{fill_circle, fill_rect, stroke_circle};
+use super::text::{measure_text_width, render_text_clipped, CachedLayout};
+
+/// Diameter of each radio circle in logical pixels.
+const CIRCLE_SIZE: f32 = 16.0;
+/// Gap between a circle and its label in logical pixels.
+const LABEL_GAP: f32 = 8.0;
+/// Height of each option row in logical pixels.
+const ROW_HEIGHT: f32 = 24.0;
+/// Inset of the circles from the left edge, leaving room for the focus ring inside the bounds.
+const RING_INSET: f32 = 3.0;
+
+pub struct RadioGroup {
+    options: Vec<String>,
+    selected: usize,
+    bounds: Rect,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    dirty: bool,
+    caches: Vec<CachedLayout>,
+}
+
+impl RadioGroup {
+    pub fn new(options: &[String], selected: usize) -> Self {
+        Self {
+            options: options.to_vec(),
+            selected: selected.min(options.len().saturating_sub(1)),
+            bounds: Rect::default(),
+            hovered: false,
+            pressed: false,
+            focused: false,
+            dirty: true,
+            caches: options.iter().map(|_| CachedLayout::default()).collect(),
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        if index < self.options.len() && index != self.selected {
+            self.selected = index;
+            self.dirty = true;
+        }
+    }
+
+    /// The option row under logical `y`, if any.
+    fn row_at(&self, y: f32) -> Option<usize> {
+        let row = ((y - self.bounds.y) / ROW_HEIGHT).floor();
+        (row >= 0.0 && (row as usize) < self.options.len()).then_some(row as usize)
+    }
+}
+
+impl Component for RadioGroup {
+    fn role(&self) -> Role {
+        Role::Content
+    }
+
+    fn measure(&mut self, _ctx: &LayoutCtx) -> Size {
+        let label_w = self.options.iter().map(|o| measure_text_width(o, false, BODY_SIZE)).fold(0.0, f32::max);
+        Size {
+            w: RING_INSET + CIRCLE_SIZE + LABEL_GAP + label_w,
+            h: ROW_HEIGHT * self.options.len() as f32,
+        }
+    }
+
+    fn set_bounds(&mut self, b: Rect) {
+        self.bounds = b;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn paint(&mut self, pm: &mut PixmapMut, ctx: &PaintCtx) -> Rect {
+        let s = ctx.scale;
+        let theme = ctx.theme;
+        let (x, y, w, h) = (self.bounds.x * s, self.bounds.y * s, self.bounds.w * s, self.bounds.h * s);
+        fill_rect(pm, x, y, w, h, theme.color_background);
+
+        let radius = CIRCLE_SIZE * s / 2.0;
+        let cx = x + (RING_INSET + CIRCLE_SIZE / 2.0) * s;
+        let text_x = x + (RING_INSET + CIRCLE_SIZE + LABEL_GAP) * s;
+        for (i, option) in self.options.iter().enumerate() {
+            let row_y = y + ROW_HEIGHT * s * i as f32;
+            let cy = row_y + ROW_HEIGHT * s / 2.0;
+
+            // Selected: accent ring with a dot. Otherwise a plain outline, accent while hovered.
+            fill_circle(pm, cx, cy, radius, theme.color_field_background);
+            if i == self.selected {
+                stroke_circle(pm, cx, cy, radius - s, theme.color_field_border_focused, 2.0 * s);
+                fill_circle(pm, cx, cy, radius * 0.45, theme.color_field_border_focused);
+                if self.focused {
+                    stroke_circle(pm, cx, cy, radius + 2.0 * s, theme.color_field_border_focused, s);
+                }
+            } else {
+                let border = if self.hovered { theme.color_field_border_focused } else { theme.color_field_border };
+                stroke_circle(pm, cx, cy, radius - s / 2.0, border, s);
+            }
+
+            // As with the checkbox, a label is clipped to its row if the layout couldn't give it its full width.
+            let layout = self.caches[i].get(option, false, BODY_SIZE * s, f32::INFINITY);
+            let clip = Rect::new(text_x, row_y, (x + w - text_x).max(0.0), ROW_HEIGHT * s);
+            render_text_clipped(pm, layout, theme.color_body_text, text_x, cy - layout.total_height / 2.0, clip);
+        }
+
+        self.dirty = false;
+        Rect::new(x, y, w, h)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_hovered(&mut self, v: bool) {
+        if self.hovered != v {
+            self.hovered = v;
+            self.dirty = true;
+        }
+    }
+
+    fn set_pressed(&mut self, v: bool) {
+        self.pressed = v;
+    }
+
+    fn set_focused(&mut self, v: bool) {
+        if self.focused != v {
+            self.focused = v;
+            self.dirty = true;
+        }
+    }
+
+    fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    fn handle_key(&mut self, k: &KeyInput) -> bool {
+        // Arrows move the selection and wrap around; Tab, Enter and Escape go to the dialog.
+        let n = self.options.len();
+        match k.key {
+            Key::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => self.select((self.selected + n - 1) % n),
+            Key::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => self.select((self.selected + 1) % n),
+            Key::Named(NamedKey::Home) => self.select(0),
+            Key::Named(NamedKey::End) => self.select(n - 1),
+            Key::Named(NamedKey::Space) => {}
+            _ => return false,
+        }
+        true
+    }
+
+    fn pointer_pressed(&mut self, _x: f32, y: f32) {
+        if let Some(row) = self.row_at(y) {
+            self.select(row);
+        }
+    }
+
+    fn cursor_icon(&self, _x: f32, _y: f32) -> CursorIcon {
+        CursorIcon::Pointer
+    }
+
+    fn collect(&self, response: &mut XDialogResponse) {
+        response.radio_index = Some(self.selected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: NamedKey) -> bool {
+        let mut group = RadioGroup::new(&["a".to_string(), "b".to_string(), "c".to_string()], 0);
+        let key = Key::Named(key);
+        group.handle_key(&KeyInput { key: &key, text: None, ctrl: false, shift: false });
+        group.selected == 2
+    }
+
+    #[test]
+    fn arrows_wrap_and_tab_is_not_consumed() {
+        assert!(key(NamedKey::ArrowUp));
+        assert!(key(NamedKey::End));
+        assert!(!key(NamedKey::ArrowDown));
+
+        let mut group = RadioGroup::new(&["a".to_string(), "b".to_string()], 5);
+        assert_eq!(group.selected, 1);
+        let tab = Key::Named(NamedKey::Tab);
+        assert!(!group.handle_key(&KeyInput { key: &tab, text: None, ctrl: false, shift: false }));
+    }
+
+    #[test]
+    fn row_at_maps_rows() {
+        let mut group = RadioGroup::new(&["a".to_string(), "b".to_string()], 0);
+        group.set_bounds(Rect::new(0.0, 100.0, 200.0, ROW_HEIGHT * 2.0));
+        assert_eq!(group.row_at(99.0), None);
+        assert_eq!(group.row_at(100.0 + ROW_HEIGHT * 1.5), Some(1));
+        assert_eq!(group.row_at(100.0 + ROW_HEIGHT * 2.0), None);
+    }
+}
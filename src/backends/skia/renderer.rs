@@ -106,6 +106,87 @@ pub fn fill_circle(
     }
 }
 
+/// Fill an arbitrary path.
+pub fn fill_path(pixmap: &mut PixmapMut, path: &tiny_skia::Path, color: (u8, u8, u8)) {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgba8(color.0, color.1, color.2, 255));
+    paint.anti_alias = true;
+
+    pixmap.fill_path(path, &paint, FillRule::Winding, Transform::identity(), None);
+}
+
+/// Stroke an arbitrary path with round caps and joins.
+pub fn stroke_path(pixmap: &mut PixmapMut, path: &tiny_skia::Path, color: (u8, u8, u8), width: f32) {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgba8(color.0, color.1, color.2, 255));
+    paint.anti_alias = true;
+
+    let stroke = Stroke {
+        width,
+        line_cap: tiny_skia::LineCap::Round,
+        line_join: tiny_skia::LineJoin::Round,
+        ..Stroke::default()
+    };
+
+    pixmap.stroke_path(path, &paint, &stroke, Transform::identity(), None);
+}
+
+/// Stroke a circle outline.
+pub fn stroke_circle(
+    pixmap: &mut PixmapMut,
+    cx: f32,
+    cy: f32,
+    radius: f32,
+    color: (u8, u8, u8),
+    width: f32,
+) {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgba8(color.0, color.1, color.2, 255));
+    paint.anti_alias = true;
+
+    let stroke = Stroke {
+        width,
+        ..Stroke::default()
+    };
+
+    if let Some(path) = PathBuilder::from_circle(cx, cy, radius) {
+        pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+}
+
+/// Stroke an eye outline — an upper and a lower arc meeting at the corners — `w`×`h` in size and
+/// centred on (`cx`, `cy`).
+pub fn stroke_eye(
+    pixmap: &mut PixmapMut,
+    cx: f32,
+    cy: f32,
+    w: f32,
+    h: f32,
+    color: (u8, u8, u8),
+    width: f32,
+) {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgba8(color.0, color.1, color.2, 255));
+    paint.anti_alias = true;
+
+    let stroke = Stroke {
+        width,
+        ..Stroke::default()
+    };
+
+    // A quadratic's peak is half its control-point offset, so push the controls out by `h`.
+    let (x0, x1) = (cx - w / 2.0, cx + w / 2.0);
+    let mut pb = PathBuilder::new();
+    pb.move_to(x0, cy);
+    pb.quad_to(cx, cy - h, x1, cy);
+    pb.quad_to(cx, cy + h, x0, cy);
+    pb.close();
+
+    if let Some(path) = pb.finish() {
+        pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+}
+
 /// Stroke a line.
 pub fn stroke_line(
     pixmap: &mut PixmapMut,
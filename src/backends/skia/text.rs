@@ -6,11 +6,15 @@
 //! rasterization (swash) come for free. Callers select the face with a `bold` flag rather than
 //! passing a font handle.
 
+use std::ops::Range;
 use std::sync::{LazyLock, Mutex};
 
-use cosmic_text::{Attrs, Buffer, Color, Family, FontSystem, Metrics, Shaping, SwashCache, Weight};
+use cosmic_text::{Attrs, Buffer, Color, Family, FontSystem, Metrics, Shaping, SwashCache, UnderlineStyle, Weight};
 use tiny_skia::PixmapMut;
+use unicode_segmentation::UnicodeSegmentation;
+use zeroize::Zeroize;
 
+use super::component::Rect;
 use super::font::{FONT_BOLD_DATA, FONT_REGULAR_DATA, UI_FONT_FAMILY};
 
 /// Line height as a multiple of the font size (close to the previous fontdue line spacing).
@@ -49,12 +53,42 @@ pub struct TextLayout {
     buffer: Buffer,
     pub total_width: f32,
     pub total_height: f32,
-    #[allow(dead_code)]
     pub line_height: f32,
 }
 
+/// A run of text with its own colour and decoration, for [`layout_rich_text`].
+pub struct TextSpan<'a> {
+    pub text: &'a str,
+    /// The span's colour, or `None` for the colour passed to [`render_text`].
+    pub color: Option<(u8, u8, u8)>,
+    pub underline: bool,
+}
+
 /// Lay out `text` at `size`, wrapping at `max_width` (pass `f32::INFINITY` for no wrapping).
 pub fn layout_text(text: &str, bold: bool, size: f32, max_width: f32) -> TextLayout {
+    shape(size, max_width, |buffer| buffer.set_text(text, &attrs(bold), Shaping::Advanced, None))
+}
+
+/// Like [`layout_text`], for text made of differently styled spans. Colour and decoration don't
+/// affect shaping, so the result has the same geometry as [`layout_text`] on the joined text.
+pub fn layout_rich_text(spans: &[TextSpan], bold: bool, size: f32, max_width: f32) -> TextLayout {
+    shape(size, max_width, |buffer| {
+        let spans = spans.iter().map(|span| {
+            let mut span_attrs = attrs(bold);
+            if let Some((r, g, b)) = span.color {
+                span_attrs = span_attrs.color(Color::rgb(r, g, b));
+            }
+            if span.underline {
+                span_attrs = span_attrs.underline(UnderlineStyle::Single);
+            }
+            (span.text, span_attrs)
+        });
+        buffer.set_rich_text(spans, &attrs(bold), Shaping::Advanced, None);
+    })
+}
+
+/// Shape the text that `set_text` puts into a fresh buffer at `size`, wrapping at `max_width`.
+fn shape(size: f32, max_width: f32, set_text: impl FnOnce(&mut Buffer)) -> TextLayout {
     let mut ctx = FONT_CONTEXT.lock().unwrap();
     let ctx = &mut *ctx;
 
@@ -64,7 +98,7 @@ pub fn layout_text(text: &str, bold: bool, size: f32, max_width: f32) -> TextLay
     let mut buffer = Buffer::new(&mut ctx.font_system, metrics);
     let width_opt = if max_width.is_finite() { Some(max_width) } else { None };
     buffer.set_size(width_opt, None);
-    buffer.set_text(text, &attrs(bold), Shaping::Advanced, None);
+    set_text(&mut buffer);
     buffer.shape_until_scroll(&mut ctx.font_system, false);
 
     let mut total_width: f32 = 0.0;
@@ -84,11 +118,101 @@ pub fn layout_text(text: &str, bold: bool, size: f32, max_width: f32) -> TextLay
     }
 }
 
+impl TextLayout {
+    /// The x offset of a caret placed before byte `index` of a single-line layout. Indices inside a
+    /// multi-character glyph (a ligature) are interpolated across the glyph's advance.
+    pub fn caret_x(&self, index: usize) -> f32 {
+        let Some(run) = self.buffer.layout_runs().next() else {
+            return 0.0;
+        };
+        for glyph in run.glyphs {
+            if index <= glyph.start {
+                return glyph.x;
+            }
+            if index < glyph.end {
+                let t = (index - glyph.start) as f32 / (glyph.end - glyph.start) as f32;
+                return glyph.x + glyph.w * t;
+            }
+        }
+        run.line_w
+    }
+
+    /// The rectangles (one per visual line, relative to the layout's top-left) covering the glyphs
+    /// of byte `range` of the laid-out text.
+    pub fn range_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        // Glyph indices are relative to their own buffer line; find where each line starts.
+        let mut line_starts = Vec::with_capacity(self.buffer.lines.len());
+        let mut offset = 0;
+        for line in self.buffer.lines.iter() {
+            line_starts.push(offset);
+            offset += line.text().len() + line.ending().as_str().len();
+        }
+
+        let mut rects = Vec::new();
+        for run in self.buffer.layout_runs() {
+            let base = line_starts[run.line_i];
+            let (mut x0, mut x1) = (f32::INFINITY, f32::NEG_INFINITY);
+            for glyph in run.glyphs.iter().filter(|g| base + g.start < range.end && base + g.end > range.start) {
+                x0 = x0.min(glyph.x);
+                x1 = x1.max(glyph.x + glyph.w);
+            }
+            if x0 < x1 {
+                rects.push(Rect::new(x0, run.line_top, x1 - x0, run.line_height));
+            }
+        }
+        rects
+    }
+
+    /// The byte index of the caret position closest to `x` on a single-line layout: the start of
+    /// the first glyph whose horizontal midpoint lies right of `x`, or the end of the text.
+    pub fn hit_test(&self, x: f32) -> usize {
+        let Some(run) = self.buffer.layout_runs().next() else {
+            return 0;
+        };
+        for glyph in run.glyphs {
+            if x < glyph.x + glyph.w / 2.0 {
+                return glyph.start;
+            }
+        }
+        run.glyphs.last().map_or(0, |g| g.end)
+    }
+}
+
 /// Measure the width of a single (unwrapped) line of text.
 pub fn measure_text_width(text: &str, bold: bool, size: f32) -> f32 {
     layout_text(text, bold, size, f32::INFINITY).total_width
 }
 
+/// Shorten `text` to fit on one line of `max_width` by replacing its middle with "…", keeping the
+/// start and end, which matter most in paths (eg. "/home/user/…/archive.tar.gz"). Returns the text
+/// unchanged when it already fits. Cuts between graphemes, so accents and emoji sequences next to
+/// the "…" stay whole.
+pub fn middle_ellipsis(text: &str, bold: bool, size: f32, max_width: f32) -> String {
+    if text.is_empty() || measure_text_width(text, bold, size) <= max_width {
+        return text.to_string();
+    }
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    let shorten = |keep: usize| {
+        let head = keep.div_ceil(2);
+        let tail = keep / 2;
+        let mut s = graphemes[..head].concat();
+        s.push('…');
+        s.push_str(&graphemes[graphemes.len() - tail..].concat());
+        s
+    };
+    // Binary search the most graphemes that fit around the ellipsis.
+    let (mut lo, mut hi) = (0, graphemes.len() - 1);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if measure_text_width(&shorten(mid), bold, size) <= max_width {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    shorten(lo)
+}
+
 /// A single memoized shape: the inputs it was shaped from plus the resulting [`TextLayout`].
 struct CacheEntry {
     text: String,
@@ -98,6 +222,14 @@ struct CacheEntry {
     layout: TextLayout,
 }
 
+impl Drop for CacheEntry {
+    // A revealed password field caches its plaintext, so wipe it however the entry goes (evicted or
+    // cleared) rather than freeing it as is.
+    fn drop(&mut self) {
+        self.text.zeroize();
+    }
+}
+
 /// Number of shaped layouts kept per component. A label is shaped at up to three distinct keys per
 /// frame — the natural-width measure (`max_width == ∞`), the wrapped-width measure, and the
 /// physical-size paint — so the cache must hold all three at once for a relayout to be free.
@@ -120,6 +252,11 @@ pub struct CachedLayout {
 }
 
 impl CachedLayout {
+    /// Drop every cached layout (eg. shaped plaintext that must not outlive its display).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
     /// Return the shaped layout for these inputs, reshaping only on a cache miss. Keys on the exact
     /// `(size, max_width)` values; during an animation, or across relayouts with unchanged text,
     /// the caller passes bit-identical values so this hits.
@@ -242,6 +379,20 @@ pub fn render_text(
     color: (u8, u8, u8),
     x: f32,
     y: f32,
+) {
+    let clip = Rect::new(0.0, 0.0, pixmap.width() as f32, pixmap.height() as f32);
+    render_text_clipped(pixmap, layout, color, x, y, clip);
+}
+
+/// Like [`render_text`], but only touches pixels inside `clip` (physical pixels). Used by
+/// components that scroll text within a fixed box, such as the text field.
+pub fn render_text_clipped(
+    pixmap: &mut PixmapMut,
+    layout: &mut TextLayout,
+    color: (u8, u8, u8),
+    x: f32,
+    y: f32,
+    clip: Rect,
 ) {
     let mut ctx = FONT_CONTEXT.lock().unwrap();
     let ctx = &mut *ctx;
@@ -249,9 +400,12 @@ pub fn render_text(
     let (cr, cg, cb) = color;
     let text_color = Color::rgb(cr, cg, cb);
 
-    let pm_w = pixmap.width() as i32;
-    let pm_h = pixmap.height() as i32;
-    let pm_w_us = pm_w as usize;
+    let pm_w_us = pixmap.width() as usize;
+    // Clip bounds, rounded outward and clamped to the pixmap.
+    let clip_x0 = (clip.x.floor() as i32).max(0);
+    let clip_y0 = (clip.y.floor() as i32).max(0);
+    let clip_x1 = ((clip.x + clip.w).ceil() as i32).min(pixmap.width() as i32);
+    let clip_y1 = ((clip.y + clip.h).ceil() as i32).min(pixmap.height() as i32);
     let ox = x.round() as i32;
     let oy = y.round() as i32;
     let gamma = Gamma::get();
@@ -271,8 +425,8 @@ pub fn render_text(
             let goy = oy + gy;
             let (gw, gh) = (w as i32, h as i32);
 
-            if gox >= 0 && goy >= 0 && gox + gw <= pm_w && goy + gh <= pm_h {
-                // Fast path: the whole glyph cell is inside the pixmap (the common case for dialog
+            if gox >= clip_x0 && goy >= clip_y0 && gox + gw <= clip_x1 && goy + gh <= clip_y1 {
+                // Fast path: the whole glyph cell is inside the clip (the common case for dialog
                 // text). Skip the per-pixel bounds test and advance the row index by a full stride
                 // instead of recomputing `y * width + x` for every texel.
                 for dy in 0..gh {
@@ -283,15 +437,15 @@ pub fn render_text(
                     }
                 }
             } else {
-                // Slow path: the glyph straddles a pixmap edge; clip per pixel.
+                // Slow path: the glyph straddles a clip edge; clip per pixel.
                 for dy in 0..gh {
                     let yy = goy + dy;
-                    if yy < 0 || yy >= pm_h {
+                    if yy < clip_y0 || yy >= clip_y1 {
                         continue;
                     }
                     for dx in 0..gw {
                         let xx = gox + dx;
-                        if xx < 0 || xx >= pm_w {
+                        if xx < clip_x0 || xx >= clip_x1 {
                             continue;
                         }
                         gamma.blend_px(data, (yy as usize * pm_w_us + xx as usize) * 4, sr, sg, sb, a);
@@ -337,6 +491,28 @@ mod tests {
         assert!(drawn > 1000, "expected substantial glyph coverage, got {drawn}");
     }
 
+    #[test]
+    fn middle_ellipsis_keeps_both_ends() {
+        let path = "/home/user/Downloads/some/deeply/nested/folder/archive-1.2.3.tar.gz";
+        assert_eq!(middle_ellipsis(path, false, 14.0, f32::INFINITY), path);
+
+        let width = measure_text_width(path, false, 14.0) / 2.0;
+        let short = middle_ellipsis(path, false, 14.0, width);
+        assert!(measure_text_width(&short, false, 14.0) <= width);
+        let (head, tail) = short.split_once('…').unwrap();
+        assert!(path.starts_with(head) && path.ends_with(tail));
+        assert!(head.len().abs_diff(tail.len()) <= 1);
+        assert!(short.chars().count() > path.chars().count() / 3);
+
+        // An "e" with a combining acute accent (3 bytes) is never split from its accent.
+        let accents = "e\u{301}".repeat(40);
+        let width = measure_text_width(&accents, false, 14.0) / 2.0;
+        let short = middle_ellipsis(&accents, false, 14.0, width);
+        let (head, tail) = short.split_once('…').unwrap();
+        assert!(!head.is_empty() && head.len() % 3 == 0 && tail.len() % 3 == 0, "{:?}", short);
+        assert!(!tail.starts_with('\u{301}'));
+    }
+
     /// Renders representative dialog text (bold title + regular body) on both the light and dark
     /// theme backgrounds at 2× scale, and dumps a PNG for eyeballing rasterization/compositing
     /// quality. Not an assertion — a manual A/B aid, so it's `#[ignore]`d (run on demand with
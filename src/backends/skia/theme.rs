@@ -1,4 +1,5 @@
 use super::desktop::{ColorScheme, DesktopAppearance};
+use crate::ProgressBarState;
 
 #[derive(Debug, Clone)]
 pub struct SkiaButtonStyle {
@@ -26,11 +27,47 @@ pub struct SkiaTheme {
     pub color_title_text: (u8, u8, u8),
     pub color_progress_background: (u8, u8, u8),
     pub color_progress_foreground: (u8, u8, u8),
+    /// Track and bar colours of a paused progress bar.
+    pub color_progress_paused_background: (u8, u8, u8),
+    pub color_progress_paused_foreground: (u8, u8, u8),
+    /// Track and bar colours of a progress bar in the error state.
+    pub color_progress_error_background: (u8, u8, u8),
+    pub color_progress_error_foreground: (u8, u8, u8),
+    pub color_field_background: (u8, u8, u8),
+    pub color_field_border: (u8, u8, u8),
+    pub color_field_border_focused: (u8, u8, u8),
+    pub color_selection: (u8, u8, u8),
+    pub color_error_text: (u8, u8, u8),
+    /// Disc colours of the standard icons; the glyphs on them are white, except the warning's.
+    pub color_icon_information: (u8, u8, u8),
+    pub color_icon_error: (u8, u8, u8),
+    pub color_icon_warning: (u8, u8, u8),
+    pub color_icon_question: (u8, u8, u8),
+    pub color_icon_success: (u8, u8, u8),
+    /// The two colours the shield icon is quartered in, after the Windows elevation shield.
+    pub color_icon_shield: ((u8, u8, u8), (u8, u8, u8)),
 
     pub style_button_inactive: SkiaButtonStyle,
     pub style_button_hover: SkiaButtonStyle,
     pub style_button_pressed: SkiaButtonStyle,
     pub style_button_focused: SkiaButtonStyle,
+    /// The default button (see `XDialogOptions::default_button`) is filled with the accent colour
+    /// even at rest, so these replace the inactive, hover and focused styles for it.
+    pub style_button_primary: SkiaButtonStyle,
+    pub style_button_primary_hover: SkiaButtonStyle,
+    pub style_button_primary_focused: SkiaButtonStyle,
+}
+
+impl SkiaTheme {
+    /// The (track, bar) colours of a progress bar in `state`. Only the normal state follows the
+    /// accent colour; paused and error keep their yellow and red.
+    pub fn progress_colors(&self, state: ProgressBarState) -> ((u8, u8, u8), (u8, u8, u8)) {
+        match state {
+            ProgressBarState::Normal => (self.color_progress_background, self.color_progress_foreground),
+            ProgressBarState::Paused => (self.color_progress_paused_background, self.color_progress_paused_foreground),
+            ProgressBarState::Error => (self.color_progress_error_background, self.color_progress_error_foreground),
+        }
+    }
 }
 
 /// Build the theme for the given desktop appearance: pick the light or dark base, then overlay
@@ -65,6 +102,21 @@ fn ubuntu_light() -> SkiaTheme {
         color_title_text: (0x3D, 0x3D, 0x3D),
         color_progress_background: (173, 206, 247),
         color_progress_foreground: (42, 125, 227),
+        color_progress_paused_background: (243, 220, 166),
+        color_progress_paused_foreground: (0xE5, 0xA5, 0x0A),
+        color_progress_error_background: (230, 172, 177),
+        color_progress_error_foreground: (0xC0, 0x1C, 0x28),
+        color_field_background: (0xFF, 0xFF, 0xFF),
+        color_field_border: (0xC7, 0xC7, 0xC7),
+        color_field_border_focused: (42, 125, 227),
+        color_selection: (173, 206, 247),
+        color_error_text: (0xC0, 0x1C, 0x28),
+        color_icon_information: (0x21, 0x96, 0xF3),
+        color_icon_error: (0xD7, 0x5A, 0x4A),
+        color_icon_warning: (0xFF, 0xC1, 0x07),
+        color_icon_question: (0x5C, 0x6B, 0xC0),
+        color_icon_success: (0x2E, 0xA0, 0x43),
+        color_icon_shield: ((0x1E, 0x6F, 0xD9), (0xF2, 0xB6, 0x0F)),
 
         style_button_inactive: SkiaButtonStyle {
             border_color: (0xC7, 0xC7, 0xC7),
@@ -97,6 +149,30 @@ fn ubuntu_light() -> SkiaTheme {
             border_radius: 6,
             border_width: 2,
         },
+
+        style_button_primary: SkiaButtonStyle {
+            border_color: (42, 125, 227),
+            background_color: (42, 125, 227),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
+
+        style_button_primary_hover: SkiaButtonStyle {
+            border_color: (37, 110, 200),
+            background_color: (37, 110, 200),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
+
+        style_button_primary_focused: SkiaButtonStyle {
+            border_color: (0x3D, 0x3D, 0x3D),
+            background_color: (42, 125, 227),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
     }
 }
 
@@ -120,6 +196,22 @@ fn ubuntu_dark() -> SkiaTheme {
         color_title_text: (0xFF, 0xFF, 0xFF),
         color_progress_background: (0x4A, 0x4A, 0x4A),
         color_progress_foreground: (42, 125, 227),
+        color_progress_paused_background: (115, 97, 35),
+        color_progress_paused_foreground: (0xF5, 0xC2, 0x11),
+        color_progress_error_background: (119, 72, 69),
+        color_progress_error_foreground: (0xFF, 0x7B, 0x72),
+        color_field_background: (0x1E, 0x1E, 0x1E),
+        color_field_border: (0x5A, 0x5A, 0x5A),
+        color_field_border_focused: (42, 125, 227),
+        color_selection: (0x2B, 0x4F, 0x7E),
+        color_error_text: (0xFF, 0x7B, 0x72),
+        // A shade lighter than the light theme, so the discs don't sink into the dark background.
+        color_icon_information: (0x4D, 0xA6, 0xF5),
+        color_icon_error: (0xE0, 0x6C, 0x5E),
+        color_icon_warning: (0xFF, 0xC1, 0x07),
+        color_icon_question: (0x7F, 0x8C, 0xE0),
+        color_icon_success: (0x4C, 0xB8, 0x62),
+        color_icon_shield: ((0x4A, 0x90, 0xE8), (0xF5, 0xC2, 0x11)),
 
         style_button_inactive: SkiaButtonStyle {
             border_color: (0x5A, 0x5A, 0x5A),
@@ -152,12 +244,36 @@ fn ubuntu_dark() -> SkiaTheme {
             border_radius: 6,
             border_width: 2,
         },
+
+        style_button_primary: SkiaButtonStyle {
+            border_color: (42, 125, 227),
+            background_color: (42, 125, 227),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
+
+        style_button_primary_hover: SkiaButtonStyle {
+            border_color: (37, 110, 200),
+            background_color: (37, 110, 200),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
+
+        style_button_primary_focused: SkiaButtonStyle {
+            border_color: (0xEE, 0xEE, 0xEE),
+            background_color: (42, 125, 227),
+            text_color: (0xFF, 0xFF, 0xFF),
+            border_radius: 6,
+            border_width: 2,
+        },
     }
 }
 
-/// Overlay the desktop's accent color onto the interactive elements (hover/pressed/focused
-/// buttons and the progress bar), deriving pressed/track shades from it and choosing readable
-/// text. Everything else (backgrounds, inactive buttons, body text) keeps the base theme.
+/// Overlay the desktop's accent color onto the interactive elements (hover/pressed/focused and
+/// default buttons, the progress bar and the text field), deriving pressed/track shades from it and
+/// choosing readable text. Everything else (backgrounds, inactive buttons, body text) keeps the base theme.
 fn apply_accent(theme: &mut SkiaTheme, accent: (u8, u8, u8)) {
     let pressed = darken(accent, 0.75);
     let on_accent = contrasting_text(accent);
@@ -165,6 +281,8 @@ fn apply_accent(theme: &mut SkiaTheme, accent: (u8, u8, u8)) {
 
     theme.color_progress_foreground = accent;
     theme.color_progress_background = blend(accent, theme.color_background, 0.65);
+    theme.color_field_border_focused = accent;
+    theme.color_selection = blend(accent, theme.color_field_background, 0.65);
 
     theme.style_button_hover.border_color = accent;
     theme.style_button_hover.background_color = accent;
@@ -176,6 +294,17 @@ fn apply_accent(theme: &mut SkiaTheme, accent: (u8, u8, u8)) {
 
     // Focused keeps the base background; only the accent outline changes.
     theme.style_button_focused.border_color = accent;
+
+    let primary_hover = darken(accent, 0.88);
+    theme.style_button_primary.border_color = accent;
+    theme.style_button_primary.background_color = accent;
+    theme.style_button_primary.text_color = on_accent;
+    theme.style_button_primary_hover.border_color = primary_hover;
+    theme.style_button_primary_hover.background_color = primary_hover;
+    theme.style_button_primary_hover.text_color = contrasting_text(primary_hover);
+    // The focused default button keeps its text-coloured outline, which reads on any accent.
+    theme.style_button_primary_focused.background_color = accent;
+    theme.style_button_primary_focused.text_color = on_accent;
 }
 
 /// Scale each channel toward black by `factor` (e.g. 0.75 = 25% darker).
@@ -185,7 +314,7 @@ fn darken((r, g, b): (u8, u8, u8), factor: f32) -> (u8, u8, u8) {
 }
 
 /// Linearly mix `from` toward `to`, where `t` is the weight of `to` (0.0 = `from`, 1.0 = `to`).
-fn blend(from: (u8, u8, u8), to: (u8, u8, u8), t: f32) -> (u8, u8, u8) {
+pub fn blend(from: (u8, u8, u8), to: (u8, u8, u8), t: f32) -> (u8, u8, u8) {
     let mix = |a: u8, b: u8| (a as f32 * (1.0 - t) + b as f32 * t).round().clamp(0.0, 255.0) as u8;
     (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
 }
@@ -3,7 +3,7 @@ pub mod taskdialog;
 
 use std::sync::mpsc::Receiver;
 
-use crate::{backends::XDialogBackendImpl, DialogMessageRequest, XDialogTheme};
+use crate::{backends::XDialogBackendImpl, DialogMessageRequest, ProgressCallbacks, XDialogError, XDialogTheme};
 
 pub struct Win32Backend;
 
@@ -14,7 +14,10 @@ impl XDialogBackendImpl for Win32Backend {
             match message {
                 DialogMessageRequest::None => {}
                 DialogMessageRequest::ShowMessageWindow(id, options, result) => {
-                    dialogs.show(id, options, false, result, None);
+                    dialogs.show(id, options, false, result, ProgressCallbacks::default());
+                }
+                DialogMessageRequest::ShowInputWindow(_id, _options, _field, result) => {
+                    let _ = result.send(Err(XDialogError::SystemError("text input is not supported by the Win32 backend".to_string())));
                 }
                 DialogMessageRequest::ExitEventLoop => {
                     dialogs.close_all();
@@ -23,18 +26,36 @@ impl XDialogBackendImpl for Win32Backend {
                 DialogMessageRequest::CloseWindow(id) => {
                     dialogs.close(id);
                 }
-                DialogMessageRequest::ShowProgressWindow(id, options, result, on_button) => {
-                    dialogs.show(id, options, true, result, on_button);
+                DialogMessageRequest::UpdateDialog(id, patch) => {
+                    dialogs.update(id, patch);
+                }
+                DialogMessageRequest::ShowProgressWindow(id, options, result, callbacks) => {
+                    dialogs.show(id, options, true, result, callbacks);
                 }
-                DialogMessageRequest::SetProgressIndeterminate(id) => {
-                    dialogs.set_progress_indeterminate(id);
+                DialogMessageRequest::SetProgressIndeterminate(id, bar) => {
+                    dialogs.set_progress_indeterminate(id, bar);
                 }
-                DialogMessageRequest::SetProgressValue(id, value) => {
-                    dialogs.set_progress_value(id, value);
+                DialogMessageRequest::SetProgressValue(id, bar, value) => {
+                    dialogs.set_progress_value(id, bar, value);
                 }
                 DialogMessageRequest::SetProgressText(id, text) => {
                     dialogs.set_progress_text(id, &text);
                 }
+                DialogMessageRequest::SetProgressStatus(id, text) => {
+                    dialogs.set_progress_status(id, &text);
+                }
+                DialogMessageRequest::SetProgressState(id, bar, state) => {
+                    dialogs.set_progress_state(id, bar, state);
+                }
+                DialogMessageRequest::SetProgressCaption(id, bar, caption) => {
+                    dialogs.set_progress_caption(id, bar, &caption);
+                }
+                DialogMessageRequest::SetChecklistStep(id, index, state) => {
+                    dialogs.set_checklist_step(id, index, state);
+                }
+                DialogMessageRequest::StartChecklistStep(id, index) => {
+                    dialogs.start_checklist_step(id, index);
+                }
             }
         }
         dialogs.close_all();
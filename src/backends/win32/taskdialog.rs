@@ -10,26 +10,47 @@ use windows::core::{BOOL, HRESULT, PCWSTR};
 use windows::Win32::Foundation::{FALSE, HMODULE, HWND, LPARAM, S_FALSE, S_OK, TRUE, WPARAM};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 use windows::Win32::UI::Controls::{
-    TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0, TASKDIALOGCONFIG_1, TASKDIALOG_BUTTON, TASKDIALOG_COMMON_BUTTON_FLAGS,
+    TaskDialogIndirect, PBST_ERROR, PBST_NORMAL, PBST_PAUSED, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0, TASKDIALOGCONFIG_1,
+    TASKDIALOG_BUTTON, TASKDIALOG_COMMON_BUTTON_FLAGS,
     TASKDIALOG_FLAGS, TASKDIALOG_NOTIFICATIONS, TDE_CONTENT, TDE_EXPANDED_INFORMATION, TDE_FOOTER, TDE_MAIN_INSTRUCTION,
-    TDF_CALLBACK_TIMER, TDF_SHOW_PROGRESS_BAR, TDF_SIZE_TO_CONTENT, TDM_SET_BUTTON_ELEVATION_REQUIRED_STATE, TDM_SET_ELEMENT_TEXT,
-    TDM_SET_MARQUEE_PROGRESS_BAR, TDM_SET_PROGRESS_BAR_MARQUEE, TDM_SET_PROGRESS_BAR_POS, TDN_BUTTON_CLICKED, TDN_CREATED,
-    TDN_DESTROYED, TDN_HYPERLINK_CLICKED, TDN_TIMER, TD_ERROR_ICON, TD_INFORMATION_ICON, TD_WARNING_ICON,
+    TDF_ALLOW_DIALOG_CANCELLATION, TDF_CALLBACK_TIMER, TDF_ENABLE_HYPERLINKS, TDF_SHOW_PROGRESS_BAR, TDF_SIZE_TO_CONTENT,
+    TDF_USE_HICON_FOOTER, TDF_USE_HICON_MAIN, TDF_VERIFICATION_FLAG_CHECKED, TDIE_ICON_MAIN, TDM_SET_BUTTON_ELEVATION_REQUIRED_STATE,
+    TDM_SET_ELEMENT_TEXT,
+    TDM_SET_MARQUEE_PROGRESS_BAR, TDM_SET_PROGRESS_BAR_MARQUEE, TDM_SET_PROGRESS_BAR_POS, TDM_SET_PROGRESS_BAR_STATE, TDM_UPDATE_ICON,
+    TDN_BUTTON_CLICKED,
+    TDN_CREATED, TDN_DESTROYED, TDN_HYPERLINK_CLICKED, TDN_TIMER, TD_ERROR_ICON, TD_INFORMATION_ICON, TD_SHIELD_ICON, TD_WARNING_ICON,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateIconFromResourceEx, DestroyIcon, EndDialog, EnumChildWindows, GetDlgCtrlID, LoadIconW, SendMessageW, SetWindowTextW, HICON,
+    IDCANCEL, IDI_ERROR, IDI_INFORMATION, IDI_QUESTION, IDI_SHIELD, IDI_WARNING, LR_DEFAULTSIZE,
 };
-use windows::Win32::UI::WindowsAndMessaging::{EndDialog, SendMessageW, HICON};
 
-use crate::{ProgressButtonCallback, ProgressDialogProxy, XDialogIcon, XDialogOptions, XDialogResult};
+use crate::checklist::Checklist;
+use crate::icon::{IconData, IconSource};
+use crate::{
+    ChecklistStepState, DialogPatch, ProgressBarState, ProgressCallbacks, XDialogIcon, XDialogOptions, XDialogResponse, XDialogResult,
+};
 use crate::model::CreationSender;
 
+/// Our buttons are given ids from here up, clear of the common ids (`IDOK`, `IDCANCEL`, ...) which
+/// the task dialog reports for its own default button and for Escape or the close button.
+const BUTTON_ID_BASE: i32 = 100;
+
 type OpenDialogMap = Arc<Mutex<HashMap<usize, (Sender<DialogRequest>, Receiver<DialogRequest>)>>>;
 
 #[derive(Debug, PartialEq)]
 enum DialogRequest {
     None,
     Close,
-    SetProgress(f32),
-    SetIndeterminate,
+    SetProgress(usize, f32),
+    SetIndeterminate(usize),
+    SetState(usize, ProgressBarState),
+    SetCaption(usize, String),
+    SetStep(usize, ChecklistStepState),
+    StartStep(usize),
     SetText(String),
+    SetStatus(String),
+    Update(DialogPatch),
 }
 
 /// Manages Win32 Task Dialogs. Each dialog runs on its own thread and communicates via channels.
@@ -45,7 +66,7 @@ impl TaskDialogManager {
 }
 
 impl TaskDialogManager {
-    pub fn show(&self, id: usize, data: XDialogOptions, has_progress: bool, creation: CreationSender, button_callback: Option<ProgressButtonCallback>) {
+    pub fn show(&self, id: usize, data: XDialogOptions, has_progress: bool, creation: CreationSender, callbacks: ProgressCallbacks) {
         let open_dialogs = self.open_dialogs.clone();
         // Insert a new dialog
         {
@@ -57,37 +78,67 @@ impl TaskDialogManager {
         let (dialog_sender, dialog_receiver) = oneshot::channel();
         let _ = creation.send(Ok(dialog_receiver));
         std::thread::spawn(move || {
+            // Read everything derived from the options before their fields are moved into the config.
+            let radio_index = data.initial_radio_index();
+            let default_button = data.default_button_index().or(data.buttons.len().checked_sub(1));
+            let dismiss_result = data.dismiss_result();
             let mut config = TaskDialogConfig::new(open_dialogs.clone());
             config.window_title = data.title;
             config.main_instruction = data.main_instruction;
             config.content = data.message;
             config.x_dialog_id = id;
-            config.button_callback = button_callback;
-            let mut default_button: Option<i32> = None;
             for (idx, text) in data.buttons.iter().enumerate().rev() {
-                if default_button.is_none() {
-                    default_button = Some(idx as i32);
-                }
-
-                let button = TaskDialogButton { text: text.clone(), id: idx as i32 };
+                let button = TaskDialogButton { text: text.clone(), id: BUTTON_ID_BASE + idx as i32 };
                 config.buttons.push(button);
             }
-            config.default_button = default_button.unwrap_or(0);
-            config.main_icon = convert_icon(data.icon);
+            config.default_button = default_button.map_or(0, |idx| BUTTON_ID_BASE + idx as i32);
+            config.set_initial_main_icon(data.icon);
             config.progress = if has_progress { ProgressState::Pos(0f32) } else { ProgressState::None };
-            config.flags = TDF_SIZE_TO_CONTENT | TDF_CALLBACK_TIMER;
+            if has_progress {
+                config.text_bars = data.progress_bars.iter().skip(1).map(|caption| TextBar::new(caption)).collect();
+                config.checklist = Checklist::new(&data.checklist);
+            }
+            for (idx, text) in data.radio_buttons.iter().enumerate() {
+                config.radio_buttons.push(TaskDialogButton { text: text.clone(), id: idx as i32 });
+            }
+            config.default_radio_buttons = radio_index.unwrap_or(0) as i32;
+            config.verification_text = data.verification_text;
+            config.expanded_information = data.expanded_details;
+            config.footer = data.footer_text;
+            config.flags |= TDF_SIZE_TO_CONTENT | TDF_CALLBACK_TIMER;
+            config.set_initial_footer_icon(data.footer_icon);
+            // The task dialog understands the same `<a href>` markup natively.
+            if !crate::hyperlink::parse_links(&config.content).1.is_empty() {
+                config.flags |= TDF_ENABLE_HYPERLINKS;
+                config.hyperlink_callback = Some(crate::hyperlink::open_link);
+            }
             if has_progress {
                 config.flags |= TDF_SHOW_PROGRESS_BAR;
             }
+            if data.verification_checked {
+                config.flags |= TDF_VERIFICATION_FLAG_CHECKED;
+            }
+            // With a cancel button, Escape and the close button dismiss the dialog as if it was pressed.
+            // A close callback needs them enabled too, to be asked whether the dialog may close.
+            if dismiss_result != XDialogResult::WindowClosed || callbacks.on_close_requested.is_some() {
+                config.flags |= TDF_ALLOW_DIALOG_CANCELLATION;
+            }
+            config.callbacks = callbacks;
             config.callback = Some(|hwnd, msg, w_param, _l_param, ref_data| {
                 if msg == TDN_BUTTON_CLICKED {
                     let config = unsafe { &mut *ref_data };
-                    if let Some(cb) = config.button_callback.as_mut() {
-                        let proxy = ProgressDialogProxy::non_owning(config.x_dialog_id);
-                        let keep_open = (cb.0)(w_param.0, &proxy);
+                    // Cancellation (Escape or the close button) may be vetoed by the close callback.
+                    if w_param.0 as i32 == IDCANCEL.0 {
+                        let keep_open = config.callbacks.close_requested(config.x_dialog_id);
                         return if keep_open { S_FALSE } else { S_OK };
                     }
-                    return S_OK;
+                    // Other common ids (eg. the button Windows adds when there are none) always
+                    // close the dialog.
+                    let Some(index) = (w_param.0 as i32).checked_sub(BUTTON_ID_BASE).filter(|i| *i >= 0) else {
+                        return S_OK;
+                    };
+                    let keep_open = config.callbacks.button_clicked(config.x_dialog_id, index as usize).unwrap_or(false);
+                    return if keep_open { S_FALSE } else { S_OK };
                 }
                 if msg == TDN_TIMER {
                     let config = unsafe { &mut *ref_data };
@@ -104,9 +155,22 @@ impl TaskDialogManager {
                                 DialogRequest::Close => unsafe {
                                     let _ = EndDialog(hwnd, -1);
                                 },
-                                DialogRequest::SetProgress(val) => desired_state = ProgressState::Pos(val),
-                                DialogRequest::SetIndeterminate => desired_state = ProgressState::Indeterminate,
+                                DialogRequest::SetProgress(0, val) => desired_state = ProgressState::Pos(val),
+                                DialogRequest::SetIndeterminate(0) => desired_state = ProgressState::Indeterminate,
+                                DialogRequest::SetState(0, state) => config.set_progress_bar_state(state),
+                                DialogRequest::SetProgress(bar, val) => {
+                                    config.update_text_bar(bar, |b| b.progress = ProgressState::Pos(val))
+                                }
+                                DialogRequest::SetIndeterminate(bar) => {
+                                    config.update_text_bar(bar, |b| b.progress = ProgressState::Indeterminate)
+                                }
+                                DialogRequest::SetState(bar, state) => config.update_text_bar(bar, |b| b.state = state),
+                                DialogRequest::SetCaption(bar, caption) => config.update_text_bar(bar, |b| b.caption = caption),
+                                DialogRequest::SetStep(index, state) => config.update_checklist(|c| c.set(index, state)),
+                                DialogRequest::StartStep(index) => config.update_checklist(|c| c.start(index)),
                                 DialogRequest::SetText(text) => config.set_content(&text),
+                                DialogRequest::SetStatus(text) => config.set_status(&text),
+                                DialogRequest::Update(patch) => config.apply_patch(patch),
                             }
                         }
 
@@ -138,18 +202,22 @@ impl TaskDialogManager {
                 dialogs.remove(&id);
             }
 
-            let xresult = match result {
+            let (xresult, checked, radio_index) = match result {
                 Ok(result) => {
-                    if result.button_id < 0 {
-                        XDialogResult::WindowClosed
+                    let xresult = if result.button_id >= BUTTON_ID_BASE {
+                        XDialogResult::ButtonPressed((result.button_id - BUTTON_ID_BASE) as usize)
+                    } else if result.button_id == IDCANCEL.0 {
+                        dismiss_result
                     } else {
-                        XDialogResult::ButtonPressed(result.button_id as usize)
-                    }
+                        XDialogResult::WindowClosed
+                    };
+                    (xresult, result.checked, radio_index.map(|_| result.radio_button_id as usize))
                 }
-                Err(_) => XDialogResult::WindowClosed,
+                Err(_) => (XDialogResult::WindowClosed, data.verification_checked, radio_index),
             };
 
-            let _ = dialog_sender.send(xresult);
+            let checked = (!config.verification_text.is_empty()).then_some(checked);
+            let _ = dialog_sender.send(XDialogResponse { checked, radio_index, ..xresult.into() });
         });
     }
 
@@ -166,9 +234,21 @@ impl TaskDialogManager {
         }
     }
 
-    pub fn set_progress_value(&self, id: usize, progress: f32) {
+    pub fn set_progress_value(&self, id: usize, bar: usize, progress: f32) {
         if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
-            let _ = obj.0.send(DialogRequest::SetProgress(progress));
+            let _ = obj.0.send(DialogRequest::SetProgress(bar, progress));
+        }
+    }
+
+    pub fn set_progress_state(&self, id: usize, bar: usize, state: ProgressBarState) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::SetState(bar, state));
+        }
+    }
+
+    pub fn set_progress_caption(&self, id: usize, bar: usize, caption: &str) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::SetCaption(bar, caption.to_string()));
         }
     }
 
@@ -178,9 +258,33 @@ impl TaskDialogManager {
         }
     }
 
-    pub fn set_progress_indeterminate(&self, id: usize) {
+    pub fn set_progress_status(&self, id: usize, text: &str) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::SetStatus(text.to_string()));
+        }
+    }
+
+    pub fn set_checklist_step(&self, id: usize, index: usize, state: ChecklistStepState) {
         if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
-            let _ = obj.0.send(DialogRequest::SetIndeterminate);
+            let _ = obj.0.send(DialogRequest::SetStep(index, state));
+        }
+    }
+
+    pub fn start_checklist_step(&self, id: usize, index: usize) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::StartStep(index));
+        }
+    }
+
+    pub fn update(&self, id: usize, patch: DialogPatch) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::Update(patch));
+        }
+    }
+
+    pub fn set_progress_indeterminate(&self, id: usize, bar: usize) {
+        if let Some(obj) = self.open_dialogs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&id) {
+            let _ = obj.0.send(DialogRequest::SetIndeterminate(bar));
         }
     }
 }
@@ -190,13 +294,80 @@ type TaskDialogHyperlinkCallback = Option<fn(context: &str) -> ()>;
 type TaskDialogWndProcCallback =
     Option<fn(hwnd: HWND, msg: TASKDIALOG_NOTIFICATIONS, w_param: WPARAM, l_param: LPARAM, ref_data: *mut TaskDialogConfig) -> HRESULT>;
 
-fn convert_icon(icon: XDialogIcon) -> TASKDIALOGCONFIG_0 {
+/// A standard icon as a task dialog resource id. The task dialog has no question icon, and a custom
+/// image can only be shown by handle (see [`icon_handle`]), so here they fall back to the
+/// information icon, as does success, which Windows has no icon for.
+fn convert_icon(icon: &XDialogIcon) -> TASKDIALOGCONFIG_0 {
     match icon {
         XDialogIcon::None => TASKDIALOGCONFIG_0 { hMainIcon: HICON(null_mut()) },
         XDialogIcon::Error => TASKDIALOGCONFIG_0 { pszMainIcon: TD_ERROR_ICON },
         XDialogIcon::Warning => TASKDIALOGCONFIG_0 { pszMainIcon: TD_WARNING_ICON },
-        XDialogIcon::Information => TASKDIALOGCONFIG_0 { pszMainIcon: TD_INFORMATION_ICON },
+        XDialogIcon::Shield => TASKDIALOGCONFIG_0 { pszMainIcon: TD_SHIELD_ICON },
+        XDialogIcon::Information | XDialogIcon::Question | XDialogIcon::Success | XDialogIcon::Custom(_) => {
+            TASKDIALOGCONFIG_0 { pszMainIcon: TD_INFORMATION_ICON }
+        }
+    }
+}
+
+fn convert_footer_icon(icon: &XDialogIcon) -> TASKDIALOGCONFIG_1 {
+    match icon {
+        XDialogIcon::None => TASKDIALOGCONFIG_1 { hFooterIcon: HICON(null_mut()) },
+        XDialogIcon::Error => TASKDIALOGCONFIG_1 { pszFooterIcon: TD_ERROR_ICON },
+        XDialogIcon::Warning => TASKDIALOGCONFIG_1 { pszFooterIcon: TD_WARNING_ICON },
+        XDialogIcon::Shield => TASKDIALOGCONFIG_1 { pszFooterIcon: TD_SHIELD_ICON },
+        XDialogIcon::Information | XDialogIcon::Question | XDialogIcon::Success | XDialogIcon::Custom(_) => {
+            TASKDIALOGCONFIG_1 { pszFooterIcon: TD_INFORMATION_ICON }
+        }
+    }
+}
+
+/// Whether `icon` is only available by handle, so a dialog opening with it shows its icon by handle.
+fn shown_by_handle(icon: &XDialogIcon) -> bool {
+    matches!(icon, XDialogIcon::Question | XDialogIcon::Custom(_))
+}
+
+/// An icon handle for `icon`, for a dialog which shows its icons by handle, and whether the caller
+/// owns it (and must destroy it). `None` for no icon, or a custom image that can't be shown (SVG).
+fn icon_handle(icon: &XDialogIcon) -> Option<(HICON, bool)> {
+    let standard = |id: PCWSTR| unsafe { LoadIconW(None, id).ok() };
+    match icon {
+        XDialogIcon::None => None,
+        XDialogIcon::Error => standard(IDI_ERROR).map(|h| (h, false)),
+        XDialogIcon::Warning => standard(IDI_WARNING).map(|h| (h, false)),
+        XDialogIcon::Information | XDialogIcon::Success => standard(IDI_INFORMATION).map(|h| (h, false)),
+        XDialogIcon::Question => standard(IDI_QUESTION).map(|h| (h, false)),
+        XDialogIcon::Shield => standard(IDI_SHIELD).map(|h| (h, false)),
+        XDialogIcon::Custom(data) => create_custom_icon(data).map(|h| (h, true)),
+    }
+}
+
+fn create_custom_icon(data: &IconData) -> Option<HICON> {
+    let resource = match data.source() {
+        // An icon resource may be a whole PNG file.
+        IconSource::Png(bytes) => bytes.to_vec(),
+        IconSource::Rgba { width, height, pixels } => rgba_icon_resource(*width, *height, pixels),
+        IconSource::Svg(_) => return None,
+    };
+    // Sized for the system's large icons, which is the size of the task dialog's main icon.
+    unsafe { CreateIconFromResourceEx(&resource, true, 0x0003_0000, 0, 0, LR_DEFAULTSIZE).ok() }
+}
+
+/// The icon resource for straight RGBA pixels: a bitmap header, the pixels as BGRA from the bottom
+/// row up, and an (unused, as the pixels have alpha) AND mask. The header's height counts both.
+fn rgba_icon_resource(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let mask_stride = width.div_ceil(32) as usize * 4;
+    let mut resource = Vec::with_capacity(40 + pixels.len() + mask_stride * height as usize);
+    for value in [40, width, height * 2] {
+        resource.extend_from_slice(&value.to_le_bytes());
     }
+    resource.extend_from_slice(&1u16.to_le_bytes()); // planes
+    resource.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+    resource.extend_from_slice(&[0; 24]); // uncompressed, and the sizes and palette are implied
+    for row in pixels.chunks_exact(width as usize * 4).rev() {
+        resource.extend(row.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], p[3]]));
+    }
+    resource.resize(resource.len() + mask_stride * height as usize, 0);
+    resource
 }
 
 #[derive(Debug, PartialEq)]
@@ -206,6 +377,37 @@ enum ProgressState {
     Pos(f32),
 }
 
+/// A progress bar after the first. The task dialog has only one, so the others are listed as lines
+/// of text (eg. "Overall: 45%") after the content.
+struct TextBar {
+    caption: String,
+    progress: ProgressState,
+    state: ProgressBarState,
+}
+
+impl TextBar {
+    fn new(caption: &str) -> Self {
+        TextBar { caption: caption.to_string(), progress: ProgressState::Pos(0.0), state: ProgressBarState::Normal }
+    }
+
+    fn line(&self) -> String {
+        let value = match self.progress {
+            ProgressState::Pos(value) => format!("{}%", (value * 100.0).round() as usize),
+            _ => "…".to_string(),
+        };
+        let marker = match self.state {
+            ProgressBarState::Normal => "",
+            ProgressBarState::Paused => " (paused)",
+            ProgressBarState::Error => " (error)",
+        };
+        if self.caption.is_empty() {
+            format!("{}{}", value, marker)
+        } else {
+            format!("{}: {}{}", self.caption, value, marker)
+        }
+    }
+}
+
 struct TaskDialogConfig {
     pub parent: HWND,
     pub instance: HMODULE,
@@ -214,6 +416,13 @@ struct TaskDialogConfig {
     pub window_title: String,
     pub main_instruction: String,
     pub content: String,
+    /// The progress status line. The task dialog has no element for it, so it is shown as a
+    /// paragraph after `content`.
+    pub status: String,
+    /// The progress bars after the first, shown as text after `status`.
+    text_bars: Vec<TextBar>,
+    /// The checklist of a progress dialog, shown as text after `content`.
+    checklist: Checklist,
     pub verification_text: String,
     pub expanded_information: String,
     pub expanded_control_text: String,
@@ -225,6 +434,8 @@ struct TaskDialogConfig {
     pub default_radio_buttons: i32,
     pub main_icon: TASKDIALOGCONFIG_0,
     pub footer_icon: TASKDIALOGCONFIG_1,
+    /// Icons created from custom images, destroyed with the config.
+    owned_icons: Vec<HICON>,
     /** When created dialog, the value set to HWND. */
     pub dialog_hwnd: HWND,
     /** When close the dialog, the value set to true, default is false. */
@@ -235,9 +446,9 @@ struct TaskDialogConfig {
     pub progress: ProgressState,
     pub x_dialog_id: usize,
     pub open_dialogs: OpenDialogMap,
-    /// Optional callback invoked when a button is clicked. Returns `true` to keep the dialog open
-    /// (returns `S_FALSE` to the task dialog) or `false` to allow it to close.
-    pub button_callback: Option<ProgressButtonCallback>,
+    /// Callbacks invoked when a button is clicked or the dialog is cancelled. They return `true` to
+    /// keep the dialog open (returns `S_FALSE` to the task dialog) or `false` to allow it to close.
+    pub callbacks: ProgressCallbacks,
 }
 
 impl TaskDialogConfig {
@@ -250,6 +461,9 @@ impl TaskDialogConfig {
             window_title: "".to_string(),
             main_instruction: "".to_string(),
             content: "".to_string(),
+            status: "".to_string(),
+            text_bars: vec![],
+            checklist: Checklist::default(),
             verification_text: "".to_string(),
             expanded_information: "".to_string(),
             expanded_control_text: "".to_string(),
@@ -261,6 +475,7 @@ impl TaskDialogConfig {
             default_radio_buttons: 0,
             main_icon: TASKDIALOGCONFIG_0 { hMainIcon: HICON(null_mut()) },
             footer_icon: TASKDIALOGCONFIG_1 { hFooterIcon: HICON(null_mut()) },
+            owned_icons: vec![],
             dialog_hwnd: HWND(null_mut()),
             is_destroyed: false,
             hyperlink_callback: None,
@@ -269,7 +484,7 @@ impl TaskDialogConfig {
             progress: ProgressState::None,
             x_dialog_id: 0,
             open_dialogs,
-            button_callback: None,
+            callbacks: ProgressCallbacks::default(),
         }
     }
 }
@@ -306,14 +521,73 @@ impl TaskDialogConfig {
         }
     }
 
+    /** Set the state (normal, paused or error) of the progress bar */
+    pub fn set_progress_bar_state(&mut self, state: ProgressBarState) {
+        if self.dialog_hwnd.is_invalid() {
+            return;
+        }
+        let state = match state {
+            ProgressBarState::Normal => PBST_NORMAL,
+            ProgressBarState::Paused => PBST_PAUSED,
+            ProgressBarState::Error => PBST_ERROR,
+        };
+        unsafe {
+            SendMessageW(self.dialog_hwnd, TDM_SET_PROGRESS_BAR_STATE.0 as u32, Some(WPARAM(state as usize)), Some(LPARAM(0)));
+        }
+    }
+
     /** Set the content text */
     pub fn set_content(&mut self, content: &str) {
         if self.dialog_hwnd.is_invalid() {
             return;
         }
         self.content = content.to_string();
+        self.send_content();
+    }
+
+    /** Set the progress status, shown after the content text */
+    pub fn set_status(&mut self, status: &str) {
+        if self.dialog_hwnd.is_invalid() {
+            return;
+        }
+        self.status = status.to_string();
+        self.send_content();
+    }
+
+    /** Update a progress bar after the first, shown as text */
+    fn update_text_bar<F: FnOnce(&mut TextBar)>(&mut self, bar: usize, f: F) {
+        let Some(text_bar) = bar.checked_sub(1).and_then(|i| self.text_bars.get_mut(i)) else {
+            return;
+        };
+        f(text_bar);
+        if !self.dialog_hwnd.is_invalid() {
+            self.send_content();
+        }
+    }
+
+    /** Update the checklist, shown as text */
+    fn update_checklist<F: FnOnce(&mut Checklist) -> bool>(&mut self, f: F) {
+        if f(&mut self.checklist) && !self.dialog_hwnd.is_invalid() {
+            self.send_content();
+        }
+    }
+
+    /// The content as shown: the content text, the checklist, the status and the text bars,
+    /// separated by blank lines.
+    fn displayed_content(&self) -> String {
+        let bars = self.text_bars.iter().map(TextBar::line).collect::<Vec<_>>().join("\n");
+        let checklist = self.checklist.to_text();
+        [self.content.as_str(), checklist.as_str(), self.status.as_str(), bars.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn send_content(&self) {
+        let content = self.displayed_content();
         unsafe {
-            let content_wchar = U16CString::from_str_unchecked(content);
+            let content_wchar = U16CString::from_str_unchecked(&content);
             SendMessageW(
                 self.dialog_hwnd,
                 TDM_SET_ELEMENT_TEXT.0 as u32,
@@ -340,6 +614,117 @@ impl TaskDialogConfig {
         }
     }
 
+    /** Apply a patch from `DialogMessageRequest::UpdateDialog` */
+    pub fn apply_patch(&mut self, patch: DialogPatch) {
+        if let Some(title) = patch.title {
+            self.set_window_title(&title);
+        }
+        if let Some(main_instruction) = patch.main_instruction {
+            self.set_main_instruction(&main_instruction);
+        }
+        if let Some(content) = patch.message {
+            self.set_content(&content);
+        }
+        if let Some(icon) = patch.icon {
+            self.set_main_icon(icon);
+        }
+        for (index, text) in patch.button_labels {
+            if index < self.buttons.len() {
+                self.set_button_text(BUTTON_ID_BASE + index as i32, &text);
+            }
+        }
+    }
+
+    /** Set the window title */
+    pub fn set_window_title(&mut self, window_title: &str) {
+        if self.dialog_hwnd.is_invalid() {
+            return;
+        }
+        self.window_title = window_title.to_string();
+        unsafe {
+            let window_title_wchar = U16CString::from_str_unchecked(window_title);
+            let _ = SetWindowTextW(self.dialog_hwnd, PCWSTR(window_title_wchar.as_ptr()));
+        }
+    }
+
+    /// Set the main icon before the dialog is created. A dialog with a custom image (or the question
+    /// icon) shows its main icon by handle for as long as it is open.
+    fn set_initial_main_icon(&mut self, icon: XDialogIcon) {
+        if shown_by_handle(&icon) {
+            if let Some(handle) = self.take_icon_handle(&icon) {
+                self.flags |= TDF_USE_HICON_MAIN;
+                self.main_icon = TASKDIALOGCONFIG_0 { hMainIcon: handle };
+                return;
+            }
+        }
+        self.main_icon = convert_icon(&icon);
+    }
+
+    /// Set the footer icon before the dialog is created. See [`set_initial_main_icon`](Self::set_initial_main_icon).
+    fn set_initial_footer_icon(&mut self, icon: XDialogIcon) {
+        if shown_by_handle(&icon) {
+            if let Some(handle) = self.take_icon_handle(&icon) {
+                self.flags |= TDF_USE_HICON_FOOTER;
+                self.footer_icon = TASKDIALOGCONFIG_1 { hFooterIcon: handle };
+                return;
+            }
+        }
+        self.footer_icon = convert_footer_icon(&icon);
+    }
+
+    /// An icon handle for `icon`, destroyed with the config if it was created for it.
+    fn take_icon_handle(&mut self, icon: &XDialogIcon) -> Option<HICON> {
+        let (handle, owned) = icon_handle(icon)?;
+        if owned {
+            self.owned_icons.push(handle);
+        }
+        Some(handle)
+    }
+
+    /** Set the main icon */
+    pub fn set_main_icon(&mut self, icon: XDialogIcon) {
+        if self.dialog_hwnd.is_invalid() {
+            return;
+        }
+        // The dialog keeps showing its main icon either by resource id or by handle.
+        let icon_param = if self.flags.contains(TDF_USE_HICON_MAIN) {
+            let handle = self.take_icon_handle(&icon).unwrap_or(HICON(null_mut()));
+            self.main_icon = TASKDIALOGCONFIG_0 { hMainIcon: handle };
+            handle.0 as isize
+        } else {
+            self.main_icon = convert_icon(&icon);
+            unsafe { self.main_icon.pszMainIcon.0 as isize }
+        };
+        unsafe {
+            SendMessageW(self.dialog_hwnd, TDM_UPDATE_ICON.0 as u32, Some(WPARAM(TDIE_ICON_MAIN.0 as usize)), Some(LPARAM(icon_param)));
+        }
+    }
+
+    /** Set the label of the custom button with the given id */
+    pub fn set_button_text(&mut self, button_id: i32, text: &str) {
+        if self.dialog_hwnd.is_invalid() {
+            return;
+        }
+        // The task dialog has no message for this, but its buttons are ordinary button controls
+        // (nested below the dialog window) whose control id is the button id.
+        unsafe extern "system" fn find_button(hwnd: HWND, l_param: LPARAM) -> BOOL {
+            let search = &mut *(l_param.0 as *mut (i32, HWND));
+            if GetDlgCtrlID(hwnd) == search.0 {
+                search.1 = hwnd;
+                return FALSE;
+            }
+            TRUE
+        }
+        unsafe {
+            let mut search = (button_id, HWND(null_mut()));
+            let _ = EnumChildWindows(Some(self.dialog_hwnd), Some(find_button), LPARAM(&mut search as *mut _ as isize));
+            if !search.1.is_invalid() {
+                let text_wchar = U16CString::from_str_unchecked(text);
+                let _ = SetWindowTextW(search.1, PCWSTR(text_wchar.as_ptr()));
+            }
+        }
+    }
+
     /** Set the footer text */
     pub fn set_footer(&mut self, footer: &str) {
         if self.dialog_hwnd.is_invalid() {
@@ -390,6 +775,14 @@ impl TaskDialogConfig {
     }
 }
 
+impl Drop for TaskDialogConfig {
+    fn drop(&mut self) {
+        for icon in self.owned_icons.drain(..) {
+            let _ = unsafe { DestroyIcon(icon) };
+        }
+    }
+}
+
 struct TaskDialogButton {
     pub id: i32,
     pub text: String,
@@ -402,6 +795,17 @@ struct TaskDialogResult {
     pub checked: bool,
 }
 
+/// A pointer to `wide`, or null when `text` is empty. The task dialog shows the verification
+/// checkbox and the details expando whenever their strings are non-null, and falls back to its own
+/// localized expando labels only for null ones.
+fn optional_text(text: &str, wide: &U16CString) -> PCWSTR {
+    if text.is_empty() {
+        PCWSTR::null()
+    } else {
+        PCWSTR(wide.as_ptr())
+    }
+}
+
 unsafe fn execute_task_dialog(conf: &mut TaskDialogConfig) -> Result<TaskDialogResult, windows::core::Error> {
     let mut result = TaskDialogResult::default();
     let conf_ptr: *mut TaskDialogConfig = conf;
@@ -413,7 +817,7 @@ unsafe fn execute_task_dialog(conf: &mut TaskDialogConfig) -> Result<TaskDialogR
     // Some text
     let window_title: U16CString = U16CString::from_str_unchecked(&conf.window_title);
     let main_instruction: U16CString = U16CString::from_str_unchecked(&conf.main_instruction);
-    let content: U16CString = U16CString::from_str_unchecked(&conf.content);
+    let content: U16CString = U16CString::from_str_unchecked(conf.displayed_content());
     let verification_text: U16CString = U16CString::from_str_unchecked(&conf.verification_text);
     let expanded_information: U16CString = U16CString::from_str_unchecked(&conf.expanded_information);
     let expanded_control_text: U16CString = U16CString::from_str_unchecked(&conf.expanded_control_text);
@@ -477,11 +881,11 @@ unsafe fn execute_task_dialog(conf: &mut TaskDialogConfig) -> Result<TaskDialogR
         pszWindowTitle: PCWSTR(window_title.as_ptr()),
         pszMainInstruction: PCWSTR(main_instruction.as_ptr()),
         pszContent: PCWSTR(content.as_ptr()),
-        pszVerificationText: PCWSTR(verification_text.as_ptr()),
-        pszExpandedInformation: PCWSTR(expanded_information.as_ptr()),
-        pszExpandedControlText: PCWSTR(expanded_control_text.as_ptr()),
-        pszCollapsedControlText: PCWSTR(collapsed_control_text.as_ptr()),
-        pszFooter: PCWSTR(footer.as_ptr()),
+        pszVerificationText: optional_text(&conf.verification_text, &verification_text),
+        pszExpandedInformation: optional_text(&conf.expanded_information, &expanded_information),
+        pszExpandedControlText: optional_text(&conf.expanded_control_text, &expanded_control_text),
+        pszCollapsedControlText: optional_text(&conf.collapsed_control_text, &collapsed_control_text),
+        pszFooter: optional_text(&conf.footer, &footer),
         cButtons: buttons.len() as u32,
         pButtons: buttons.as_slice().as_ptr(),
         nDefaultButton: conf.default_button,
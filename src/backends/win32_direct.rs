@@ -1,7 +1,6 @@
 use std::sync::LazyLock;
 
 use crate::backends::win32::taskdialog::TaskDialogManager;
-use crate::channel::DialogRequestHandler;
 use crate::*;
 
 static MANAGER: LazyLock<TaskDialogManager> = LazyLock::new(TaskDialogManager::new);
@@ -12,29 +11,57 @@ impl DialogRequestHandler for Win32DirectHandler {
     fn send(&self, message: DialogMessageRequest) -> Result<(), XDialogError> {
         match message {
             DialogMessageRequest::ShowMessageWindow(id, options, result) => {
-                MANAGER.show(id, options, false, result, None);
+                MANAGER.show(id, options, false, result, ProgressCallbacks::default());
                 Ok(())
             }
-            DialogMessageRequest::ShowProgressWindow(id, options, result, on_button) => {
-                MANAGER.show(id, options, true, result, on_button);
+            DialogMessageRequest::ShowInputWindow(_id, _options, _field, result) => {
+                let _ = result.send(Err(XDialogError::SystemError("text input is not supported by the Win32 backend".to_string())));
                 Ok(())
             }
-            DialogMessageRequest::SetProgressValue(id, value) => {
-                MANAGER.set_progress_value(id, value);
+            DialogMessageRequest::ShowProgressWindow(id, options, result, callbacks) => {
+                MANAGER.show(id, options, true, result, callbacks);
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressValue(id, bar, value) => {
+                MANAGER.set_progress_value(id, bar, value);
                 Ok(())
             }
             DialogMessageRequest::SetProgressText(id, text) => {
                 MANAGER.set_progress_text(id, &text);
                 Ok(())
             }
-            DialogMessageRequest::SetProgressIndeterminate(id) => {
-                MANAGER.set_progress_indeterminate(id);
+            DialogMessageRequest::SetProgressStatus(id, text) => {
+                MANAGER.set_progress_status(id, &text);
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressState(id, bar, state) => {
+                MANAGER.set_progress_state(id, bar, state);
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressCaption(id, bar, caption) => {
+                MANAGER.set_progress_caption(id, bar, &caption);
+                Ok(())
+            }
+            DialogMessageRequest::SetChecklistStep(id, index, state) => {
+                MANAGER.set_checklist_step(id, index, state);
+                Ok(())
+            }
+            DialogMessageRequest::StartChecklistStep(id, index) => {
+                MANAGER.start_checklist_step(id, index);
+                Ok(())
+            }
+            DialogMessageRequest::SetProgressIndeterminate(id, bar) => {
+                MANAGER.set_progress_indeterminate(id, bar);
                 Ok(())
             }
             DialogMessageRequest::CloseWindow(id) => {
                 MANAGER.close(id);
                 Ok(())
             }
+            DialogMessageRequest::UpdateDialog(id, patch) => {
+                MANAGER.update(id, patch);
+                Ok(())
+            }
             DialogMessageRequest::ExitEventLoop | DialogMessageRequest::None => Ok(()),
         }
     }
@@ -3,17 +3,29 @@ use std::{sync::mpsc::channel, thread};
 use crate::backends::XDialogBackendImpl;
 use crate::channel::{send_request, ChannelHandler};
 use crate::model::*;
+use crate::{set_auto_answer, XDialogAutoAnswer, XDialogBackend};
 
-#[derive(Debug)]
 /// Builder pattern to configure/initialise the XDialog library. Must be configured and `run` in
 /// the main thread before any other XDialog functions are called.
 pub struct XDialogBuilder {
     theme: XDialogTheme,
+    backend: Option<Box<dyn XDialogBackend + Send + Sync>>,
+    auto_answer: Option<XDialogAutoAnswer>,
 }
 
 impl Default for XDialogBuilder {
     fn default() -> XDialogBuilder {
-        XDialogBuilder { theme: XDialogTheme::SystemDefault }
+        XDialogBuilder { theme: XDialogTheme::SystemDefault, backend: None, auto_answer: None }
+    }
+}
+
+impl std::fmt::Debug for XDialogBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XDialogBuilder")
+            .field("theme", &self.theme)
+            .field("custom_backend", &self.backend.is_some())
+            .field("auto_answer", &self.auto_answer)
+            .finish()
     }
 }
 
@@ -29,12 +41,29 @@ impl XDialogBuilder {
         self
     }
 
+    /// Show dialogs with `backend` instead of the platform's backend. It runs on the main thread in
+    /// place of the platform's event loop; see [`XDialogBackend`]. The backend must be `Send` and
+    /// `Sync`, so the builder stays so.
+    pub fn with_backend<B: XDialogBackend + Send + Sync + 'static>(mut self, backend: B) -> XDialogBuilder {
+        self.backend = Some(Box::new(backend));
+        self
+    }
+
+    /// Answer dialogs with `policy` without showing them, eg. from an `--unattended` command line
+    /// flag. Without this, the `XDIALOG_AUTO_ANSWER` environment variable is used; see
+    /// [`XDialogAutoAnswer`].
+    pub fn with_auto_answer(mut self, policy: XDialogAutoAnswer) -> XDialogBuilder {
+        self.auto_answer = Some(policy);
+        self
+    }
+
     /// Run with no return value. This is the simplest way to use xdialog when your application
     /// logic does not need to return an exit code or result.
     ///
     /// This function will block the main thread and run the specified `main` function in a
-    /// separate thread.
-    pub fn run(self, main: fn()) {
+    /// separate thread. `main` can be a function or a closure which captures (and so moves to that
+    /// thread) whatever it needs, eg. parsed command line arguments.
+    pub fn run<F: FnOnce() + Send + 'static>(self, main: F) {
         self.run_loop(main);
     }
 
@@ -43,7 +72,7 @@ impl XDialogBuilder {
     ///
     /// This function will block the main thread and run the specified `main` function in a
     /// separate thread.
-    pub fn run_i32(self, main: fn() -> i32) -> i32 {
+    pub fn run_i32<F: FnOnce() -> i32 + Send + 'static>(self, main: F) -> i32 {
         self.run_loop(main)
     }
 
@@ -52,19 +81,57 @@ impl XDialogBuilder {
     ///
     /// This function will block the main thread and run the specified `main` function in a
     /// separate thread.
-    pub fn run_result<T: Send + 'static, E: Send + 'static>(self, main: fn() -> Result<T, E>) -> Result<T, E> {
+    pub fn run_result<T: Send + 'static, E: Send + 'static>(self, main: impl FnOnce() -> Result<T, E> + Send + 'static) -> Result<T, E> {
         self.run_loop(main)
     }
 
+    /// Run with startup context (eg. parsed command line arguments or configuration), which is
+    /// moved to the separate thread and passed to `main`, returning whatever `main` returns. This
+    /// lets `main` be a plain function taking the context, rather than a closure capturing it.
+    ///
+    /// This function will block the main thread and run the specified `main` function in a
+    /// separate thread.
+    ///
+    /// ```no_run
+    /// use xdialog::*;
+    ///
+    /// struct Args {
+    ///     product: String,
+    /// }
+    ///
+    /// fn main() {
+    ///     let args = Args { product: std::env::args().nth(1).unwrap_or("My App".to_string()) };
+    ///     let code = XDialogBuilder::new().run_with_context(args, run);
+    ///     std::process::exit(code);
+    /// }
+    ///
+    /// fn run(args: Args) -> i32 {
+    ///     show_message_info_ok(&args.product, "Installed", "The update was installed.").unwrap();
+    ///     0
+    /// }
+    /// ```
+    pub fn run_with_context<C, T, F>(self, context: C, main: F) -> T
+    where
+        C: Send + 'static,
+        T: Send + 'static,
+        F: FnOnce(C) -> T + Send + 'static,
+    {
+        self.run_loop(move || main(context))
+    }
+
     /// Run the XDialog library with the specified configuration, returning an arbitrary type.
     /// For most use cases, prefer [`run`](Self::run), [`run_i32`](Self::run_i32), or
-    /// [`run_result`](Self::run_result) instead.
+    /// [`run_result`](Self::run_result) instead, or [`run_with_context`](Self::run_with_context) to
+    /// pass startup context to `main`.
     ///
     /// This function will block the main thread and run the specified `main` function in a
     /// separate thread.
-    pub fn run_loop<T: Send + 'static>(self, main: fn() -> T) -> T {
+    pub fn run_loop<T: Send + 'static>(self, main: impl FnOnce() -> T + Send + 'static) -> T {
         let (send_message, receive_message) = channel::<DialogMessageRequest>();
         crate::channel::init_handler(Box::new(ChannelHandler { sender: send_message }));
+        if let Some(policy) = &self.auto_answer {
+            set_auto_answer(Some(policy.clone()));
+        }
 
         let result = thread::spawn(move || {
             let result = main();
@@ -72,8 +139,10 @@ impl XDialogBuilder {
             result
         });
 
-        let backend_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            Self::run_default_backend(receive_message, self.theme);
+        let theme = self.theme;
+        let backend_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || match self.backend {
+            Some(mut backend) => backend.run_loop(receive_message, theme),
+            None => Self::run_default_backend(receive_message, theme),
         }));
 
         if let Err(e) = backend_result {
@@ -103,3 +172,17 @@ impl XDialogBuilder {
         crate::backends::skia::SkiaBackend::run_loop(receiver, theme);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_is_send_and_sync_and_keeps_its_turbofish() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<XDialogBuilder>();
+        // The result types can still be named explicitly, as before closures were accepted.
+        let _ = |builder: XDialogBuilder| builder.run_loop::<i32>(|| 0);
+        let _ = |builder: XDialogBuilder| builder.run_result::<(), String>(|| Ok(()));
+    }
+}
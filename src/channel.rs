@@ -3,13 +3,6 @@ use std::sync::OnceLock;
 
 use crate::*;
 
-/// Trait for dispatching dialog requests to a backend.
-/// Implementations must be thread-safe (`Send + Sync`).
-pub trait DialogRequestHandler: Send + Sync {
-    /// Send a dialog message request to the backend.
-    fn send(&self, message: DialogMessageRequest) -> Result<(), XDialogError>;
-}
-
 static REQUEST_HANDLER: OnceLock<Box<dyn DialogRequestHandler>> = OnceLock::new();
 
 pub fn init_handler(handler: Box<dyn DialogRequestHandler>) {
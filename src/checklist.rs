@@ -0,0 +1,109 @@
+/// The state of one step of a progress dialog's checklist (see [`XDialogOptions::checklist`]).
+///
+/// [`XDialogOptions::checklist`]: crate::XDialogOptions::checklist
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ChecklistStepState {
+    /// Not started yet
+    #[default]
+    Pending,
+    /// In progress
+    Running,
+    /// Finished successfully
+    Done,
+    /// Stopped by an error
+    Failed,
+}
+
+impl ChecklistStepState {
+    /// The symbol marking a step in this state where the checklist is shown as text.
+    #[cfg_attr(target_os = "linux", allow(dead_code))] // the skia backend draws the markers
+    fn marker(self) -> char {
+        match self {
+            ChecklistStepState::Pending => '○',
+            ChecklistStepState::Running => '▶',
+            ChecklistStepState::Done => '✓',
+            ChecklistStepState::Failed => '✗',
+        }
+    }
+}
+
+/// The steps of a checklist and their states, kept by each backend for an open progress dialog.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Checklist {
+    steps: Vec<(String, ChecklistStepState)>,
+}
+
+impl Checklist {
+    /// A checklist of `labels`, all pending.
+    pub fn new(labels: &[String]) -> Self {
+        Checklist { steps: labels.iter().map(|label| (label.clone(), ChecklistStepState::Pending)).collect() }
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub fn steps(&self) -> &[(String, ChecklistStepState)] {
+        &self.steps
+    }
+
+    /// Set the state of the step at `index`. Returns whether anything changed.
+    pub fn set(&mut self, index: usize, state: ChecklistStepState) -> bool {
+        match self.steps.get_mut(index) {
+            Some((_, current)) if *current != state => {
+                *current = state;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark the step at `index` running, and the steps before it that are pending or running done.
+    /// Failed steps stay failed. Returns whether anything changed.
+    pub fn start(&mut self, index: usize) -> bool {
+        if index >= self.steps.len() {
+            return false;
+        }
+        let mut changed = false;
+        for i in 0..index {
+            if matches!(self.steps[i].1, ChecklistStepState::Pending | ChecklistStepState::Running) {
+                changed |= self.set(i, ChecklistStepState::Done);
+            }
+        }
+        self.set(index, ChecklistStepState::Running) || changed
+    }
+
+    /// The checklist as text for backends which can't draw it, one step per line: eg. "✓ Download".
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    pub fn to_text(&self) -> String {
+        self.steps.iter().map(|(label, state)| format!("{} {}", state.marker(), label)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checklist() -> Checklist {
+        Checklist::new(&["Download".to_string(), "Verify".to_string(), "Extract".to_string()])
+    }
+
+    #[test]
+    fn starting_a_step_finishes_the_ones_before_it() {
+        let mut list = checklist();
+        assert!(list.start(0));
+        assert!(!list.start(0));
+        assert!(list.set(1, ChecklistStepState::Failed));
+        assert!(list.start(2));
+        assert_eq!(
+            list.steps().iter().map(|(_, state)| *state).collect::<Vec<_>>(),
+            [ChecklistStepState::Done, ChecklistStepState::Failed, ChecklistStepState::Running]
+        );
+        assert!(!list.start(3));
+        assert!(!list.set(3, ChecklistStepState::Done));
+    }
+
+    #[test]
+    fn text_marks_each_step() {
+        let mut list = checklist();
+        list.start(1);
+        assert_eq!(list.to_text(), "✓ Download\n▶ Verify\n○ Extract");
+    }
+}
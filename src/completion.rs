@@ -0,0 +1,166 @@
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::*;
+
+/// The response of a dialog whose caller doesn't block on it (eg. a progress dialog), filled in when
+/// the dialog closes and observable from any thread or task afterwards.
+pub(crate) struct DialogCompletion {
+    state: Mutex<CompletionState>,
+    closed: Condvar,
+    /// The dialog's result channel, until the response arrives. It holds a [`ReceiverWaker`], so the
+    /// response is taken as soon as it's sent rather than by a thread blocking on the channel.
+    receiver: Mutex<Option<oneshot::AsyncReceiver<XDialogResponse>>>,
+}
+
+#[derive(Default)]
+struct CompletionState {
+    response: Option<XDialogResponse>,
+    /// Set by [`DialogCompletion::request_close`] before the caller asks the backend to close the dialog.
+    close_requested: bool,
+    /// Whether `close_requested` was already set when the response arrived.
+    closed_by_request: bool,
+    #[cfg(feature = "async")]
+    wakers: Vec<Waker>,
+}
+
+impl DialogCompletion {
+    /// Start watching the result `receiver` of a dialog. A backend that drops the sender without a
+    /// result counts as the window closing.
+    pub fn watch(receiver: oneshot::Receiver<XDialogResponse>) -> Arc<Self> {
+        let completion = Self::pending();
+        *completion.receiver.lock().unwrap_or_else(|e| e.into_inner()) = Some(receiver.into_future());
+        completion.poll_receiver();
+        completion
+    }
+
+    /// A completion with no dialog behind it, completed only by calling [`complete`](Self::complete).
+    pub fn pending() -> Arc<Self> {
+        Arc::new(DialogCompletion { state: Mutex::new(CompletionState::default()), closed: Condvar::new(), receiver: Mutex::new(None) })
+    }
+
+    /// Complete with the response if it has arrived, or else leave a waker in the channel which calls
+    /// this again once it does (or the sender is dropped).
+    fn poll_receiver(self: &Arc<Self>) {
+        let mut receiver = self.receiver.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(channel) = receiver.as_mut() else {
+            return;
+        };
+        let waker = Waker::from(Arc::new(ReceiverWaker(Arc::downgrade(self))));
+        let Poll::Ready(result) = Pin::new(channel).poll(&mut Context::from_waker(&waker)) else {
+            return;
+        };
+        *receiver = None;
+        drop(receiver);
+        self.complete(result.unwrap_or_else(|_| XDialogResult::WindowClosed.into()));
+    }
+
+    /// Record the response of the dialog and wake everyone waiting for it. Only the first response counts.
+    pub fn complete(&self, response: XDialogResponse) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.response.is_some() {
+            return;
+        }
+        state.response = Some(response);
+        state.closed_by_request = state.close_requested;
+        #[cfg(feature = "async")]
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+        self.closed.notify_all();
+    }
+
+    /// Record that the caller is closing the dialog, so a response arriving afterwards is attributed
+    /// to it rather than to the user. Has no effect once the dialog has closed.
+    pub fn request_close(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.close_requested = true;
+    }
+
+    /// The response, and whether the close was requested by the caller, if the dialog has closed.
+    pub fn try_response(&self) -> Option<(XDialogResponse, bool)> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.response.clone().map(|r| (r, state.closed_by_request))
+    }
+
+    /// Block the current thread until the dialog closes and return its response.
+    pub fn wait(&self) -> XDialogResponse {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let state = self.closed.wait_while(state, |s| s.response.is_none()).unwrap_or_else(|e| e.into_inner());
+        state.response.clone().unwrap_or_else(|| XDialogResult::WindowClosed.into())
+    }
+
+    /// Wait until the dialog closes and return its response.
+    #[cfg(feature = "async")]
+    pub async fn wait_async(&self) -> XDialogResponse {
+        std::future::poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            match state.response.as_ref() {
+                Some(response) => Poll::Ready(response.clone()),
+                None => {
+                    if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                        state.wakers.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+}
+
+/// The waker left in a dialog's result channel. It holds the completion weakly, so a dialog nobody
+/// is watching any more doesn't keep its completion alive.
+struct ReceiverWaker(Weak<DialogCompletion>);
+
+impl Wake for ReceiverWaker {
+    fn wake(self: Arc<Self>) {
+        if let Some(completion) = self.0.upgrade() {
+            completion.poll_receiver();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_is_attributed_to_the_caller_only_when_requested_first() {
+        let user_closed = DialogCompletion::pending();
+        assert!(user_closed.try_response().is_none());
+        user_closed.complete(XDialogResult::WindowClosed.into());
+        user_closed.request_close();
+        assert_eq!(user_closed.try_response(), Some((XDialogResult::WindowClosed.into(), false)));
+
+        let caller_closed = DialogCompletion::pending();
+        caller_closed.request_close();
+        caller_closed.complete(XDialogResult::WindowClosed.into());
+        assert_eq!(caller_closed.wait(), XDialogResult::WindowClosed.into());
+        assert_eq!(caller_closed.try_response().map(|(_, by_request)| by_request), Some(true));
+    }
+
+    #[test]
+    fn watched_channels_complete_when_answered_or_dropped() {
+        let (sender, receiver) = oneshot::channel();
+        let answered = DialogCompletion::watch(receiver);
+        assert!(answered.try_response().is_none());
+        let _ = std::thread::spawn(move || sender.send(XDialogResult::ButtonPressed(0).into())).join();
+        assert_eq!(answered.try_response(), Some((XDialogResult::ButtonPressed(0).into(), false)));
+
+        let (sender, receiver) = oneshot::channel::<XDialogResponse>();
+        let dropped = DialogCompletion::watch(receiver);
+        drop(sender);
+        assert_eq!(dropped.wait().result, XDialogResult::WindowClosed);
+    }
+
+    #[test]
+    fn only_the_first_response_counts() {
+        let completion = DialogCompletion::pending();
+        completion.complete(XDialogResult::ButtonPressed(1).into());
+        completion.complete(XDialogResult::WindowClosed.into());
+        assert_eq!(completion.wait().result, XDialogResult::ButtonPressed(1));
+    }
+}
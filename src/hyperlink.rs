@@ -0,0 +1,192 @@
+use std::ops::Range;
+
+use crate::*;
+
+/// A hyperlink found in a dialog message by [`parse_links`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MessageLink {
+    /// Byte range of the link's visible text within the plain message text.
+    pub range: Range<usize>,
+    pub href: String,
+}
+
+/// Split `message` into its plain text and the `<a href="...">text</a>` links it contains (the
+/// markup Windows task dialogs use natively). Malformed tags are left in the text as written, and
+/// a link with no text shows its href instead.
+pub(crate) fn parse_links(message: &str) -> (String, Vec<MessageLink>) {
+    let mut text = String::with_capacity(message.len());
+    let mut links = Vec::new();
+    let mut rest = message;
+    while let Some(start) = rest.find('<') {
+        let Some((href, label, len)) = parse_anchor(&rest[start..]) else {
+            text.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+            continue;
+        };
+        text.push_str(&rest[..start]);
+        let from = text.len();
+        text.push_str(if label.is_empty() { href } else { label });
+        links.push(MessageLink { range: from..text.len(), href: href.to_string() });
+        rest = &rest[start + len..];
+    }
+    text.push_str(rest);
+    (text, links)
+}
+
+/// Parse one anchor at the start of `s`, returning its href, its text and its length in bytes.
+fn parse_anchor(s: &str) -> Option<(&str, &str, usize)> {
+    if !s.get(..2)?.eq_ignore_ascii_case("<a") || !s[2..].starts_with(char::is_whitespace) {
+        return None;
+    }
+    let attrs = s[2..].trim_start();
+    if !attrs.get(..5)?.eq_ignore_ascii_case("href=") {
+        return None;
+    }
+    let quote = attrs[5..].chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &attrs[6..];
+    let href_end = value.find(quote)?;
+    let after = value[href_end + 1..].trim_start();
+    let body = after.strip_prefix('>')?;
+    // ASCII lowercasing keeps byte offsets, so the index is valid in `body` too.
+    let label_end = body.to_ascii_lowercase().find("</a>")?;
+    let len = s.len() - body.len() + label_end + 4;
+    Some((&value[..href_end], &body[..label_end], len))
+}
+
+/// The URL schemes opened with the system's default handler. Any other link (eg. `file:`, or a
+/// custom scheme registered by another application) only goes to an application's own handler, so
+/// a message built from untrusted text can't launch arbitrary programs.
+const SYSTEM_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Whether `href` has one of the [`SYSTEM_SCHEMES`], ignoring ASCII case.
+fn opens_with_system(href: &str) -> bool {
+    match href.split_once(':') {
+        Some((scheme, _)) => SYSTEM_SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme)),
+        None => false,
+    }
+}
+
+/// Open a clicked link: hand it to the application's handler when one is set (see
+/// [`set_hyperlink_handler`]), otherwise open `http`, `https` and `mailto` links with the system's
+/// default handler, and ignore the rest.
+pub(crate) fn open_link(href: &str) {
+    if let Some(handler) = get_link_handler() {
+        handler(href);
+        return;
+    }
+    if !opens_with_system(href) {
+        warn!("xdialog: not opening link {} (only http, https and mailto links open without a handler)", href);
+        return;
+    }
+    // The system opener can block (a D-Bus round trip, or waiting on a child process), so keep it
+    // off the UI thread.
+    let href = href.to_string();
+    std::thread::spawn(move || {
+        if let Err(e) = open_with_system(&href) {
+            warn!("xdialog: failed to open link {}: {}", href, e);
+        }
+    });
+}
+
+/// Open through the XDG desktop portal's `OpenURI` interface, which also works inside Flatpak and
+/// Snap sandboxes, falling back to `xdg-open`.
+#[cfg(target_os = "linux")]
+fn open_with_system(href: &str) -> Result<(), String> {
+    if open_with_portal(href).is_some() {
+        return Ok(());
+    }
+    run_opener("xdg-open", &[], href)
+}
+
+#[cfg(target_os = "linux")]
+fn open_with_portal(href: &str) -> Option<()> {
+    use std::collections::HashMap;
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::{OwnedObjectPath, Value};
+
+    let conn = Connection::session().ok()?;
+    let proxy = Proxy::new(
+        &conn,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.OpenURI",
+    )
+    .ok()?;
+    let options: HashMap<&str, Value> = HashMap::new();
+    proxy.call::<_, _, OwnedObjectPath>("OpenURI", &("", href, options)).ok()?;
+    Some(())
+}
+
+#[cfg(target_os = "macos")]
+fn open_with_system(href: &str) -> Result<(), String> {
+    run_opener("open", &[], href)
+}
+
+#[cfg(windows)]
+fn open_with_system(href: &str) -> Result<(), String> {
+    run_opener("rundll32", &["url.dll,FileProtocolHandler"], href)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn open_with_system(_href: &str) -> Result<(), String> {
+    Err("no system link handler on this platform".to_string())
+}
+
+/// Run `program` with `args` and then `href`, which is passed as a single argument (never through
+/// a shell). Refuses hrefs which the program would parse as an option.
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn run_opener(program: &str, args: &[&str], href: &str) -> Result<(), String> {
+    if href.starts_with('-') {
+        return Err("refusing to open a link starting with '-'".to_string());
+    }
+    let status = std::process::Command::new(program).args(args).arg(href).status().map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_passes_through() {
+        let (text, links) = parse_links("No links here, but a < and a <b>tag</b>.");
+        assert_eq!(text, "No links here, but a < and a <b>tag</b>.");
+        assert!(links.is_empty());
+    }
+
+    #[test]
+    fn anchors_become_ranges() {
+        let (text, links) = parse_links("See <a href=\"https://velopack.io\">the docs</a> or <A HREF='mailto:x@y.z'>mail</A> us.");
+        assert_eq!(text, "See the docs or mail us.");
+        assert_eq!(links.len(), 2);
+        assert_eq!(&text[links[0].range.clone()], "the docs");
+        assert_eq!(links[0].href, "https://velopack.io");
+        assert_eq!(&text[links[1].range.clone()], "mail");
+        assert_eq!(links[1].href, "mailto:x@y.z");
+    }
+
+    #[test]
+    fn empty_label_shows_href_and_malformed_tags_stay() {
+        let (text, links) = parse_links("ü <a href=\"https://a.b\"></a> <a href=\"x\">open");
+        assert_eq!(text, "ü https://a.b <a href=\"x\">open");
+        assert_eq!(&text[links[0].range.clone()], "https://a.b");
+        assert_eq!(links.len(), 1);
+    }
+
+    #[test]
+    fn only_web_and_mail_links_open_with_the_system() {
+        assert!(opens_with_system("https://velopack.io"));
+        assert!(opens_with_system("HTTP://velopack.io"));
+        assert!(opens_with_system("mailto:x@y.z"));
+        assert!(!opens_with_system("file:///etc/passwd"));
+        assert!(!opens_with_system("ms-settings:windowsupdate"));
+        assert!(!opens_with_system("C:\\Windows\\System32\\calc.exe"));
+        assert!(!opens_with_system("javascript:alert(1)"));
+        assert!(!opens_with_system("velopack.io"));
+        assert!(!opens_with_system("-https://velopack.io"));
+    }
+}
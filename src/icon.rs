@@ -0,0 +1,89 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::XDialogError;
+
+/// The image of an [`XDialogIcon::Custom`](crate::XDialogIcon::Custom) icon, eg. a product logo.
+///
+/// The image is scaled to the size of the icon it replaces, keeping its aspect ratio, so a square
+/// image at least 64 pixels across (or an SVG) looks best. Cloning is cheap: the image data is shared.
+///
+/// Support differs by backend: the skia backend (Linux) draws all three formats, and AppKit shows
+/// PNG and RGBA images, and SVG from macOS 14. The Win32 backend shows PNG and RGBA images, but a
+/// dialog which opened with a standard main icon can't switch to a custom one. The `maccf-direct`
+/// backend can't show custom images. Where a backend can't show an image, it shows the information
+/// icon instead.
+#[derive(Clone, Eq, PartialEq)]
+pub struct IconData(IconSource);
+
+/// The encoded form of an [`IconData`], which each backend decodes itself.
+#[derive(Clone, Eq, PartialEq)]
+pub(crate) enum IconSource {
+    /// Straight (not premultiplied) RGBA pixels, row by row from the top.
+    Rgba { width: u32, height: u32, pixels: Arc<[u8]> },
+    Png(Arc<[u8]>),
+    Svg(Arc<str>),
+}
+
+impl IconData {
+    /// An icon from `width` × `height` pixels of straight (not premultiplied) RGBA, 4 bytes per
+    /// pixel, row by row from the top. Fails if `pixels` is not exactly that long or the image is empty.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, XDialogError> {
+        let expected = width as usize * height as usize * 4;
+        if expected == 0 || pixels.len() != expected {
+            return Err(XDialogError::InvalidIcon(format!(
+                "{}x{} RGBA needs {} bytes, got {}",
+                width,
+                height,
+                expected,
+                pixels.len()
+            )));
+        }
+        Ok(IconData(IconSource::Rgba { width, height, pixels: pixels.into() }))
+    }
+
+    /// An icon from the bytes of a PNG file, eg. `include_bytes!("logo.png")`. The image is decoded
+    /// when the dialog is shown; one which can't be decoded is logged and no icon is drawn.
+    pub fn from_png(bytes: impl Into<Vec<u8>>) -> Self {
+        IconData(IconSource::Png(bytes.into().into()))
+    }
+
+    /// An icon from an SVG document, eg. `include_str!("logo.svg")`. The skia backend draws it with
+    /// `resvg`, without text or embedded raster images.
+    pub fn from_svg(svg: impl Into<String>) -> Self {
+        IconData(IconSource::Svg(svg.into().into()))
+    }
+
+    pub(crate) fn source(&self) -> &IconSource {
+        &self.0
+    }
+}
+
+impl fmt::Debug for IconData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The image data itself is too long to be useful in a debug print.
+        match &self.0 {
+            IconSource::Rgba { width, height, .. } => write!(f, "IconData::Rgba({}x{})", width, height),
+            IconSource::Png(bytes) => write!(f, "IconData::Png({} bytes)", bytes.len()),
+            IconSource::Svg(text) => write!(f, "IconData::Svg({} bytes)", text.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgba_must_cover_every_pixel() {
+        assert!(IconData::from_rgba(2, 2, vec![0; 16]).is_ok());
+        assert!(IconData::from_rgba(2, 2, vec![0; 12]).is_err());
+        assert!(IconData::from_rgba(0, 0, vec![]).is_err());
+    }
+
+    #[test]
+    fn debug_prints_the_size_not_the_data() {
+        assert_eq!(format!("{:?}", IconData::from_rgba(1, 2, vec![0; 8]).unwrap()), "IconData::Rgba(1x2)");
+        assert_eq!(format!("{:?}", IconData::from_png(vec![0; 3])), "IconData::Png(3 bytes)");
+    }
+}
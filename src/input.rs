@@ -0,0 +1,134 @@
+use crate::*;
+
+/// Shows a dialog with a single-line text field below the message and blocks until the user closes
+/// it. The field starts with `initial_text` selected, so typing replaces it.
+///
+/// Returns an [`XDialogResponse`] whose `result` is the button that closed the dialog and whose
+/// `text` holds the field contents at that moment. Pressing Enter in the field activates the
+/// affirmative button: `options.default_button` if set, otherwise the last entry in `options.buttons`.
+///
+/// In silent mode no dialog is shown and the response carries `XDialogResult::SilentMode` and the
+/// unchanged `initial_text`.
+///
+/// The Win32 TaskDialog and `maccf-direct` backends have no text field, so with those this returns
+/// [`XDialogError::SystemError`].
+///
+/// ### Example
+/// ```rust,no_run
+/// use xdialog::*;
+///
+/// # fn run() {
+/// let response = show_input(
+///     XDialogOptions {
+///         title: "Proxy".to_string(),
+///         main_instruction: "Proxy server".to_string(),
+///         message: "Enter the host name of your proxy server.".to_string(),
+///         icon: XDialogIcon::Information,
+///         buttons: vec!["Cancel".to_string(), "OK".to_string()],
+///         ..Default::default()
+///     },
+///     "proxy.example.com",
+/// ).unwrap();
+///
+/// if response.result == XDialogResult::ButtonPressed(1) {
+///     println!("proxy: {}", response.text.unwrap_or_default());
+/// }
+/// # }
+/// ```
+pub fn show_input<P: AsRef<str>>(options: XDialogOptions, initial_text: P) -> Result<XDialogResponse, XDialogError> {
+    show_input_internal(options, InputField { initial_text: initial_text.as_ref().to_string(), validator: None, masked: false })
+}
+
+/// Like [`show_input`], but with a callback which validates the field contents.
+///
+/// The callback runs on the backend thread every time the text changes. Returning `Err(message)`
+/// disables the affirmative button and shows `message` as an inline error below the field;
+/// returning `Ok(())` enables the button and clears the error. The other buttons (eg. "Cancel") are
+/// never blocked.
+pub fn show_input_with_validation<P, F>(options: XDialogOptions, initial_text: P, validate: F) -> Result<XDialogResponse, XDialogError>
+where
+    P: AsRef<str>,
+    F: FnMut(&str) -> Result<(), String> + Send + 'static,
+{
+    let field = InputField { initial_text: initial_text.as_ref().to_string(), validator: Some(Box::new(validate)), masked: false };
+    show_input_internal(options, field)
+}
+
+/// Shows a dialog with an empty, masked password field below the message and blocks until the
+/// user closes it.
+///
+/// Returns an [`XDialogResponse`] whose `result` is the button that closed the dialog and whose
+/// `secret` holds the entered password. The secret is never placed in `text`, never passes
+/// through the request channel, and is wiped from memory when the [`XDialogSecret`] is dropped.
+///
+/// The field shows one bullet per character and refuses to copy or cut its contents (pasting
+/// works). On Linux a toggle at the end of the field reveals the password while editing; macOS
+/// uses the native secure text field, which has no such toggle.
+///
+/// In silent mode no dialog is shown and the response carries `XDialogResult::SilentMode` and no
+/// secret. Like [`show_input`], this is unsupported by the Win32 TaskDialog and `maccf-direct`
+/// backends.
+pub fn show_password(options: XDialogOptions) -> Result<XDialogResponse, XDialogError> {
+    show_input_internal(options, InputField { initial_text: String::new(), validator: None, masked: true })
+}
+
+fn show_input_internal(options: XDialogOptions, field: InputField) -> Result<XDialogResponse, XDialogError> {
+    if get_silent() {
+        let text = (!field.masked).then_some(field.initial_text);
+        return Ok(XDialogResponse { text, ..options.silent_response() });
+    }
+    if let Some(response) = auto_answer(&options) {
+        let text = (!field.masked).then_some(field.initial_text);
+        return Ok(XDialogResponse { text, ..response });
+    }
+
+    let id = get_next_id();
+    let (creation_sender, creation_receiver) = oneshot::channel();
+    send_request(DialogMessageRequest::ShowInputWindow(id, options, field, creation_sender))?;
+    crate::message::wait_for_response(id, creation_receiver, None)
+}
+
+/// The boxed closure type behind [`InputField`] validation.
+type InputValidatorFn = Box<dyn FnMut(&str) -> Result<(), String> + Send + 'static>;
+
+/// The text field of an input dialog: its initial contents, optional validation callback and
+/// whether it is a masked password field. This type is public because it appears in
+/// [`DialogMessageRequest`], for custom backends to read; callers use [`show_input`],
+/// [`show_input_with_validation`] or [`show_password`] rather than constructing this directly.
+pub struct InputField {
+    pub(crate) initial_text: String,
+    pub(crate) validator: Option<InputValidatorFn>,
+    pub(crate) masked: bool,
+}
+
+impl InputField {
+    /// The text the field starts with.
+    pub fn initial_text(&self) -> &str {
+        &self.initial_text
+    }
+
+    /// Whether the field is a masked password field, whose contents are reported in
+    /// [`XDialogResponse::secret`] rather than [`XDialogResponse::text`].
+    pub fn is_masked(&self) -> bool {
+        self.masked
+    }
+
+    /// Runs the validation callback (if any) against `text`. A backend shows the error message
+    /// and keeps the dialog open when it fails.
+    pub fn validate(&mut self, text: &str) -> Result<(), String> {
+        match self.validator.as_mut() {
+            Some(validate) => validate(text),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for InputField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputField")
+            .field("initial_text", &self.initial_text)
+            .field("validator", &self.validator.as_ref().map(|_| "<callback>"))
+            .field("masked", &self.masked)
+            .finish()
+    }
+}
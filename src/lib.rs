@@ -50,6 +50,10 @@
 //!
 //!   // Or run_result() for Result-based error handling:
 //!   // let result = XDialogBuilder::new().run_result(your_main_logic_result);
+//!
+//!   // Each of these also takes a closure, and run_with_context() passes startup context (eg.
+//!   // parsed arguments) to a function:
+//!   // let code = XDialogBuilder::new().run_with_context(args, your_main_logic_with_args);
 //! }
 //!
 //! fn your_main_logic() {
@@ -103,6 +107,9 @@
 //! - **Linux**: Pure Rust software renderer using winit + tiny-skia + cosmic-text. No
 //!   C/C++ build dependencies, works with static musl linking, and embeds its own font (Ubuntu),
 //!   falling back to system fonts for glyphs it doesn't cover (CJK, emoji, …).
+//! - **Custom**: Implement [`XDialogBackend`] and pass it to [`XDialogBuilder::with_backend`], or
+//!   implement [`DialogRequestHandler`] and install it with [`init_with_handler`], to show dialogs
+//!   in your own UI.
 //! - **Headless Linux**: When no X11 or Wayland display server is available, all dialog functions
 //!   return [`XDialogError::NoBackendAvailable`]. The application continues running without panicking.
 //!
@@ -112,10 +119,19 @@
 #[macro_use]
 extern crate log;
 
+pub use auto_answer::*;
+pub use backend::*;
+pub use checklist::*;
+pub use icon::*;
+pub use input::*;
 pub use message::*;
+#[cfg(feature = "async")]
+pub use message_async::*;
 pub use model::*;
 pub use progress::*;
+pub use secret::*;
 use state::*;
+pub use tracker::*;
 
 mod backends;
 
@@ -134,10 +150,23 @@ pub use backends::win32_direct::init_win32_direct;
 #[cfg(all(target_os = "macos", feature = "maccf-direct"))]
 pub use backends::maccf_direct::init_maccf_direct;
 
+mod auto_answer;
+mod backend;
+mod checklist;
+mod completion;
+mod hyperlink;
+mod icon;
+mod input;
 mod message;
+#[cfg(feature = "async")]
+mod message_async;
 mod model;
 mod progress;
+mod secret;
 mod state;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tracker;
 
 /// Set the silent mode for the dialog. When silent mode is enabled, all dialog functions will
 /// return `XDialogResult::SilentMode` without showing any dialogs.
@@ -145,8 +174,25 @@ pub fn set_silent_mode(silent: bool) {
     set_silent(silent);
 }
 
+/// Set the handler for hyperlinks clicked in dialog messages (see [`XDialogOptions::message`]). It
+/// receives the link's `href` and is called on the UI thread, so it should return quickly. Without
+/// a handler, `http`, `https` and `mailto` links are opened with the system's default handler
+/// (through the XDG desktop portal or `xdg-open` on Linux), and links with any other scheme do
+/// nothing; a handler receives every link, whatever its scheme.
+pub fn set_hyperlink_handler<F: Fn(&str) + Send + Sync + 'static>(handler: F) {
+    set_link_handler(Some(std::sync::Arc::new(handler)));
+}
+
+/// Remove the handler set with [`set_hyperlink_handler`], so links open with the system's default
+/// handler again.
+pub fn clear_hyperlink_handler() {
+    set_link_handler(None);
+}
+
+/// The errors returned by the dialog functions. Variants may be added in minor releases.
 #[allow(missing_docs)]
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum XDialogError {
     #[error("xdialog backend not initialized")]
     NotInitialized,
@@ -158,4 +204,8 @@ pub enum XDialogError {
     SystemError(String),
     #[error("no display server available (X11 or Wayland required)")]
     NoBackendAvailable,
+    #[error("invalid icon data: {0}")]
+    InvalidIcon(String),
+    #[error("invalid auto-answer policy: {0}")]
+    InvalidAutoAnswer(String),
 }
@@ -8,7 +8,7 @@ pub fn show_message_info_ok<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     main_instruction: P2,
     message: P3,
 ) -> Result<(), XDialogError> {
-    show_message_internal(XDialogIcon::Information, window_title, main_instruction, message, vec!["OK".to_string()])?;
+    show_message_internal(XDialogIcon::Information, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0)?;
     Ok(())
 }
 
@@ -18,7 +18,7 @@ pub fn show_message_warn_ok<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     main_instruction: P2,
     message: P3,
 ) -> Result<(), XDialogError> {
-    show_message_internal(XDialogIcon::Warning, window_title, main_instruction, message, vec!["OK".to_string()])?;
+    show_message_internal(XDialogIcon::Warning, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0)?;
     Ok(())
 }
 
@@ -28,7 +28,7 @@ pub fn show_message_error_ok<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     main_instruction: P2,
     message: P3,
 ) -> Result<(), XDialogError> {
-    show_message_internal(XDialogIcon::Error, window_title, main_instruction, message, vec!["OK".to_string()])?;
+    show_message_internal(XDialogIcon::Error, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0)?;
     Ok(())
 }
 
@@ -40,7 +40,8 @@ pub fn show_message_ok_cancel<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     message: P3,
     icon: XDialogIcon,
 ) -> Result<bool, XDialogError> {
-    let result = show_message_internal(icon, window_title, main_instruction, message, vec!["Cancel".to_string(), "OK".to_string()])?;
+    let buttons = vec!["Cancel".to_string(), "OK".to_string()];
+    let result = show_message_internal(icon, window_title, main_instruction, message, buttons, 1, 0)?;
     Ok(result == XDialogResult::ButtonPressed(1))
 }
 
@@ -52,7 +53,8 @@ pub fn show_message_yes_no<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     message: P3,
     icon: XDialogIcon,
 ) -> Result<bool, XDialogError> {
-    let result = show_message_internal(icon, window_title, main_instruction, message, vec!["No".to_string(), "Yes".to_string()])?;
+    let buttons = vec!["No".to_string(), "Yes".to_string()];
+    let result = show_message_internal(icon, window_title, main_instruction, message, buttons, 1, 0)?;
     Ok(result == XDialogResult::ButtonPressed(1))
 }
 
@@ -64,7 +66,8 @@ pub fn show_message_retry_cancel<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>
     message: P3,
     icon: XDialogIcon,
 ) -> Result<bool, XDialogError> {
-    let result = show_message_internal(icon, window_title, main_instruction, message, vec!["Cancel".to_string(), "Retry".to_string()])?;
+    let buttons = vec!["Cancel".to_string(), "Retry".to_string()];
+    let result = show_message_internal(icon, window_title, main_instruction, message, buttons, 1, 0)?;
     Ok(result == XDialogResult::ButtonPressed(1))
 }
 
@@ -74,34 +77,184 @@ fn show_message_internal<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
     main_instruction: P2,
     message: P3,
     buttons: Vec<String>,
+    default_button: usize,
+    cancel_button: usize,
 ) -> Result<XDialogResult, XDialogError> {
-    let data = XDialogOptions {
+    let data = helper_options(icon, window_title, main_instruction, message, buttons, default_button, cancel_button);
+    show_message(data, None)
+}
+
+/// The options behind the `show_message_*` helpers.
+pub(crate) fn helper_options<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    icon: XDialogIcon,
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+    buttons: Vec<String>,
+    default_button: usize,
+    cancel_button: usize,
+) -> XDialogOptions {
+    XDialogOptions {
         title: window_title.as_ref().to_string(),
         main_instruction: main_instruction.as_ref().to_string(),
         message: message.as_ref().to_string(),
         icon,
         buttons,
-    };
-    show_message(data, None)
+        default_button: Some(default_button),
+        cancel_button: Some(cancel_button),
+        ..Default::default()
+    }
 }
 
 /// Shows a message box with the specified options and blocks until the user closes it or the timeout occurs.
 pub fn show_message(options: XDialogOptions, timeout: Option<Duration>) -> Result<XDialogResult, XDialogError> {
+    Ok(show_message_ex(options, timeout)?.result)
+}
+
+/// Like [`show_message`], but returns the full [`XDialogResponse`], which also carries the final
+/// state of the verification checkbox (see [`XDialogOptions::verification_text`]).
+pub fn show_message_ex(options: XDialogOptions, timeout: Option<Duration>) -> Result<XDialogResponse, XDialogError> {
     if get_silent() {
-        return Ok(XDialogResult::SilentMode);
+        return Ok(options.silent_response());
+    }
+    if let Some(response) = auto_answer(&options) {
+        return Ok(response);
     }
 
     let id = get_next_id();
     let (creation_sender, creation_receiver) = oneshot::channel();
     send_request(DialogMessageRequest::ShowMessageWindow(id, options, creation_sender))?;
+    wait_for_response(id, creation_receiver, timeout)
+}
+
+/// Shows a message box with the specified options and returns as soon as it opens, with a
+/// [`MessageDialogHandle`] to read the user's answer later or to close the dialog. Dropping the
+/// handle leaves the dialog open.
+///
+/// In silent mode no dialog is shown and the handle already holds the silent response, as it holds
+/// the answer when dialogs are auto-answered (see [`XDialogAutoAnswer`]).
+///
+/// ### Example
+/// ```rust,no_run
+/// use xdialog::*;
+///
+/// # fn run() {
+/// let mut handle = show_message_nonblocking(XDialogOptions {
+///     title: "My App".to_string(),
+///     main_instruction: "Installing in the background".to_string(),
+///     buttons: vec!["OK".to_string()],
+///     ..Default::default()
+/// })
+/// .unwrap();
+///
+/// // ... keep working, checking in now and then
+/// if handle.try_result().unwrap().is_none() {
+///     handle.close().unwrap();
+/// }
+/// # }
+/// ```
+pub fn show_message_nonblocking(options: XDialogOptions) -> Result<MessageDialogHandle, XDialogError> {
+    let id = get_next_id();
+    if get_silent() {
+        return Ok(MessageDialogHandle { id, state: HandleState::Closed(options.silent_response()) });
+    }
+    if let Some(response) = auto_answer(&options) {
+        return Ok(MessageDialogHandle { id, state: HandleState::Closed(response) });
+    }
+
+    let (creation_sender, creation_receiver) = oneshot::channel();
+    send_request(DialogMessageRequest::ShowMessageWindow(id, options, creation_sender))?;
+    let receiver = creation_receiver.recv().map_err(XDialogError::NoResult)??;
+    Ok(MessageDialogHandle { id, state: HandleState::Open(receiver) })
+}
+
+/// A message dialog shown with [`show_message_nonblocking`]. Reports the [`XDialogResponse`] once
+/// the dialog closes, whether the user closed it or [`close`](Self::close) did.
+pub struct MessageDialogHandle {
+    id: usize,
+    state: HandleState,
+}
+
+enum HandleState {
+    Open(oneshot::Receiver<XDialogResponse>),
+    Closed(XDialogResponse),
+}
+
+impl MessageDialogHandle {
+    /// Returns the response if the dialog has closed, or `None` while it is still open.
+    pub fn try_result(&mut self) -> Result<Option<XDialogResponse>, XDialogError> {
+        if let HandleState::Open(receiver) = &self.state {
+            match receiver.try_recv() {
+                Ok(response) => self.state = HandleState::Closed(response),
+                Err(oneshot::TryRecvError::Empty) => return Ok(None),
+                Err(oneshot::TryRecvError::Disconnected) => return Err(XDialogError::NoResult(oneshot::RecvError)),
+            }
+        }
+        Ok(self.response())
+    }
+
+    /// Blocks until the dialog closes and returns its response.
+    pub fn wait(self) -> Result<XDialogResponse, XDialogError> {
+        match self.state {
+            HandleState::Open(receiver) => receiver.recv().map_err(XDialogError::NoResult),
+            HandleState::Closed(response) => Ok(response),
+        }
+    }
+
+    /// Blocks until the dialog closes or `timeout` elapses. Returns the response, or `None` if the
+    /// dialog is still open; unlike the timeout of [`show_message`], this leaves it open.
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<Option<XDialogResponse>, XDialogError> {
+        if let HandleState::Open(receiver) = &self.state {
+            match receiver.recv_timeout(timeout) {
+                Ok(response) => self.state = HandleState::Closed(response),
+                Err(oneshot::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(oneshot::RecvTimeoutError::Disconnected) => return Err(XDialogError::NoResult(oneshot::RecvError)),
+            }
+        }
+        Ok(self.response())
+    }
+
+    /// Closes the dialog, unless it has already closed. The response then reports
+    /// `XDialogResult::WindowClosed`.
+    pub fn close(&self) -> Result<(), XDialogError> {
+        match self.state {
+            HandleState::Open(_) => send_request(DialogMessageRequest::CloseWindow(self.id)),
+            HandleState::Closed(_) => Ok(()),
+        }
+    }
+
+    /// Changes the dialog's text, icon or button labels while it is open. Does nothing once it
+    /// has closed.
+    pub fn update(&self, patch: DialogPatch) -> Result<(), XDialogError> {
+        match self.state {
+            HandleState::Open(_) => send_request(DialogMessageRequest::UpdateDialog(self.id, patch)),
+            HandleState::Closed(_) => Ok(()),
+        }
+    }
+
+    fn response(&self) -> Option<XDialogResponse> {
+        match &self.state {
+            HandleState::Open(_) => None,
+            HandleState::Closed(response) => Some(response.clone()),
+        }
+    }
+}
+
+/// Waits for the backend to confirm creation of dialog `id`, then blocks until the dialog closes or
+/// the timeout elapses (in which case the dialog is closed and `TimeoutElapsed` is returned).
+pub(crate) fn wait_for_response(
+    id: usize,
+    creation_receiver: oneshot::Receiver<Result<oneshot::Receiver<XDialogResponse>, XDialogError>>,
+    timeout: Option<Duration>,
+) -> Result<XDialogResponse, XDialogError> {
     let dialog_receiver = creation_receiver.recv().map_err(XDialogError::NoResult)??;
 
     match timeout {
         Some(timeout) => match dialog_receiver.recv_timeout(timeout) {
-            Ok(result) => Ok(result),
+            Ok(response) => Ok(response),
             Err(oneshot::RecvTimeoutError::Timeout) => {
                 send_request(DialogMessageRequest::CloseWindow(id))?;
-                Ok(XDialogResult::TimeoutElapsed)
+                Ok(XDialogResult::TimeoutElapsed.into())
             }
             Err(oneshot::RecvTimeoutError::Disconnected) => Err(XDialogError::NoResult(oneshot::RecvError)),
         },
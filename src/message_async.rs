@@ -0,0 +1,141 @@
+//! Async counterparts of the blocking functions in `message.rs`, enabled by the `async` feature.
+//! They await the same channels the blocking versions wait on, so they don't need (or tie up) a
+//! particular runtime.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::*;
+
+/// Like [`show_message_info_ok`], but waits for the dialog without blocking the thread.
+pub async fn show_message_info_ok_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+) -> Result<(), XDialogError> {
+    let data = helper_options(XDialogIcon::Information, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0);
+    show_message_async(data, None).await?;
+    Ok(())
+}
+
+/// Like [`show_message_warn_ok`], but waits for the dialog without blocking the thread.
+pub async fn show_message_warn_ok_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+) -> Result<(), XDialogError> {
+    let data = helper_options(XDialogIcon::Warning, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0);
+    show_message_async(data, None).await?;
+    Ok(())
+}
+
+/// Like [`show_message_error_ok`], but waits for the dialog without blocking the thread.
+pub async fn show_message_error_ok_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+) -> Result<(), XDialogError> {
+    let data = helper_options(XDialogIcon::Error, window_title, main_instruction, message, vec!["OK".to_string()], 0, 0);
+    show_message_async(data, None).await?;
+    Ok(())
+}
+
+/// Like [`show_message_ok_cancel`], but waits for the dialog without blocking the thread.
+pub async fn show_message_ok_cancel_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+    icon: XDialogIcon,
+) -> Result<bool, XDialogError> {
+    let buttons = vec!["Cancel".to_string(), "OK".to_string()];
+    let data = helper_options(icon, window_title, main_instruction, message, buttons, 1, 0);
+    Ok(show_message_async(data, None).await? == XDialogResult::ButtonPressed(1))
+}
+
+/// Like [`show_message_yes_no`], but waits for the dialog without blocking the thread.
+pub async fn show_message_yes_no_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+    icon: XDialogIcon,
+) -> Result<bool, XDialogError> {
+    let buttons = vec!["No".to_string(), "Yes".to_string()];
+    let data = helper_options(icon, window_title, main_instruction, message, buttons, 1, 0);
+    Ok(show_message_async(data, None).await? == XDialogResult::ButtonPressed(1))
+}
+
+/// Like [`show_message_retry_cancel`], but waits for the dialog without blocking the thread.
+pub async fn show_message_retry_cancel_async<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
+    window_title: P1,
+    main_instruction: P2,
+    message: P3,
+    icon: XDialogIcon,
+) -> Result<bool, XDialogError> {
+    let buttons = vec!["Cancel".to_string(), "Retry".to_string()];
+    let data = helper_options(icon, window_title, main_instruction, message, buttons, 1, 0);
+    Ok(show_message_async(data, None).await? == XDialogResult::ButtonPressed(1))
+}
+
+/// Like [`show_message`], but waits for the dialog without blocking the thread, so it can be called
+/// from a task on any async runtime.
+///
+/// ### Example
+/// ```rust,no_run
+/// use xdialog::*;
+///
+/// # async fn run() {
+/// let options = XDialogOptions {
+///     title: "My App".to_string(),
+///     main_instruction: "Update downloaded".to_string(),
+///     buttons: vec!["Later".to_string(), "Restart now".to_string()],
+///     default_button: Some(1),
+///     cancel_button: Some(0),
+///     ..Default::default()
+/// };
+/// let result = show_message_async(options, None).await.unwrap();
+/// # let _ = result;
+/// # }
+/// ```
+pub async fn show_message_async(options: XDialogOptions, timeout: Option<Duration>) -> Result<XDialogResult, XDialogError> {
+    Ok(show_message_ex_async(options, timeout).await?.result)
+}
+
+/// Like [`show_message_ex`], but waits for the dialog without blocking the thread.
+pub async fn show_message_ex_async(options: XDialogOptions, timeout: Option<Duration>) -> Result<XDialogResponse, XDialogError> {
+    if get_silent() {
+        return Ok(options.silent_response());
+    }
+    if let Some(response) = auto_answer(&options) {
+        return Ok(response);
+    }
+
+    let id = get_next_id();
+    let (creation_sender, creation_receiver) = oneshot::channel();
+    send_request(DialogMessageRequest::ShowMessageWindow(id, options, creation_sender))?;
+    let dialog_receiver = creation_receiver.await.map_err(XDialogError::NoResult)??;
+
+    let Some(timeout) = timeout else {
+        return dialog_receiver.await.map_err(XDialogError::NoResult);
+    };
+
+    // Without a runtime to ask for a timer, a thread closes the dialog when the timeout elapses. It
+    // exits early once the dialog has closed and `cancel_timer` is dropped.
+    let timed_out = Arc::new(AtomicBool::new(false));
+    let (cancel_timer, timer) = oneshot::channel::<()>();
+    let timer_flag = timed_out.clone();
+    std::thread::spawn(move || {
+        if let Err(oneshot::RecvTimeoutError::Timeout) = timer.recv_timeout(timeout) {
+            timer_flag.store(true, Ordering::SeqCst);
+            let _ = send_request(DialogMessageRequest::CloseWindow(id));
+        }
+    });
+
+    let response = dialog_receiver.await.map_err(XDialogError::NoResult)?;
+    drop(cancel_timer);
+    if timed_out.load(Ordering::SeqCst) {
+        Ok(XDialogResult::TimeoutElapsed.into())
+    } else {
+        Ok(response)
+    }
+}
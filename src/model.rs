@@ -16,23 +16,34 @@ pub enum XDialogTheme {
 pub enum XDialogIcon {
     /// No icon
     #[default]
-    None = 0,
+    None,
     /// Error icon
     Error,
     /// Warning icon
     Warning,
     /// Information icon
     Information,
+    /// Question icon, for a dialog asking the user to choose (eg. "Save changes?")
+    Question,
+    /// Shield icon, for an action which needs administrator rights
+    Shield,
+    /// Success icon, for an operation which completed (eg. "Update installed")
+    Success,
+    /// A custom image, eg. a product logo (see [`IconData`](crate::IconData))
+    Custom(crate::IconData),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
-/// Options for constructing a new custom message or progress dialog
+/// Options for constructing a new custom message or progress dialog. Fields may be added in minor
+/// releases, so set the ones needed and fill the rest with `..Default::default()`.
 pub struct XDialogOptions {
     /// The title of the dialog window (required)
     pub title: String,
     /// The main instruction / header text. Can be set to an empty string to hide this element.
     pub main_instruction: String,
-    /// The body text of the dialog. Can be set to an empty string to hide this element.
+    /// The body text of the dialog. Can be set to an empty string to hide this element. May contain
+    /// hyperlinks written as `<a href="https://example.com">link text</a>`; clicking one opens it
+    /// (see [`set_hyperlink_handler`](crate::set_hyperlink_handler)).
     pub message: String,
     /// The icon to display in the dialog, or None for no icon.
     pub icon: XDialogIcon,
@@ -40,6 +51,82 @@ pub struct XDialogOptions {
     /// For progress dialogs the buttons are shown on every platform; an empty array shows no button
     /// except on platforms which require one (Windows shows a default button).
     pub buttons: Vec<String>,
+    /// The index of the default button, which Enter activates wherever the keyboard focus is and
+    /// which is styled as the primary action. `None` leaves Enter to the focused control.
+    pub default_button: Option<usize>,
+    /// The index of the cancel button. When set, pressing Escape or closing the window reports
+    /// `ButtonPressed(cancel_button)` instead of `WindowClosed`.
+    pub cancel_button: Option<usize>,
+    /// The label of a verification checkbox (eg. "Don't ask me again") shown beside the buttons. Can
+    /// be set to an empty string to hide this element. Its final state is reported in
+    /// [`XDialogResponse::checked`]. Not shown by the `maccf-direct` backend.
+    pub verification_text: String,
+    /// Whether the verification checkbox starts out checked.
+    pub verification_checked: bool,
+    /// Mutually exclusive choices (eg. "Update now", "Update on exit", "Skip this version") shown as
+    /// a radio group above the buttons. Can be empty to hide this element. The chosen index is
+    /// reported in [`XDialogResponse::radio_index`]. Not shown by the `maccf-direct` backend.
+    pub radio_buttons: Vec<String>,
+    /// The index into `radio_buttons` which starts out selected.
+    pub default_radio_button: usize,
+    /// Secondary technical text (eg. a stack trace or log excerpt) hidden behind a "Show details"
+    /// toggle below the message. Can be set to an empty string to hide this element.
+    pub expanded_details: String,
+    /// A secondary note (eg. "Your data will not be lost") shown in small text beneath the buttons.
+    /// Can be set to an empty string to hide this element. Not shown by the `maccf-direct` backend.
+    pub footer_text: String,
+    /// A small icon shown beside `footer_text`, or None for no icon.
+    pub footer_icon: XDialogIcon,
+    /// The captions of a progress dialog's bars, top to bottom (eg. `["Current file", "Overall"]`),
+    /// each addressed by its index with [`ProgressDialogProxy::bar`](crate::ProgressDialogProxy::bar).
+    /// Can be empty for a single bar without a caption. Ignored by message dialogs.
+    pub progress_bars: Vec<String>,
+    /// The steps of a multi-phase operation (eg. `["Download", "Verify", "Extract"]`), listed above
+    /// the progress bars of a progress dialog, each marked pending, running, done or failed (see
+    /// [`ProgressDialogProxy::start_step`](crate::ProgressDialogProxy::start_step)). Can be empty
+    /// to hide this element. Ignored by message dialogs.
+    pub checklist: Vec<String>,
+}
+
+/// Helpers for backends (including custom ones, see [`XDialogBackend`](crate::XDialogBackend)), so
+/// they all read the options the same way.
+impl XDialogOptions {
+    /// The captions of the bars of a progress dialog: `progress_bars`, or a single bar without a
+    /// caption when that is empty.
+    pub fn progress_bar_captions(&self) -> Vec<String> {
+        if self.progress_bars.is_empty() {
+            vec![String::new()]
+        } else {
+            self.progress_bars.clone()
+        }
+    }
+
+    /// The response reported in silent mode: no button pressed, and the verification checkbox and
+    /// radio group left in their initial state.
+    pub(crate) fn silent_response(&self) -> XDialogResponse {
+        let checked = (!self.verification_text.is_empty()).then_some(self.verification_checked);
+        XDialogResponse { checked, radio_index: self.initial_radio_index(), ..XDialogResult::SilentMode.into() }
+    }
+
+    /// `default_button`, if it indexes one of the buttons.
+    pub fn default_button_index(&self) -> Option<usize> {
+        self.default_button.filter(|&i| i < self.buttons.len())
+    }
+
+    /// The result reported when the dialog is dismissed with Escape or its close button:
+    /// `ButtonPressed(cancel_button)` when that indexes one of the buttons, otherwise `WindowClosed`.
+    pub fn dismiss_result(&self) -> XDialogResult {
+        match self.cancel_button.filter(|&i| i < self.buttons.len()) {
+            Some(i) => XDialogResult::ButtonPressed(i),
+            None => XDialogResult::WindowClosed,
+        }
+    }
+
+    /// The radio button selected when the dialog opens (`default_radio_button`, clamped to the
+    /// available choices), or `None` without radio buttons.
+    pub fn initial_radio_index(&self) -> Option<usize> {
+        self.radio_buttons.len().checked_sub(1).map(|last| self.default_radio_button.min(last))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -55,25 +142,108 @@ pub enum XDialogResult {
     ButtonPressed(usize),
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// The complete outcome of a dialog: the [`XDialogResult`] plus the final state of any interactive
+/// content the dialog contained. Backends deliver this for every dialog; the simple APIs such as
+/// [`show_message`](crate::show_message) only return its `result`.
+///
+/// Fields may be added in minor releases: a custom backend builds one from an [`XDialogResult`]
+/// (`XDialogResponse::from(result)`) and then sets the fields it reports.
+#[non_exhaustive]
+pub struct XDialogResponse {
+    /// How the dialog was closed
+    pub result: XDialogResult,
+    /// The contents of the text field when the dialog closed, for dialogs shown with
+    /// [`show_input`](crate::show_input). `None` for dialogs without a text field.
+    pub text: Option<String>,
+    /// The contents of the masked field when the dialog closed, for dialogs shown with
+    /// [`show_password`](crate::show_password). `None` for other dialogs.
+    pub secret: Option<crate::XDialogSecret>,
+    /// The state of the verification checkbox when the dialog closed. `None` for dialogs without
+    /// one (see [`XDialogOptions::verification_text`]).
+    pub checked: Option<bool>,
+    /// The index of the selected radio button when the dialog closed. `None` for dialogs without
+    /// radio buttons (see [`XDialogOptions::radio_buttons`]).
+    pub radio_index: Option<usize>,
+}
+
+impl From<XDialogResult> for XDialogResponse {
+    fn from(result: XDialogResult) -> Self {
+        XDialogResponse { result, text: None, secret: None, checked: None, radio_index: None }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// Changes to make to an open dialog, sent with [`DialogMessageRequest::UpdateDialog`] (see
+/// [`ProgressDialogProxy::update`](crate::ProgressDialogProxy::update)). Fields left as `None` (and
+/// buttons not listed in `button_labels`) keep their current value.
+pub struct DialogPatch {
+    /// The new window title.
+    pub title: Option<String>,
+    /// The new main instruction. An empty string hides it.
+    pub main_instruction: Option<String>,
+    /// The new body text, which may contain hyperlinks as in [`XDialogOptions::message`]. An empty
+    /// string hides it.
+    pub message: Option<String>,
+    /// The new icon, or `Some(XDialogIcon::None)` to remove it.
+    pub icon: Option<XDialogIcon>,
+    /// New labels for existing buttons, as pairs of the button's index and its label. Indexes
+    /// without a button are ignored; buttons can't be added or removed.
+    pub button_labels: Vec<(usize, String)>,
+}
+
 /// Channel sender used by backends to deliver the dialog result receiver back to the caller.
-/// Sends `Ok(receiver)` on successful dialog creation, or `Err(e)` on failure.
-pub type CreationSender = oneshot::Sender<Result<oneshot::Receiver<XDialogResult>, crate::XDialogError>>;
+/// Sends `Ok(receiver)` on successful dialog creation, or `Err(e)` on failure. Custom backends can
+/// answer it with [`DialogResponder`](crate::DialogResponder).
+pub type CreationSender = oneshot::Sender<Result<oneshot::Receiver<XDialogResponse>, crate::XDialogError>>;
 
-#[allow(missing_docs)]
+/// A request from the dialog functions to the backend (see [`XDialogBackend`](crate::XDialogBackend)).
+/// The first field of each is the id of the dialog it concerns, unique for the life of the process;
+/// requests for an id which isn't open should be ignored.
+///
+/// New requests may be added in minor releases, so a backend or handler matching on this needs a
+/// fallback arm (eg. `_ => {}`, ignoring requests it doesn't know). A show request dropped by the
+/// fallback fails to open, returning an error to its caller rather than blocking it.
 #[derive(Debug, Default)]
+#[non_exhaustive]
 pub enum DialogMessageRequest {
     // generic
+    /// Nothing to do.
     #[default]
     None,
+    /// The application's `main` has returned: close every dialog and return from the event loop.
     ExitEventLoop,
+    /// Close the dialog, reporting `XDialogResult::WindowClosed`.
     CloseWindow(usize),
+    /// Change the dialog's text, icon or button labels.
+    UpdateDialog(usize, DialogPatch),
 
     // messagebox
+    /// Show a message dialog.
     ShowMessageWindow(usize, XDialogOptions, CreationSender),
 
+    // text input
+    /// Show a dialog with a text field, whose contents are reported in [`XDialogResponse::text`]
+    /// (or [`XDialogResponse::secret`] when the field is masked).
+    ShowInputWindow(usize, XDialogOptions, crate::input::InputField, CreationSender),
+
     // progress
-    ShowProgressWindow(usize, XDialogOptions, CreationSender, Option<crate::progress::ProgressButtonCallback>),
-    SetProgressIndeterminate(usize),
-    SetProgressValue(usize, f32),
+    /// Show a progress dialog, whose buttons and close button run the callbacks.
+    ShowProgressWindow(usize, XDialogOptions, CreationSender, crate::progress::ProgressCallbacks),
+    /// Set the progress bar at the index to indeterminate.
+    SetProgressIndeterminate(usize, usize),
+    /// Set the value (from 0.0 to 1.0) of the progress bar at the index.
+    SetProgressValue(usize, usize, f32),
+    /// Set the text of a progress dialog.
     SetProgressText(usize, String),
+    /// Set the status line of a progress dialog.
+    SetProgressStatus(usize, String),
+    /// Set the state of the progress bar at the index.
+    SetProgressState(usize, usize, crate::progress::ProgressBarState),
+    /// Set the caption of the progress bar at the index.
+    SetProgressCaption(usize, usize, String),
+    /// Set the state of the checklist step at the index.
+    SetChecklistStep(usize, usize, crate::checklist::ChecklistStepState),
+    /// Start the checklist step at the index: it becomes active, and the steps before it done.
+    StartChecklistStep(usize, usize),
 }
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use crate::completion::DialogCompletion;
 use crate::*;
 
 /// Shows a progress dialog with the specified options and returns a proxy object to control it.
@@ -48,8 +51,9 @@ pub fn show_progress<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
         message: message.as_ref().to_string(),
         icon,
         buttons: vec![],
+        ..Default::default()
     };
-    show_progress_internal(data, None)
+    show_progress_internal(data, ProgressCallbacks::default())
 }
 
 /// Shows a progress dialog with custom buttons. Like [`show_progress`], but the buttons in
@@ -60,7 +64,7 @@ pub fn show_progress<P1: AsRef<str>, P2: AsRef<str>, P3: AsRef<str>>(
 /// This is useful to relabel the button that Windows always displays on a progress dialog (eg.
 /// to a localized "Hide"), or to offer a "Cancel" button on all platforms.
 pub fn show_progress_ex(options: XDialogOptions) -> Result<ProgressDialogProxy, XDialogError> {
-    show_progress_internal(options, None)
+    show_progress_internal(options, ProgressCallbacks::default())
 }
 
 /// Shows a progress dialog with custom buttons and a callback invoked when a button is clicked.
@@ -94,6 +98,7 @@ pub fn show_progress_ex(options: XDialogOptions) -> Result<ProgressDialogProxy,
 ///         message: "Crunching numbers...".to_string(),
 ///         icon: XDialogIcon::Information,
 ///         buttons: vec!["Cancel".to_string()],
+///         ..Default::default()
 ///     },
 ///     move |_button_index, proxy| {
 ///         flag.store(true, Ordering::SeqCst);
@@ -108,21 +113,55 @@ pub fn show_progress_with_callback<F>(options: XDialogOptions, on_button: F) ->
 where
     F: FnMut(usize, &ProgressDialogProxy) -> bool + Send + 'static,
 {
-    show_progress_internal(options, Some(ProgressButtonCallback(Box::new(on_button))))
+    show_progress_internal(options, ProgressCallbacks::new().on_button(on_button))
+}
+
+/// Shows a progress dialog with the callbacks in `callbacks`. Like [`show_progress_with_callback`],
+/// but can also react when the user tries to close the window, eg. to keep an installer's progress
+/// dialog open while the installation finishes or cancels.
+///
+/// ### Example
+/// ```rust,no_run
+/// use xdialog::*;
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// # fn run() {
+/// let cancelled = Arc::new(AtomicBool::new(false));
+/// let flag = cancelled.clone();
+/// let progress = show_progress_with_callbacks(
+///     XDialogOptions {
+///         title: "Installing".to_string(),
+///         main_instruction: "Installing My App".to_string(),
+///         icon: XDialogIcon::Information,
+///         ..Default::default()
+///     },
+///     ProgressCallbacks::new().on_close_requested(move |proxy| {
+///         flag.store(true, Ordering::SeqCst);
+///         let _ = proxy.set_text("Cancelling...");
+///         true // keep the dialog open until the installer has rolled back
+///     }),
+/// ).unwrap();
+/// # let _ = (cancelled, progress);
+/// # }
+/// ```
+pub fn show_progress_with_callbacks(options: XDialogOptions, callbacks: ProgressCallbacks) -> Result<ProgressDialogProxy, XDialogError> {
+    show_progress_internal(options, callbacks)
 }
 
-fn show_progress_internal(options: XDialogOptions, on_button: Option<ProgressButtonCallback>) -> Result<ProgressDialogProxy, XDialogError> {
+fn show_progress_internal(options: XDialogOptions, callbacks: ProgressCallbacks) -> Result<ProgressDialogProxy, XDialogError> {
     let id = get_next_id();
 
-    if get_silent() {
-        return Ok(ProgressDialogProxy { id, silent: true, owned: true });
+    // Auto-answered runs are unattended, so progress dialogs aren't shown there either.
+    if get_silent() || auto_answer_active() {
+        return Ok(ProgressDialogProxy::silent(id));
     }
 
     let (creation_sender, creation_receiver) = oneshot::channel();
-    send_request(DialogMessageRequest::ShowProgressWindow(id, options, creation_sender, on_button))?;
-    // Wait for creation confirmation, discard the dialog result receiver
-    let _ = creation_receiver.recv().map_err(XDialogError::NoResult)??;
-    Ok(ProgressDialogProxy { id, silent: false, owned: true })
+    send_request(DialogMessageRequest::ShowProgressWindow(id, options, creation_sender, callbacks))?;
+    // Wait for creation confirmation, then watch the result so the proxy can tell when the dialog closes.
+    let result_receiver = creation_receiver.recv().map_err(XDialogError::NoResult)??;
+    Ok(ProgressDialogProxy { id, silent: false, owned: true, completion: Some(DialogCompletion::watch(result_receiver)) })
 }
 
 /// The boxed closure type behind [`ProgressButtonCallback`].
@@ -140,6 +179,99 @@ impl std::fmt::Debug for ProgressButtonCallback {
     }
 }
 
+/// The boxed closure type behind [`ProgressCloseCallback`].
+type ProgressCloseCallbackFn = Box<dyn FnMut(&ProgressDialogProxy) -> bool + Send + 'static>;
+
+/// A boxed callback invoked when the user tries to close a progress dialog (its close button, or
+/// Escape). Returns `true` to keep the dialog open or `false` to let it close. Like
+/// [`ProgressButtonCallback`], callers pass a closure to [`ProgressCallbacks::on_close_requested`]
+/// rather than constructing this directly.
+pub struct ProgressCloseCallback(pub(crate) ProgressCloseCallbackFn);
+
+impl std::fmt::Debug for ProgressCloseCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<callback>")
+    }
+}
+
+/// The callbacks of a progress dialog, see [`show_progress_with_callbacks`]. All of them run on the
+/// backend thread with a non-owning [`ProgressDialogProxy`], are never invoked in silent mode, and
+/// are optional.
+#[derive(Debug, Default)]
+pub struct ProgressCallbacks {
+    pub(crate) on_button: Option<ProgressButtonCallback>,
+    pub(crate) on_close_requested: Option<ProgressCloseCallback>,
+}
+
+impl ProgressCallbacks {
+    /// No callbacks: buttons and the close button close the dialog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Invoked with the index of the clicked button. See [`show_progress_with_callback`].
+    pub fn on_button<F>(mut self, on_button: F) -> Self
+    where
+        F: FnMut(usize, &ProgressDialogProxy) -> bool + Send + 'static,
+    {
+        self.on_button = Some(ProgressButtonCallback(Box::new(on_button)));
+        self
+    }
+
+    /// Invoked when the user tries to close the dialog with its close button or Escape. Return
+    /// `true` to veto the close and keep the dialog open (eg. after switching it to a
+    /// "Cancelling..." state), or `false` to let it close as usual. Closing the dialog with
+    /// [`ProgressDialogProxy::close`] does not invoke it.
+    pub fn on_close_requested<F>(mut self, on_close_requested: F) -> Self
+    where
+        F: FnMut(&ProgressDialogProxy) -> bool + Send + 'static,
+    {
+        self.on_close_requested = Some(ProgressCloseCallback(Box::new(on_close_requested)));
+        self
+    }
+
+    /// Runs the button callback for a click on button `index` of dialog `id`. Returns whether the
+    /// dialog should stay open, or `None` without a button callback. Backends call it on their event
+    /// loop's thread, so requests the callback makes through its proxy are handled after it returns.
+    pub fn button_clicked(&mut self, id: usize, index: usize) -> Option<bool> {
+        let cb = self.on_button.as_mut()?;
+        Some((cb.0)(index, &ProgressDialogProxy::non_owning(id)))
+    }
+
+    /// Runs the close callback of dialog `id`. Returns whether the dialog should stay open (`false`
+    /// without a close callback).
+    pub fn close_requested(&mut self, id: usize) -> bool {
+        match self.on_close_requested.as_mut() {
+            Some(cb) => (cb.0)(&ProgressDialogProxy::non_owning(id)),
+            None => false,
+        }
+    }
+}
+
+/// The state of a progress bar, shown by its colour like the Windows progress bar: eg. a download
+/// that failed keeps its position but turns red. See [`ProgressDialogProxy::set_state`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ProgressBarState {
+    /// In progress, in the accent colour
+    #[default]
+    Normal,
+    /// Paused (yellow)
+    Paused,
+    /// Stopped by an error (red)
+    Error,
+}
+
+/// Why a progress dialog closed. See [`ProgressDialogProxy::close_reason`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ProgressCloseReason {
+    /// The user closed the window (eg. clicked the 'X' button or pressed Escape)
+    WindowClosed,
+    /// A button was pressed, with the index of the button in the `buttons` array
+    ButtonPressed(usize),
+    /// The dialog was closed with [`ProgressDialogProxy::close`]
+    Programmatic,
+}
+
 /// A proxy object to control a progress dialog. See `show_progress` for more information.
 pub struct ProgressDialogProxy {
     id: usize,
@@ -147,26 +279,47 @@ pub struct ProgressDialogProxy {
     /// When `true`, the dialog is closed when this proxy is dropped. The proxy handed to a button
     /// callback is non-owning (`false`) so it does not close the dialog when it goes out of scope.
     owned: bool,
+    /// Filled in when the dialog closes. `None` for non-owning proxies. In silent mode it is completed
+    /// by [`close`](Self::close), as there is no window for the user to close.
+    completion: Option<Arc<DialogCompletion>>,
 }
 
 impl ProgressDialogProxy {
     /// Constructs a non-owning proxy for an existing dialog. Dropping it does not close the dialog.
     /// Used by backends to hand a controllable proxy to a button callback.
     pub(crate) fn non_owning(id: usize) -> Self {
-        ProgressDialogProxy { id, silent: false, owned: false }
+        ProgressDialogProxy { id, silent: false, owned: false, completion: None }
+    }
+
+    /// Constructs a proxy for a dialog which isn't shown (in silent mode): its updates are ignored,
+    /// and it only closes when [`close`](Self::close) is called.
+    pub(crate) fn silent(id: usize) -> Self {
+        ProgressDialogProxy { id, silent: true, owned: true, completion: Some(DialogCompletion::pending()) }
+    }
+
+    /// A handle to the progress bar at `index` in [`XDialogOptions::progress_bars`], to update it
+    /// separately from the others (eg. `proxy.bar(1).set_value(0.5)`). Updates to an index
+    /// without a bar are ignored.
+    pub fn bar(&self, index: usize) -> ProgressBarHandle<'_> {
+        ProgressBarHandle { proxy: self, index }
     }
 
-    /// Sets the progress bar to indeterminate mode.
+    /// Sets the (first) progress bar to indeterminate mode.
     pub fn set_indeterminate(&self) -> Result<(), XDialogError> {
-        if self.silent { return Ok(()); }
-        send_request(DialogMessageRequest::SetProgressIndeterminate(self.id))
+        self.bar(0).set_indeterminate()
     }
 
-    /// Sets the progress bar to a specific value between 0.0 and 1.0. Values outside that range
-    /// are clamped (e.g. `50.0` becomes `1.0`), matching the native progress controls.
+    /// Sets the (first) progress bar to a specific value between 0.0 and 1.0. Values outside that
+    /// range are clamped (e.g. `50.0` becomes `1.0`), matching the native progress controls.
     pub fn set_value(&self, value: f32) -> Result<(), XDialogError> {
-        if self.silent { return Ok(()); }
-        send_request(DialogMessageRequest::SetProgressValue(self.id, value.clamp(0.0, 1.0)))
+        self.bar(0).set_value(value)
+    }
+
+    /// Sets the state of the (first) progress bar (eg. `ProgressBarState::Error` after a failure),
+    /// which changes its colour but keeps its value. The AppKit backend tints the bar, and
+    /// `maccf-direct` marks its text bar with a symbol.
+    pub fn set_state(&self, state: ProgressBarState) -> Result<(), XDialogError> {
+        self.bar(0).set_state(state)
     }
 
     /// Sets the text displayed below the progress bar.
@@ -175,11 +328,151 @@ impl ProgressDialogProxy {
         send_request(DialogMessageRequest::SetProgressText(self.id, text.as_ref().to_string()))
     }
 
-    /// Closes the progress dialog.
-    pub fn close(&self) -> Result<(), XDialogError> {
+    /// Sets the status line shown under the progress bars, for a live status (eg. the file being
+    /// copied) alongside an unchanging message. It is kept to a single line, shortening long text
+    /// (such as paths) in the middle, so updating it doesn't resize the dialog. An empty string
+    /// clears it.
+    ///
+    /// The Win32 TaskDialog and `maccf-direct` backends have no separate line, so they show the
+    /// status as another paragraph after the body text.
+    pub fn set_status<P: AsRef<str>>(&self, text: P) -> Result<(), XDialogError> {
+        if self.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetProgressStatus(self.id, text.as_ref().to_string()))
+    }
+
+    /// Marks the checklist step at `index` (see [`XDialogOptions::checklist`]) running, and the
+    /// steps before it done, unless they failed. Use this to advance through the steps in order.
+    pub fn start_step(&self, index: usize) -> Result<(), XDialogError> {
+        if self.silent { return Ok(()); }
+        send_request(DialogMessageRequest::StartChecklistStep(self.id, index))
+    }
+
+    /// Sets the state of the checklist step at `index`, eg. `ChecklistStepState::Failed` after an
+    /// error, or `Done` for the last step.
+    pub fn set_step_state(&self, index: usize, state: ChecklistStepState) -> Result<(), XDialogError> {
+        if self.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetChecklistStep(self.id, index, state))
+    }
+
+    /// Sets the window title.
+    pub fn set_title<P: AsRef<str>>(&self, title: P) -> Result<(), XDialogError> {
+        self.update(DialogPatch { title: Some(title.as_ref().to_string()), ..Default::default() })
+    }
+
+    /// Sets the main instruction (eg. "Downloading" → "Installing"). An empty string hides it.
+    pub fn set_main_instruction<P: AsRef<str>>(&self, main_instruction: P) -> Result<(), XDialogError> {
+        self.update(DialogPatch { main_instruction: Some(main_instruction.as_ref().to_string()), ..Default::default() })
+    }
+
+    /// Sets the icon (eg. to `XDialogIcon::Error` when the operation fails).
+    pub fn set_icon(&self, icon: XDialogIcon) -> Result<(), XDialogError> {
+        self.update(DialogPatch { icon: Some(icon), ..Default::default() })
+    }
+
+    /// Sets the label of the button at `index` in the dialog's `buttons`.
+    pub fn set_button_text<P: AsRef<str>>(&self, index: usize, text: P) -> Result<(), XDialogError> {
+        self.update(DialogPatch { button_labels: vec![(index, text.as_ref().to_string())], ..Default::default() })
+    }
+
+    /// Applies several changes at once, so the dialog is laid out once for all of them.
+    pub fn update(&self, patch: DialogPatch) -> Result<(), XDialogError> {
         if self.silent { return Ok(()); }
+        send_request(DialogMessageRequest::UpdateDialog(self.id, patch))
+    }
+
+    /// Closes the progress dialog. [`close_reason`](Self::close_reason) then reports
+    /// [`ProgressCloseReason::Programmatic`], unless the user closed it first.
+    pub fn close(&self) -> Result<(), XDialogError> {
+        if let Some(completion) = self.completion.as_ref() {
+            completion.request_close();
+        }
+        if self.silent {
+            if let Some(completion) = self.completion.as_ref() {
+                completion.complete(XDialogResult::WindowClosed.into());
+            }
+            return Ok(());
+        }
         send_request(DialogMessageRequest::CloseWindow(self.id))
     }
+
+    /// Returns `true` once the dialog has closed, for any [reason](Self::close_reason). Long-running
+    /// work can check this to stop when the user dismisses the dialog. Always `false` for the proxy
+    /// passed to a button callback.
+    pub fn is_closed(&self) -> bool {
+        self.close_reason().is_some()
+    }
+
+    /// Why the dialog closed, or `None` while it is still open (and always for the proxy passed to a
+    /// button callback).
+    pub fn close_reason(&self) -> Option<ProgressCloseReason> {
+        let (response, closed_by_request) = self.completion.as_ref()?.try_response()?;
+        Some(match response.result {
+            XDialogResult::ButtonPressed(index) => ProgressCloseReason::ButtonPressed(index),
+            _ if closed_by_request => ProgressCloseReason::Programmatic,
+            _ => ProgressCloseReason::WindowClosed,
+        })
+    }
+
+    /// Blocks the current thread until the dialog closes: because the user closed the window or
+    /// pressed a button that closes it, or because of [`close`](Self::close). Returns why it closed.
+    /// Returns immediately in silent mode, and for the proxy passed to a button callback, with the
+    /// reason if it is known.
+    pub fn wait_closed(&self) -> Option<ProgressCloseReason> {
+        if !self.silent {
+            if let Some(completion) = self.completion.as_ref() {
+                completion.wait();
+            }
+        }
+        self.close_reason()
+    }
+
+    /// Like [`wait_closed`](Self::wait_closed), but waits without blocking the thread. Works with any
+    /// async runtime.
+    #[cfg(feature = "async")]
+    pub async fn wait_closed_async(&self) -> Option<ProgressCloseReason> {
+        if !self.silent {
+            if let Some(completion) = self.completion.as_ref() {
+                completion.wait_async().await;
+            }
+        }
+        self.close_reason()
+    }
+}
+
+/// One of the progress bars of a dialog, returned by [`ProgressDialogProxy::bar`].
+///
+/// Only the Skia and AppKit backends show several bars. The Win32 TaskDialog shows the first bar
+/// (without its caption) and lists the others as text under the body (eg. "Overall: 45%"), and
+/// `maccf-direct` draws each as a captioned text bar.
+pub struct ProgressBarHandle<'a> {
+    proxy: &'a ProgressDialogProxy,
+    index: usize,
+}
+
+impl ProgressBarHandle<'_> {
+    /// Sets this bar to indeterminate mode.
+    pub fn set_indeterminate(&self) -> Result<(), XDialogError> {
+        if self.proxy.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetProgressIndeterminate(self.proxy.id, self.index))
+    }
+
+    /// Sets this bar to a value between 0.0 and 1.0, clamping values outside that range.
+    pub fn set_value(&self, value: f32) -> Result<(), XDialogError> {
+        if self.proxy.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetProgressValue(self.proxy.id, self.index, value.clamp(0.0, 1.0)))
+    }
+
+    /// Sets the state of this bar. See [`ProgressDialogProxy::set_state`].
+    pub fn set_state(&self, state: ProgressBarState) -> Result<(), XDialogError> {
+        if self.proxy.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetProgressState(self.proxy.id, self.index, state))
+    }
+
+    /// Sets the caption shown above this bar. An empty string hides it.
+    pub fn set_caption<P: AsRef<str>>(&self, caption: P) -> Result<(), XDialogError> {
+        if self.proxy.silent { return Ok(()); }
+        send_request(DialogMessageRequest::SetProgressCaption(self.proxy.id, self.index, caption.as_ref().to_string()))
+    }
 }
 
 impl Drop for ProgressDialogProxy {
@@ -0,0 +1,44 @@
+use zeroize::Zeroize;
+
+/// A secret string (eg. a password entered into [`show_password`](crate::show_password)) whose
+/// memory is wiped when it is dropped.
+///
+/// The value is only reachable through [`XDialogSecret::expose_secret`], and the `Debug`
+/// implementation never prints it, so a secret can't end up in a log line by accident. Avoid
+/// copying the exposed `&str` into an ordinary `String`: that copy won't be wiped.
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct XDialogSecret(String);
+
+impl XDialogSecret {
+    /// Wraps `secret`, eg. for a custom backend reporting the contents of a masked field.
+    pub fn new(secret: String) -> Self {
+        XDialogSecret(secret)
+    }
+
+    /// Borrows the secret value.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// The length of the secret in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for XDialogSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for XDialogSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("XDialogSecret(<redacted>)")
+    }
+}
@@ -1,7 +1,11 @@
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+type LinkHandler = Arc<dyn Fn(&str) + Send + Sync + 'static>;
 
 static SILENT: AtomicBool = AtomicBool::new(false);
 static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+static LINK_HANDLER: RwLock<Option<LinkHandler>> = RwLock::new(None);
 
 pub fn set_silent(silent: bool) {
     SILENT.store(silent, Ordering::Relaxed);
@@ -14,3 +18,11 @@ pub fn get_silent() -> bool {
 pub fn get_next_id() -> usize {
     NEXT_ID.fetch_add(1, Ordering::Relaxed)
 }
+
+pub fn set_link_handler(handler: Option<LinkHandler>) {
+    *LINK_HANDLER.write().unwrap_or_else(|e| e.into_inner()) = handler;
+}
+
+pub fn get_link_handler() -> Option<LinkHandler> {
+    LINK_HANDLER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
@@ -0,0 +1,371 @@
+//! A scripted backend for testing code which shows dialogs, with no display. Enabled by the
+//! `testing` feature.
+//!
+//! [`MockBackend`] records every request it receives, and answers each dialog with the next
+//! [`MockAnswer`] in its script, so a test can press a button that silent mode never would. Install
+//! it with [`init_with_handler`](crate::init_with_handler), which answers dialogs on the thread
+//! showing them, or run it in place of the platform's backend with
+//! [`XDialogBuilder::with_backend`](crate::XDialogBuilder::with_backend).
+//!
+//! Only one backend can be installed per process, so the tests of one test binary share it: script
+//! and assert from one test, or run the binary with `--test-threads=1` and [`reset`](MockBackend::reset)
+//! between tests.
+//!
+//! ```rust
+//! use xdialog::testing::*;
+//! use xdialog::*;
+//!
+//! let mock = MockBackend::new();
+//! init_with_handler(mock.clone());
+//! mock.answer(MockAnswer::PressButton("Yes".to_string()));
+//!
+//! // The code under test:
+//! let update = show_message_yes_no("My App", "Update now?", "", XDialogIcon::Question).unwrap();
+//! let progress = show_progress("My App", "Updating", "", XDialogIcon::Information).unwrap();
+//! progress.set_value(0.5).unwrap();
+//! progress.close().unwrap();
+//!
+//! assert!(update);
+//! assert_eq!(mock.progress_updates(), [ProgressUpdate::Value { bar: 0, value: 0.5 }]);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::*;
+
+/// How [`MockBackend`] answers a dialog, see [`MockBackend::answer`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockAnswer {
+    /// Press the button with this label. A dialog without one fails to open.
+    PressButton(String),
+    /// Press the button at this index in [`XDialogOptions::buttons`]. A dialog without one fails to
+    /// open.
+    PressButtonIndex(usize),
+    /// Close the window, as with its close button or Escape: this reports the cancel button when
+    /// the dialog has one (see [`XDialogOptions::cancel_button`]), otherwise `WindowClosed`.
+    CloseWindow,
+    /// Leave the dialog open for the duration, then report `TimeoutElapsed`. A shorter timeout
+    /// given to [`show_message`] elapses first, as it would with a real backend.
+    TimeOutAfter(Duration),
+    /// Leave the dialog open until the application closes it.
+    LeaveOpen,
+    /// Report this response as is, eg. with text typed into an input dialog.
+    Respond(XDialogResponse),
+}
+
+/// A request [`MockBackend`] received, with the parts of it which can be compared.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockRequest {
+    /// A message dialog was shown.
+    ShowMessage {
+        /// The id of the dialog
+        id: usize,
+        /// The options the dialog was shown with
+        options: XDialogOptions,
+    },
+    /// An input dialog was shown.
+    ShowInput {
+        /// The id of the dialog
+        id: usize,
+        /// The options the dialog was shown with
+        options: XDialogOptions,
+        /// The text the field started with
+        initial_text: String,
+        /// Whether the field was a masked password field
+        masked: bool,
+    },
+    /// A progress dialog was shown.
+    ShowProgress {
+        /// The id of the dialog
+        id: usize,
+        /// The options the dialog was shown with
+        options: XDialogOptions,
+    },
+    /// The application closed a dialog.
+    Close(usize),
+    /// The application changed a dialog's text, icon or button labels.
+    Update(usize, DialogPatch),
+    /// The application updated a progress dialog.
+    Progress(usize, ProgressUpdate),
+}
+
+/// An update to a progress dialog, see [`MockBackend::progress_updates`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressUpdate {
+    /// The bar at index `bar` was set to indeterminate.
+    Indeterminate {
+        /// The index of the bar
+        bar: usize,
+    },
+    /// The bar at index `bar` was set to `value`.
+    Value {
+        /// The index of the bar
+        bar: usize,
+        /// The new value, from 0.0 to 1.0
+        value: f32,
+    },
+    /// The bar at index `bar` was set to `state`.
+    State {
+        /// The index of the bar
+        bar: usize,
+        /// The new state
+        state: ProgressBarState,
+    },
+    /// The caption of the bar at index `bar` was set.
+    Caption {
+        /// The index of the bar
+        bar: usize,
+        /// The new caption
+        caption: String,
+    },
+    /// The text was set.
+    Text(String),
+    /// The status line was set.
+    Status(String),
+    /// The checklist step at index `step` was set to `state`.
+    ChecklistStep {
+        /// The index of the step
+        step: usize,
+        /// The new state
+        state: ChecklistStepState,
+    },
+    /// The checklist step at index `step` was started.
+    StartChecklistStep {
+        /// The index of the step
+        step: usize,
+    },
+}
+
+/// A backend which records requests and answers dialogs from a script, see the
+/// [module documentation](self). Cloning it gives another handle to the same backend.
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    script: VecDeque<MockAnswer>,
+    requests: Vec<MockRequest>,
+    /// The dialogs left open by their answer, by id.
+    open: HashMap<usize, DialogResponder>,
+}
+
+impl std::fmt::Debug for MockBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockBackend").field("script", &state.script).field("requests", &state.requests).finish()
+    }
+}
+
+impl MockBackend {
+    /// A backend with an empty script, which hasn't received any requests.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `answer` to the end of the script. Each dialog takes the next answer when it opens; a
+    /// message or input dialog finding the script empty fails to open, with an error naming it,
+    /// while a progress dialog stays open.
+    pub fn answer(&self, answer: MockAnswer) -> &Self {
+        self.lock().script.push_back(answer);
+        self
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The updates to progress dialogs received so far, in order. Use [`requests`](Self::requests)
+    /// to tell several dialogs' updates apart.
+    pub fn progress_updates(&self) -> Vec<ProgressUpdate> {
+        let state = self.lock();
+        state
+            .requests
+            .iter()
+            .filter_map(|r| match r {
+                MockRequest::Progress(_, update) => Some(update.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The ids of the dialogs which are open, in no particular order.
+    pub fn open_dialogs(&self) -> Vec<usize> {
+        self.lock().open.keys().copied().collect()
+    }
+
+    /// Close every open dialog (reporting `WindowClosed`), and forget the script and the recorded requests.
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.script.clear();
+        state.requests.clear();
+        state.close_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn handle(&self, message: DialogMessageRequest) {
+        match message {
+            DialogMessageRequest::None => {}
+            DialogMessageRequest::ExitEventLoop => self.lock().close_all(),
+            DialogMessageRequest::CloseWindow(id) => {
+                let mut state = self.lock();
+                state.requests.push(MockRequest::Close(id));
+                if let Some(responder) = state.open.remove(&id) {
+                    responder.respond(XDialogResult::WindowClosed);
+                }
+            }
+            DialogMessageRequest::UpdateDialog(id, patch) => self.lock().requests.push(MockRequest::Update(id, patch)),
+            DialogMessageRequest::ShowMessageWindow(id, options, creation) => {
+                let answer = self.next_answer(MockRequest::ShowMessage { id, options: options.clone() });
+                let base = options.silent_response();
+                self.open(id, options, creation, answer, base, None);
+            }
+            DialogMessageRequest::ShowInputWindow(id, options, field, creation) => {
+                let initial_text = field.initial_text().to_string();
+                let masked = field.is_masked();
+                let request = MockRequest::ShowInput { id, options: options.clone(), initial_text: initial_text.clone(), masked };
+                let answer = self.next_answer(request);
+                let base = if masked {
+                    XDialogResponse { secret: Some(XDialogSecret::new(initial_text)), ..options.silent_response() }
+                } else {
+                    XDialogResponse { text: Some(initial_text), ..options.silent_response() }
+                };
+                self.open(id, options, creation, answer, base, None);
+            }
+            DialogMessageRequest::ShowProgressWindow(id, options, creation, callbacks) => {
+                let answer = self.next_answer(MockRequest::ShowProgress { id, options: options.clone() });
+                let base = options.silent_response();
+                self.open(id, options, creation, Some(answer.unwrap_or(MockAnswer::LeaveOpen)), base, Some(callbacks));
+            }
+            DialogMessageRequest::SetProgressIndeterminate(id, bar) => self.progress(id, ProgressUpdate::Indeterminate { bar }),
+            DialogMessageRequest::SetProgressValue(id, bar, value) => self.progress(id, ProgressUpdate::Value { bar, value }),
+            DialogMessageRequest::SetProgressText(id, text) => self.progress(id, ProgressUpdate::Text(text)),
+            DialogMessageRequest::SetProgressStatus(id, text) => self.progress(id, ProgressUpdate::Status(text)),
+            DialogMessageRequest::SetProgressState(id, bar, state) => self.progress(id, ProgressUpdate::State { bar, state }),
+            DialogMessageRequest::SetProgressCaption(id, bar, caption) => self.progress(id, ProgressUpdate::Caption { bar, caption }),
+            DialogMessageRequest::SetChecklistStep(id, step, state) => self.progress(id, ProgressUpdate::ChecklistStep { step, state }),
+            DialogMessageRequest::StartChecklistStep(id, step) => self.progress(id, ProgressUpdate::StartChecklistStep { step }),
+        }
+    }
+
+    /// Record `request` and take the next answer for the dialog it shows.
+    fn next_answer(&self, request: MockRequest) -> Option<MockAnswer> {
+        let mut state = self.lock();
+        state.requests.push(request);
+        state.script.pop_front()
+    }
+
+    fn progress(&self, id: usize, update: ProgressUpdate) {
+        self.lock().requests.push(MockRequest::Progress(id, update));
+    }
+
+    /// Open dialog `id` and answer it. `base` is the response the dialog would report with its
+    /// contents untouched, which the answer's result is reported in.
+    fn open(
+        &self,
+        id: usize,
+        options: XDialogOptions,
+        creation: CreationSender,
+        answer: Option<MockAnswer>,
+        base: XDialogResponse,
+        callbacks: Option<ProgressCallbacks>,
+    ) {
+        let mut closing = false;
+        let mut timeout = None;
+        let pressed = |index| XDialogResponse { result: XDialogResult::ButtonPressed(index), ..base.clone() };
+        let response = match answer {
+            Some(MockAnswer::PressButton(label)) => match options.buttons.iter().position(|b| *b == label) {
+                Some(index) => Some(pressed(index)),
+                None => {
+                    let error = format!("the dialog {:?} has no button {:?} (it has {:?})", options.title, label, options.buttons);
+                    return DialogResponder::reject(creation, XDialogError::SystemError(error));
+                }
+            },
+            Some(MockAnswer::PressButtonIndex(index)) if index < options.buttons.len() => Some(pressed(index)),
+            Some(MockAnswer::PressButtonIndex(index)) => {
+                let error = format!("the dialog {:?} has no button {} (it has {:?})", options.title, index, options.buttons);
+                return DialogResponder::reject(creation, XDialogError::SystemError(error));
+            }
+            Some(MockAnswer::CloseWindow) => {
+                closing = true;
+                Some(XDialogResponse { result: options.dismiss_result(), ..base.clone() })
+            }
+            Some(MockAnswer::Respond(response)) => Some(response),
+            Some(MockAnswer::LeaveOpen) => None,
+            Some(MockAnswer::TimeOutAfter(duration)) => {
+                timeout = Some(duration);
+                None
+            }
+            None => {
+                let error = format!("no scripted answer for the dialog {:?} ({:?})", options.title, options.main_instruction);
+                return DialogResponder::reject(creation, XDialogError::SystemError(error));
+            }
+        };
+
+        let responder = DialogResponder::open(creation);
+        let Some(response) = response else {
+            self.lock().open.insert(id, responder);
+            if let Some(duration) = timeout {
+                let mock = self.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(duration);
+                    if let Some(responder) = mock.lock().open.remove(&id) {
+                        responder.respond(XDialogResponse { result: XDialogResult::TimeoutElapsed, ..base });
+                    }
+                });
+            }
+            return;
+        };
+
+        // A progress dialog runs its callbacks first, as a real one would for the click or close,
+        // and they can keep it open. The lock isn't held while they run, as they update the dialog
+        // through this backend.
+        if let Some(mut callbacks) = callbacks {
+            let keep_open = match response.result {
+                _ if closing || response.result == XDialogResult::WindowClosed => callbacks.close_requested(id),
+                XDialogResult::ButtonPressed(index) => callbacks.button_clicked(id, index).unwrap_or(false),
+                _ => false,
+            };
+            if keep_open {
+                self.lock().open.insert(id, responder);
+                return;
+            }
+        }
+        responder.respond(response);
+    }
+}
+
+impl MockState {
+    fn close_all(&mut self) {
+        for (_, responder) in self.open.drain() {
+            responder.respond(XDialogResult::WindowClosed);
+        }
+    }
+}
+
+impl DialogRequestHandler for MockBackend {
+    fn send(&self, message: DialogMessageRequest) -> Result<(), XDialogError> {
+        self.handle(message);
+        Ok(())
+    }
+}
+
+impl XDialogBackend for MockBackend {
+    fn run_loop(&mut self, requests: Receiver<DialogMessageRequest>, _theme: XDialogTheme) {
+        while let Ok(message) = requests.recv() {
+            let exit = matches!(message, DialogMessageRequest::ExitEventLoop);
+            self.handle(message);
+            if exit {
+                break;
+            }
+        }
+    }
+}
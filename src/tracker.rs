@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// How long a [`ProgressTracker`] waits between updates of the dialog by default.
+const DEFAULT_UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+/// The span of recent samples the transfer rate is averaged over.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// What a [`ProgressTracker`] counts, which decides how its status line is formatted.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ProgressUnit {
+    /// Bytes, shown as sizes (eg. "12.3 MB of 80 MB")
+    Bytes,
+    /// Items (eg. files), shown as counts (eg. "120 of 800 items")
+    Items,
+}
+
+/// Drives a progress dialog from a running count of bytes or items: it sets the progress value and
+/// the [status line](ProgressDialogProxy::set_status) to eg. "12.3 MB of 80 MB — 2 min remaining",
+/// estimating the time remaining from the rate averaged over the last few seconds.
+///
+/// Updating the tracker is cheap, so it can be called for every chunk written: the dialog is only
+/// updated every 250 ms (see [`with_update_interval`](Self::with_update_interval)), and once more
+/// as soon as the count reaches the total.
+///
+/// The tracker owns the proxy, so dropping it closes the dialog. Use [`proxy`](Self::proxy) to
+/// change anything else about the dialog, or [`into_proxy`](Self::into_proxy) to take it back.
+///
+/// ### Example
+/// ```rust,no_run
+/// use xdialog::*;
+///
+/// # fn run() {
+/// let progress = show_progress("My App", "Downloading update", "", XDialogIcon::Information).unwrap();
+/// let mut tracker = ProgressTracker::new(progress, ProgressUnit::Bytes, 80_000_000);
+/// # let chunks: Vec<Vec<u8>> = vec![];
+/// for chunk in chunks {
+///     // ... write the chunk
+///     tracker.advance(chunk.len() as u64).unwrap();
+/// }
+/// # }
+/// ```
+pub struct ProgressTracker {
+    proxy: ProgressDialogProxy,
+    unit: ProgressUnit,
+    total: u64,
+    done: u64,
+    interval: Duration,
+    last_update: Option<Instant>,
+    /// Whether the dialog was last switched to indeterminate (for an unknown total), to only do so once.
+    indeterminate: bool,
+    /// Whether the count has reached the total, so the update forced on reaching it isn't repeated
+    /// by a caller which keeps reporting the final count.
+    finished: bool,
+    /// `(time, done)` at each dialog update within the last `RATE_WINDOW`, oldest first.
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ProgressTracker {
+    /// Track `total` bytes or items on the dialog behind `proxy`. A `total` of 0 means it is
+    /// unknown: the progress bar is indeterminate and no time remaining is shown.
+    pub fn new(proxy: ProgressDialogProxy, unit: ProgressUnit, total: u64) -> Self {
+        ProgressTracker {
+            proxy,
+            unit,
+            total,
+            done: 0,
+            interval: DEFAULT_UPDATE_INTERVAL,
+            last_update: None,
+            indeterminate: false,
+            finished: false,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Sets how often the dialog is updated at most.
+    pub fn with_update_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Changes the total, eg. once a download's size becomes known.
+    pub fn set_total(&mut self, total: u64) {
+        self.total = total;
+    }
+
+    /// Sets the count done so far, updating the dialog if the update interval has elapsed.
+    pub fn set_position(&mut self, done: u64) -> Result<(), XDialogError> {
+        self.set_position_at(done, Instant::now())
+    }
+
+    /// Adds `delta` to the count done so far. See [`set_position`](Self::set_position).
+    pub fn advance(&mut self, delta: u64) -> Result<(), XDialogError> {
+        self.set_position(self.done.saturating_add(delta))
+    }
+
+    /// The count done so far.
+    pub fn position(&self) -> u64 {
+        self.done
+    }
+
+    /// The average rate, in bytes or items per second, over the last few seconds. `None` until
+    /// there are two updates to compare.
+    pub fn rate(&self) -> Option<f64> {
+        let (&(start, start_done), &(end, end_done)) = (self.samples.front()?, self.samples.back()?);
+        let secs = end.duration_since(start).as_secs_f64();
+        (secs > 0.0).then(|| end_done.saturating_sub(start_done) as f64 / secs)
+    }
+
+    /// The estimated time remaining at the current [`rate`](Self::rate). `None` without a known
+    /// total or while nothing is moving.
+    pub fn remaining(&self) -> Option<Duration> {
+        let rate = self.rate().filter(|r| *r > 0.0)?;
+        if self.total == 0 {
+            return None;
+        }
+        let left = self.total.saturating_sub(self.done) as f64;
+        Some(Duration::from_secs_f64(left / rate))
+    }
+
+    /// The status line shown in the dialog, eg. "12.3 MB of 80 MB — 2 min remaining".
+    pub fn status(&self) -> String {
+        let amount = match (self.unit, self.total) {
+            (ProgressUnit::Bytes, 0) => format_size(self.done),
+            (ProgressUnit::Bytes, total) => format!("{} of {}", format_size(self.done), format_size(total)),
+            (ProgressUnit::Items, 0) => format!("{} items", self.done),
+            (ProgressUnit::Items, total) => format!("{} of {} items", self.done, total),
+        };
+        match self.remaining() {
+            Some(remaining) if self.done < self.total => format!("{} — {} remaining", amount, format_duration(remaining)),
+            _ => amount,
+        }
+    }
+
+    /// The proxy of the tracked dialog.
+    pub fn proxy(&self) -> &ProgressDialogProxy {
+        &self.proxy
+    }
+
+    /// Stops tracking and returns the proxy of the dialog, leaving it open.
+    pub fn into_proxy(self) -> ProgressDialogProxy {
+        self.proxy
+    }
+
+    fn set_position_at(&mut self, done: u64, now: Instant) -> Result<(), XDialogError> {
+        self.done = done;
+        let finished = self.total > 0 && done >= self.total;
+        let just_finished = finished && !self.finished;
+        self.finished = finished;
+        let due = self.last_update.is_none_or(|last| now.duration_since(last) >= self.interval);
+        if !due && !just_finished {
+            return Ok(());
+        }
+        self.last_update = Some(now);
+        self.record_sample(now);
+        self.update_dialog()
+    }
+
+    fn record_sample(&mut self, now: Instant) {
+        self.samples.push_back((now, self.done));
+        while self.samples.len() > 2 && self.samples.front().is_some_and(|(t, _)| now.duration_since(*t) > RATE_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    fn update_dialog(&mut self) -> Result<(), XDialogError> {
+        if self.total == 0 {
+            if !self.indeterminate {
+                self.proxy.set_indeterminate()?;
+                self.indeterminate = true;
+            }
+        } else {
+            self.indeterminate = false;
+            self.proxy.set_value(self.done as f32 / self.total as f32)?;
+        }
+        self.proxy.set_status(self.status())
+    }
+}
+
+/// Formats a byte count with decimal units, as file managers do: "512 bytes", "12.3 MB", "80 MB".
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["kB", "MB", "GB", "TB", "PB"];
+    if bytes < 1000 {
+        return format!("{} bytes", bytes);
+    }
+    // One decimal place below 100, dropping a trailing ".0". The unit is chosen after rounding, so
+    // 999,950 bytes is "1 MB" rather than "1000 kB".
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    let rounded = loop {
+        let rounded = if value < 100.0 { (value * 10.0).round() / 10.0 } else { value.round() };
+        if rounded < 1000.0 || unit == UNITS.len() - 1 {
+            break rounded;
+        }
+        value /= 1000.0;
+        unit += 1;
+    };
+    let text = if rounded < 100.0 { format!("{:.1}", rounded) } else { format!("{:.0}", rounded) };
+    format!("{} {}", text.trim_end_matches(".0"), UNITS[unit])
+}
+
+/// Formats a duration as a rough time remaining: "45 sec", "2 min", "1 h 5 min".
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{} sec", secs.max(1))
+    } else if secs < 3600 {
+        format!("{} min", secs.div_ceil(60))
+    } else {
+        let minutes = secs.div_ceil(60);
+        match minutes % 60 {
+            0 => format!("{} h", minutes / 60),
+            m => format!("{} h {} min", minutes / 60, m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tracker of a dialog which isn't shown, without turning on silent mode for the other tests.
+    fn silent_tracker(unit: ProgressUnit, total: u64) -> ProgressTracker {
+        ProgressTracker::new(ProgressDialogProxy::silent(get_next_id()), unit, total)
+    }
+
+    #[test]
+    fn sizes_use_decimal_units() {
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1_000), "1 kB");
+        assert_eq!(format_size(12_345_678), "12.3 MB");
+        assert_eq!(format_size(80_000_000), "80 MB");
+        assert_eq!(format_size(123_456_789), "123 MB");
+        assert_eq!(format_size(2_500_000_000), "2.5 GB");
+
+        // Rounding up to the next unit (or to 100, with no decimal) changes the format.
+        assert_eq!(format_size(99_960), "100 kB");
+        assert_eq!(format_size(999_499), "999 kB");
+        assert_eq!(format_size(999_950), "1 MB");
+        assert_eq!(format_size(999_999_999), "1 GB");
+    }
+
+    #[test]
+    fn durations_are_rounded_up_to_minutes() {
+        assert_eq!(format_duration(Duration::from_millis(300)), "1 sec");
+        assert_eq!(format_duration(Duration::from_secs(45)), "45 sec");
+        assert_eq!(format_duration(Duration::from_secs(61)), "2 min");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1 h");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1 h 5 min");
+    }
+
+    #[test]
+    fn rate_and_remaining_time_come_from_recent_updates() {
+        let mut tracker = silent_tracker(ProgressUnit::Bytes, 80_000_000);
+        let start = Instant::now();
+        tracker.set_position_at(0, start).unwrap();
+        assert_eq!(tracker.rate(), None);
+
+        tracker.set_position_at(12_300_000, start + Duration::from_secs(12)).unwrap();
+        assert_eq!(tracker.rate(), Some(1_025_000.0));
+        assert_eq!(tracker.status(), "12.3 MB of 80 MB — 2 min remaining");
+
+        // A slow phase only counts for as long as it is within the averaging window.
+        for s in 13..=30 {
+            tracker.set_position_at(12_300_000 + (s - 12) * 100_000, start + Duration::from_secs(s)).unwrap();
+        }
+        let rate = tracker.rate().unwrap();
+        assert!((rate - 100_000.0).abs() < 1.0, "{}", rate);
+    }
+
+    #[test]
+    fn updates_are_throttled_except_the_last() {
+        let mut tracker = silent_tracker(ProgressUnit::Items, 10).with_update_interval(Duration::from_secs(1));
+        let start = Instant::now();
+        tracker.set_position_at(1, start).unwrap();
+        tracker.set_position_at(2, start + Duration::from_millis(100)).unwrap();
+        assert_eq!(tracker.samples.len(), 1);
+        assert_eq!(tracker.position(), 2);
+
+        tracker.set_position_at(10, start + Duration::from_millis(200)).unwrap();
+        assert_eq!(tracker.samples.len(), 2);
+        assert_eq!(tracker.status(), "10 of 10 items");
+
+        // Reporting the final count again is throttled like any other update.
+        tracker.set_position_at(10, start + Duration::from_millis(300)).unwrap();
+        assert_eq!(tracker.samples.len(), 2);
+        tracker.set_position_at(10, start + Duration::from_millis(1300)).unwrap();
+        assert_eq!(tracker.samples.len(), 3);
+    }
+
+    #[test]
+    fn unknown_totals_show_only_the_amount() {
+        let mut tracker = silent_tracker(ProgressUnit::Bytes, 0);
+        let start = Instant::now();
+        tracker.set_position_at(0, start).unwrap();
+        tracker.set_position_at(1_500, start + Duration::from_secs(1)).unwrap();
+        assert_eq!(tracker.remaining(), None);
+        assert_eq!(tracker.status(), "1.5 kB");
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::Thread;
+
+use xdialog::*;
+
+/// A minimal executor, to show the async API doesn't depend on any particular runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn async_message_dialogs_work_in_silent_mode() {
+    set_silent_mode(true);
+
+    block_on(async {
+        show_message_info_ok_async("Silent", "Test", "Body").await.unwrap();
+        show_message_warn_ok_async("Silent", "Test", "Body").await.unwrap();
+        show_message_error_ok_async("Silent", "Test", "Body").await.unwrap();
+        assert!(!show_message_ok_cancel_async("Silent", "Test", "Body", XDialogIcon::Information).await.unwrap());
+        assert!(!show_message_yes_no_async("Silent", "Test", "Body", XDialogIcon::Warning).await.unwrap());
+        assert!(!show_message_retry_cancel_async("Silent", "Test", "Body", XDialogIcon::Error).await.unwrap());
+
+        let options = XDialogOptions {
+            title: "Silent".to_string(),
+            verification_text: "Don't ask me again".to_string(),
+            verification_checked: true,
+            ..Default::default()
+        };
+        let response = show_message_ex_async(options, None).await.unwrap();
+        assert_eq!(response.result, XDialogResult::SilentMode);
+        assert_eq!(response.checked, Some(true));
+    });
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn silent_progress_wait_closed_returns_immediately() {
+    set_silent_mode(true);
+
+    let progress = show_progress("Silent", "Test", "Body", XDialogIcon::Information).unwrap();
+    block_on(progress.wait_closed_async());
+}
@@ -0,0 +1,32 @@
+#![cfg(not(target_os = "macos"))]
+
+use xdialog::*;
+
+#[test]
+#[ntest::timeout(2000)]
+fn auto_answered_dialogs_report_the_chosen_button_without_a_window() {
+    XDialogBuilder::new().with_auto_answer(XDialogAutoAnswer::Button("Yes".to_string())).run(run);
+}
+
+fn run() {
+    assert!(show_message_yes_no("Installer", "Install now?", "", XDialogIcon::Question).unwrap());
+
+    // A dialog without the named button is dismissed.
+    let options = XDialogOptions {
+        title: "Installer".to_string(),
+        buttons: vec!["Cancel".to_string(), "OK".to_string()],
+        cancel_button: Some(0),
+        ..Default::default()
+    };
+    let response = show_input(options.clone(), "C:\\Program Files").unwrap();
+    assert_eq!((response.result, response.text.as_deref()), (XDialogResult::ButtonPressed(0), Some("C:\\Program Files")));
+
+    // Progress dialogs aren't shown.
+    let progress = show_progress("Installer", "Installing", "", XDialogIcon::Information).unwrap();
+    progress.set_value(0.5).unwrap();
+    progress.close().unwrap();
+
+    // Policies set in code replace the builder's.
+    set_auto_answer(Some(XDialogAutoAnswer::Default));
+    assert_eq!(show_message(XDialogOptions { default_button: Some(1), ..options }, None).unwrap(), XDialogResult::ButtonPressed(1));
+}
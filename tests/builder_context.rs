@@ -0,0 +1,15 @@
+#![cfg(not(target_os = "macos"))]
+
+use xdialog::*;
+
+#[test]
+#[ntest::timeout(2000)]
+fn run_with_context_passes_context_and_returns_result() {
+    let args = vec!["--product".to_string(), "My App".to_string()];
+    let result = XDialogBuilder::new().run_with_context(args, run);
+    assert_eq!(result, "My App");
+}
+
+fn run(args: Vec<String>) -> String {
+    args[1].clone()
+}
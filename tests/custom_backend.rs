@@ -0,0 +1,52 @@
+#![cfg(not(target_os = "macos"))]
+
+use std::sync::mpsc::Receiver;
+use xdialog::*;
+
+/// Presses the default button of message dialogs, submits input dialogs with their initial text
+/// and refuses to show progress dialogs.
+struct ScriptedBackend;
+
+impl XDialogBackend for ScriptedBackend {
+    fn run_loop(&mut self, requests: Receiver<DialogMessageRequest>, theme: XDialogTheme) {
+        assert_eq!(theme, XDialogTheme::Dark);
+        while let Ok(request) = requests.recv() {
+            match request {
+                DialogMessageRequest::ExitEventLoop => break,
+                DialogMessageRequest::ShowMessageWindow(_id, options, creation) => {
+                    let index = options.default_button_index().unwrap();
+                    DialogResponder::open(creation).respond(XDialogResult::ButtonPressed(index));
+                }
+                DialogMessageRequest::ShowInputWindow(_id, _options, mut field, creation) => {
+                    let text = field.initial_text().to_uppercase();
+                    assert!(field.validate(&text).is_ok());
+                    let mut response = XDialogResponse::from(XDialogResult::ButtonPressed(0));
+                    response.text = Some(text);
+                    DialogResponder::open(creation).respond(response);
+                }
+                DialogMessageRequest::ShowProgressWindow(_id, _options, creation, _callbacks) => {
+                    DialogResponder::reject(creation, XDialogError::SystemError("no progress".to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn custom_backend_receives_requests_and_answers_them() {
+    XDialogBuilder::new().with_theme(XDialogTheme::Dark).with_backend(ScriptedBackend).run(run);
+}
+
+fn run() {
+    let yes = show_message_yes_no("Custom", "Continue?", "", XDialogIcon::Question).unwrap();
+    assert!(yes);
+
+    let options = XDialogOptions { title: "Custom".to_string(), buttons: vec!["OK".to_string()], ..Default::default() };
+    let response = show_input(options, "xdialog").unwrap();
+    assert_eq!(response.text.as_deref(), Some("XDIALOG"));
+
+    let progress = show_progress("Custom", "Working", "", XDialogIcon::None);
+    assert!(matches!(progress, Err(XDialogError::SystemError(_))));
+}
@@ -0,0 +1,31 @@
+use xdialog::*;
+
+#[test]
+#[ntest::timeout(2000)]
+fn input_dialogs_work_in_silent_mode() {
+    set_silent_mode(true);
+
+    let options = XDialogOptions {
+        title: "Silent".to_string(),
+        main_instruction: "Test".to_string(),
+        message: "Body".to_string(),
+        icon: XDialogIcon::None,
+        buttons: vec!["Cancel".to_string(), "OK".to_string()],
+        ..Default::default()
+    };
+
+    let response = show_input(options.clone(), "initial").unwrap();
+    assert_eq!(response.result, XDialogResult::SilentMode);
+    assert_eq!(response.text.as_deref(), Some("initial"));
+
+    // The validator never runs when no dialog is shown.
+    let response = show_input_with_validation(options.clone(), "", |_| Err("never called".to_string())).unwrap();
+    assert_eq!(response.result, XDialogResult::SilentMode);
+    assert_eq!(response.text.as_deref(), Some(""));
+
+    // A password dialog never reports plain text, and has no secret when nothing was entered.
+    let response = show_password(options).unwrap();
+    assert_eq!(response.result, XDialogResult::SilentMode);
+    assert_eq!(response.text, None);
+    assert_eq!(response.secret, None);
+}
@@ -27,6 +27,7 @@ fn test_message_timeout() {
             message: "This dialog should auto-close after 1 second".to_string(),
             icon: XDialogIcon::Information,
             buttons: vec!["OK".to_string()],
+            ..Default::default()
         },
         Some(timeout),
     )
@@ -18,3 +18,65 @@ fn message_dialogs_work_in_silent_mode() {
     let retry_cancel = show_message_retry_cancel("Silent", "Test", "Body", XDialogIcon::Error).unwrap();
     assert!(!retry_cancel);
 }
+
+#[test]
+#[ntest::timeout(2000)]
+fn verification_checkbox_keeps_its_initial_state_in_silent_mode() {
+    set_silent_mode(true);
+
+    let mut options = XDialogOptions {
+        title: "Silent".to_string(),
+        main_instruction: "Test".to_string(),
+        buttons: vec!["OK".to_string()],
+        ..Default::default()
+    };
+    let response = show_message_ex(options.clone(), None).unwrap();
+    assert_eq!(response.result, XDialogResult::SilentMode);
+    assert_eq!(response.checked, None);
+
+    options.verification_text = "Don't ask me again".to_string();
+    options.verification_checked = true;
+    let response = show_message_ex(options, None).unwrap();
+    assert_eq!(response.result, XDialogResult::SilentMode);
+    assert_eq!(response.checked, Some(true));
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn radio_group_reports_its_default_in_silent_mode() {
+    set_silent_mode(true);
+
+    let mut options = XDialogOptions {
+        title: "Silent".to_string(),
+        main_instruction: "Test".to_string(),
+        buttons: vec!["OK".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(show_message_ex(options.clone(), None).unwrap().radio_index, None);
+
+    options.radio_buttons = vec!["Update now".to_string(), "Update on exit".to_string(), "Skip this version".to_string()];
+    options.default_radio_button = 1;
+    assert_eq!(show_message_ex(options.clone(), None).unwrap().radio_index, Some(1));
+
+    // An out-of-range default selects the last choice.
+    options.default_radio_button = 10;
+    assert_eq!(show_message_ex(options, None).unwrap().radio_index, Some(2));
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn nonblocking_handle_holds_the_silent_response() {
+    set_silent_mode(true);
+
+    let options = XDialogOptions {
+        title: "Silent".to_string(),
+        main_instruction: "Test".to_string(),
+        buttons: vec!["OK".to_string()],
+        ..Default::default()
+    };
+    let mut handle = show_message_nonblocking(options).unwrap();
+    assert_eq!(handle.try_result().unwrap().unwrap().result, XDialogResult::SilentMode);
+    assert_eq!(handle.wait_timeout(std::time::Duration::from_millis(10)).unwrap().unwrap().result, XDialogResult::SilentMode);
+    handle.close().unwrap();
+    assert_eq!(handle.wait().unwrap().result, XDialogResult::SilentMode);
+}
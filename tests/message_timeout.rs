@@ -20,6 +20,7 @@ fn run() {
             message: "This dialog should auto-close after 1 second".to_string(),
             icon: XDialogIcon::Information,
             buttons: vec!["OK".to_string()],
+            ..Default::default()
         },
         Some(timeout),
     )
@@ -0,0 +1,86 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use xdialog::testing::*;
+use xdialog::*;
+
+fn options(buttons: &[&str]) -> XDialogOptions {
+    XDialogOptions {
+        title: "Updater".to_string(),
+        buttons: buttons.iter().map(|b| b.to_string()).collect(),
+        cancel_button: Some(0),
+        ..Default::default()
+    }
+}
+
+// The handler can only be installed once per process, so every scenario runs in this one test.
+#[test]
+#[ntest::timeout(2000)]
+fn mock_backend_answers_from_the_script_and_records_requests() {
+    let mock = MockBackend::new();
+    init_with_handler(mock.clone());
+
+    // Buttons by label or index, and closing the window, which reports the cancel button.
+    mock.answer(MockAnswer::PressButton("Yes".to_string()))
+        .answer(MockAnswer::PressButtonIndex(0))
+        .answer(MockAnswer::CloseWindow);
+    assert!(show_message_yes_no("Updater", "Update now?", "", XDialogIcon::Question).unwrap());
+    assert!(!show_message_yes_no("Updater", "Update now?", "", XDialogIcon::Question).unwrap());
+    assert_eq!(show_message(options(&["Cancel", "OK"]), None).unwrap(), XDialogResult::ButtonPressed(0));
+
+    // Input dialogs report their initial text, unless the answer says otherwise.
+    mock.answer(MockAnswer::PressButton("OK".to_string()));
+    let response = show_input(options(&["Cancel", "OK"]), "1.2.3").unwrap();
+    assert_eq!((response.result, response.text.as_deref()), (XDialogResult::ButtonPressed(1), Some("1.2.3")));
+    assert!(matches!(
+        mock.requests().last(),
+        Some(MockRequest::ShowInput { initial_text, masked: false, .. }) if initial_text == "1.2.3"
+    ));
+
+    // A scripted timeout, and the caller's own shorter timeout.
+    mock.answer(MockAnswer::TimeOutAfter(Duration::from_millis(20))).answer(MockAnswer::LeaveOpen);
+    assert_eq!(show_message(options(&["OK"]), None).unwrap(), XDialogResult::TimeoutElapsed);
+    assert_eq!(show_message(options(&["OK"]), Some(Duration::from_millis(20))).unwrap(), XDialogResult::TimeoutElapsed);
+    assert!(mock.open_dialogs().is_empty());
+
+    // Dialogs with no answer, or without the scripted button (by label or index), fail to open.
+    assert!(matches!(show_message(options(&["OK"]), None), Err(XDialogError::SystemError(_))));
+    mock.answer(MockAnswer::PressButton("Retry".to_string()));
+    assert!(matches!(show_message(options(&["OK"]), None), Err(XDialogError::SystemError(_))));
+    mock.answer(MockAnswer::PressButtonIndex(1));
+    assert!(matches!(show_message(options(&["OK"]), None), Err(XDialogError::SystemError(_))));
+
+    // Progress dialogs stay open without an answer, and their updates are recorded.
+    mock.reset();
+    let progress = show_progress("Updater", "Downloading", "", XDialogIcon::Information).unwrap();
+    progress.set_value(0.25).unwrap();
+    progress.bar(0).set_state(ProgressBarState::Paused).unwrap();
+    progress.set_status("update.zip").unwrap();
+    assert!(!progress.is_closed());
+    progress.close().unwrap();
+    assert_eq!(
+        mock.progress_updates(),
+        [
+            ProgressUpdate::Value { bar: 0, value: 0.25 },
+            ProgressUpdate::State { bar: 0, state: ProgressBarState::Paused },
+            ProgressUpdate::Status("update.zip".to_string()),
+        ]
+    );
+
+    // A scripted click runs the progress dialog's button callback, which keeps it open here.
+    let clicks = Arc::new(AtomicUsize::new(0));
+    let counter = clicks.clone();
+    let callbacks = ProgressCallbacks::new().on_button(move |_, proxy| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        proxy.set_text("Pausing...").unwrap();
+        true
+    });
+    mock.answer(MockAnswer::PressButton("Pause".to_string()));
+    let progress = show_progress_with_callbacks(options(&["Pause"]), callbacks).unwrap();
+    assert_eq!(clicks.load(Ordering::SeqCst), 1);
+    assert!(mock.progress_updates().contains(&ProgressUpdate::Text("Pausing...".to_string())));
+    assert!(!progress.is_closed());
+}
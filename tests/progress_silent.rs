@@ -16,7 +16,79 @@ fn progress_proxy_works_in_silent_mode() {
     progress.set_value(0.5).unwrap();
     progress.set_text("Updating...").unwrap();
     progress.set_indeterminate().unwrap();
+    progress.set_state(ProgressBarState::Error).unwrap();
+    progress.set_status("C:\\Program Files\\My App\\resources\\app.asar").unwrap();
+    progress.set_main_instruction("Installing").unwrap();
+    progress.set_icon(XDialogIcon::Error).unwrap();
+    progress.set_button_text(0, "Close").unwrap();
     progress.close().unwrap();
     progress.close().unwrap(); // double close should also be fine
     // Drop will call close() again
 }
+
+#[test]
+#[ntest::timeout(2000)]
+fn silent_progress_reports_programmatic_close() {
+    set_silent_mode(true);
+
+    let progress = show_progress("Silent Test", "Testing close reason", "Body", XDialogIcon::Information).unwrap();
+    assert!(!progress.is_closed());
+    assert_eq!(progress.close_reason(), None);
+    assert_eq!(progress.wait_closed(), None); // returns immediately in silent mode
+
+    progress.close().unwrap();
+    assert!(progress.is_closed());
+    assert_eq!(progress.close_reason(), Some(ProgressCloseReason::Programmatic));
+    assert_eq!(progress.wait_closed(), Some(ProgressCloseReason::Programmatic));
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn silent_progress_accepts_callbacks() {
+    set_silent_mode(true);
+
+    let options = XDialogOptions { title: "Silent Test".to_string(), buttons: vec!["Cancel".to_string()], ..Default::default() };
+    let callbacks = ProgressCallbacks::new()
+        .on_button(|_, _| panic!("callbacks are never invoked in silent mode"))
+        .on_close_requested(|_| panic!("callbacks are never invoked in silent mode"));
+    let progress = show_progress_with_callbacks(options, callbacks).unwrap();
+    progress.set_value(0.5).unwrap();
+    progress.close().unwrap();
+    assert_eq!(progress.close_reason(), Some(ProgressCloseReason::Programmatic));
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn silent_progress_accepts_multiple_bars() {
+    set_silent_mode(true);
+
+    let options = XDialogOptions {
+        title: "Silent Test".to_string(),
+        progress_bars: vec!["Current file".to_string(), "Overall".to_string()],
+        ..Default::default()
+    };
+    let progress = show_progress_ex(options).unwrap();
+    progress.bar(0).set_value(0.5).unwrap();
+    progress.bar(1).set_indeterminate().unwrap();
+    progress.bar(1).set_state(ProgressBarState::Paused).unwrap();
+    progress.bar(1).set_caption("Overall (2 of 3)").unwrap();
+    progress.bar(7).set_value(1.0).unwrap(); // no such bar, ignored
+    progress.close().unwrap();
+}
+
+#[test]
+#[ntest::timeout(2000)]
+fn silent_progress_accepts_checklist_updates() {
+    set_silent_mode(true);
+
+    let options = XDialogOptions {
+        title: "Silent Test".to_string(),
+        checklist: vec!["Download".to_string(), "Verify".to_string(), "Apply".to_string()],
+        ..Default::default()
+    };
+    let progress = show_progress_ex(options).unwrap();
+    progress.start_step(0).unwrap();
+    progress.start_step(1).unwrap();
+    progress.set_step_state(1, ChecklistStepState::Failed).unwrap();
+    progress.close().unwrap();
+}
@@ -578,6 +578,7 @@ fn run_all_captures() {
             message: "This is a test message for visual regression testing.".to_string(),
             icon: XDialogIcon::Information,
             buttons: vec!["OK".to_string()],
+            ..Default::default()
         },
         Some(Duration::from_secs(DIALOG_TIMEOUT_SECS)),
     );